@@ -0,0 +1,301 @@
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::{
+    adapters::{RV32_REGISTER_AS, check_rd_write, check_register_accesses},
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    execution::{DEFAULT_PC_STEP, ExecutionBridge, ExecutionError, ExecutionState},
+    instructions::Instruction,
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, BasicAdapterInterface, MinimalInstruction,
+        VmAdapterAir, VmAdapterChip, VmAdapterInterface,
+    },
+    memory::{
+        MemoryAddress, MemoryBridge, MemoryController, MemoryReadAuxCols, MemoryReadRecord,
+        MemoryWriteAuxCols, MemoryWriteRecord,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    utils::not,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/adapters/alu.rs
+// for full implementation details.
+
+/// The number of bits of an immediate `rs2`: a 16-bit value sign-extended to 24 bits.
+pub const RV32_IMM_BITS: usize = 24;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32BaseAluAdapterCols<T> {
+        pub from_state: ExecutionState<T>,
+        pub rd_ptr: T,
+        pub rs1_ptr: T,
+        /// Pointer if rs2 was a read, immediate value otherwise
+        pub rs2: T,
+        /// 1 if rs2 was a read, 0 if an immediate
+        pub rs2_as: T,
+        pub reads_aux: [MemoryReadAuxCols<T>; 2],
+        pub writes_aux: MemoryWriteAuxCols<T, RV32_REGISTER_NUM_LIMBS>,
+    }
+}
+
+/// Reads instructions of the form OP a, b, c, d, e where \[a:4\]_d = \[b:4\]_d op \[c:4\]_e.
+/// Operand d can only be 1, and e can be either 1 (for register reads) or 0 (when c is an
+/// immediate).
+#[derive(Clone, Copy)]
+pub struct Rv32BaseAluAdapterAir {
+    pub memory_bridge: MemoryBridge,
+    pub execution_bridge: ExecutionBridge,
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+}
+
+impl BaseAir<F> for Rv32BaseAluAdapterAir {
+    fn width(&self) -> usize {
+        Rv32BaseAluAdapterCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> VmAdapterAir<AB> for Rv32BaseAluAdapterAir {
+    type Interface = BasicAdapterInterface<
+        AB::Expr,
+        MinimalInstruction<AB::Expr>,
+        2,
+        1,
+        RV32_REGISTER_NUM_LIMBS,
+        RV32_REGISTER_NUM_LIMBS,
+    >;
+
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    ) {
+        let local: &Rv32BaseAluAdapterCols<AB::Var> = local.borrow();
+        let MinimalInstruction { is_valid, opcode } = ctx.instruction;
+        let [rs1_data, rs2_data] = ctx.reads;
+        let [rd_data] = ctx.writes;
+        let timestamp = local.from_state.timestamp;
+        let mut timestamp_delta: usize = 0;
+        let mut timestamp_pp = || {
+            timestamp_delta += 1;
+            timestamp + AB::F::from_canonical_usize(timestamp_delta - 1)
+        };
+
+        // If rs2 is an immediate value, constrain that:
+        // 1. It's a 16-bit two's complement integer (stored in rs2_limbs[0] and rs2_limbs[1])
+        // 2. It's properly transformed into 4 limbs: rs2_limbs[2] and rs2_limbs[3] are both 0 or
+        //    both RV32_LIMB_MAX depending on the sign
+        let rs2_sign = rs2_data[2].clone();
+        let rs2_imm = rs2_data[0].clone()
+            + rs2_data[1].clone() * AB::F::from_canonical_u32(1 << RV32_CELL_BITS)
+            + rs2_sign.clone() * AB::F::from_canonical_u32(1 << (2 * RV32_CELL_BITS));
+        builder.assert_bool(local.rs2_as);
+        // A register can only be read by a valid row.
+        builder.when(local.rs2_as).assert_one(is_valid.clone());
+        let mut rs2_imm_when = builder.when(not::<AB::Expr>(local.rs2_as));
+        rs2_imm_when.assert_eq(local.rs2, rs2_imm);
+        rs2_imm_when.assert_eq(rs2_sign.clone(), rs2_data[3].clone());
+        rs2_imm_when.assert_zero(
+            rs2_sign.clone() * (AB::Expr::from_canonical_u32(RV32_LIMB_MAX) - rs2_sign),
+        );
+        self.bitwise_lookup_bus
+            .send_range::<AB::Expr>(rs2_data[0].clone(), rs2_data[1].clone())
+            .eval(builder, is_valid.clone() - local.rs2_as);
+
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), local.rs1_ptr),
+                rs1_data,
+                timestamp_pp(),
+                &local.reads_aux[0],
+            )
+            .eval(builder, is_valid.clone());
+        // An immediate rs2 still takes a timestamp so that the timestamp change is constant.
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), local.rs2),
+                rs2_data,
+                timestamp_pp(),
+                &local.reads_aux[1],
+            )
+            .eval(builder, local.rs2_as);
+        self.memory_bridge
+            .write(
+                MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), local.rd_ptr),
+                rd_data,
+                timestamp_pp(),
+                &local.writes_aux,
+            )
+            .eval(builder, is_valid.clone());
+
+        self.execution_bridge
+            .execute_and_increment_pc(
+                opcode,
+                [
+                    local.rd_ptr.into(),
+                    local.rs1_ptr.into(),
+                    local.rs2.into(),
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    local.rs2_as.into(),
+                ],
+                local.from_state,
+                AB::F::from_canonical_usize(timestamp_delta),
+            )
+            .eval(builder, is_valid);
+    }
+
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var {
+        let cols: &Rv32BaseAluAdapterCols<AB::Var> = local.borrow();
+        cols.from_state.pc
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32BaseAluReadRecord {
+    /// Read register value from address space d=1
+    pub rs1: MemoryReadRecord,
+    /// Read register value of rs2, or `None` if rs2 is an immediate
+    pub rs2: Option<MemoryReadRecord>,
+    /// Immediate value of rs2, or 0 if rs2 is a register
+    pub rs2_imm: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32BaseAluWriteRecord {
+    pub from_state: ExecutionState<u32>,
+    /// Write to destination register
+    pub rd: MemoryWriteRecord,
+}
+
+/// Reads `rs1` and either `rs2` or an immediate, and writes the output of the core to `rd`.
+pub struct Rv32BaseAluAdapterChip {
+    pub air: Rv32BaseAluAdapterAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32BaseAluAdapterChip {
+    pub fn new(
+        memory_bridge: MemoryBridge,
+        execution_bridge: ExecutionBridge,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+    ) -> Self {
+        Self {
+            air: Rv32BaseAluAdapterAir {
+                memory_bridge,
+                execution_bridge,
+                bitwise_lookup_bus: bitwise_lookup_chip.bus(),
+            },
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+impl VmAdapterChip for Rv32BaseAluAdapterChip {
+    type ReadRecord = Rv32BaseAluReadRecord;
+    type WriteRecord = Rv32BaseAluWriteRecord;
+    type Air = Rv32BaseAluAdapterAir;
+    type Interface = BasicAdapterInterface<
+        F,
+        MinimalInstruction<F>,
+        2,
+        1,
+        RV32_REGISTER_NUM_LIMBS,
+        RV32_REGISTER_NUM_LIMBS,
+    >;
+
+    fn check(
+        &self,
+        memory: &MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        let Instruction { a, b, c, e, .. } = *instruction;
+        let pc = from_state.pc;
+        match e.as_canonical_u32() == RV32_REGISTER_AS {
+            true => check_register_accesses(memory, pc, &[a, b, c], 3)?,
+            false => check_register_accesses(memory, pc, &[a, b], 3)?,
+        }
+        check_rd_write(instruction, pc)
+    }
+
+    fn preprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord) {
+        let Instruction { b, c, d, e, .. } = *instruction;
+        assert_eq!(d.as_canonical_u32(), RV32_REGISTER_AS, "rd and rs1 must be registers");
+        let e = e.as_canonical_u32();
+        assert!(e == 0 || e == RV32_REGISTER_AS, "rs2 must be a register or an immediate");
+
+        let rs1 = memory.read(RV32_REGISTER_AS, b.as_canonical_u32());
+        let (rs2, rs2_data, rs2_imm) = if e == 0 {
+            let imm = c.as_canonical_u32();
+            let sign = imm >> (2 * RV32_CELL_BITS);
+            assert!(
+                imm < (1 << RV32_IMM_BITS) && (sign == 0 || sign == RV32_LIMB_MAX),
+                "immediate {imm:#x} is not a sign-extended 16-bit value"
+            );
+            // The immediate takes the timestamp of the read it replaces.
+            memory.increment_timestamp_by(1);
+            let data = [imm, imm >> RV32_CELL_BITS, sign, sign]
+                .map(|limb| F::from_canonical_u32(limb & RV32_LIMB_MAX));
+            self.bitwise_lookup_chip
+                .request_range(imm & RV32_LIMB_MAX, (imm >> RV32_CELL_BITS) & RV32_LIMB_MAX);
+            (None, data, imm)
+        } else {
+            let rs2 = memory.read(RV32_REGISTER_AS, c.as_canonical_u32());
+            (Some(rs2), rs2.data, 0)
+        };
+        ([rs1.data, rs2_data], Rv32BaseAluReadRecord { rs1, rs2, rs2_imm })
+    }
+
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        _read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord) {
+        let [rd_data] = output.writes;
+        let rd = memory.write(RV32_REGISTER_AS, instruction.a.as_canonical_u32(), rd_data);
+        let to_pc = output.to_pc.unwrap_or(from_state.pc + DEFAULT_PC_STEP);
+        (ExecutionState::new(to_pc, memory.timestamp()), Rv32BaseAluWriteRecord { from_state, rd })
+    }
+
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    ) {
+        let row_slice: &mut Rv32BaseAluAdapterCols<F> = row_slice.borrow_mut();
+        row_slice.from_state = write_record.from_state.map(F::from_canonical_u32);
+        row_slice.rd_ptr = F::from_canonical_u32(write_record.rd.address.pointer);
+        row_slice.rs1_ptr = F::from_canonical_u32(read_record.rs1.address.pointer);
+        memory.fill_read_aux(&read_record.rs1, &mut row_slice.reads_aux[0]);
+        match read_record.rs2 {
+            Some(rs2) => {
+                row_slice.rs2 = F::from_canonical_u32(rs2.address.pointer);
+                row_slice.rs2_as = F::from_canonical_u32(RV32_REGISTER_AS);
+                memory.fill_read_aux(&rs2, &mut row_slice.reads_aux[1]);
+            }
+            None => row_slice.rs2 = F::from_canonical_u32(read_record.rs2_imm),
+        }
+        memory.fill_write_aux(&write_record.rd, &mut row_slice.writes_aux);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
@@ -0,0 +1,193 @@
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::{
+    adapters::{RV32_REGISTER_AS, check_register_accesses},
+    aligned_borrow,
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::{DEFAULT_PC_STEP, ExecutionBridge, ExecutionError, ExecutionState},
+    instructions::Instruction,
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, BasicAdapterInterface, ImmInstruction,
+        VmAdapterAir, VmAdapterChip, VmAdapterInterface,
+    },
+    memory::{MemoryAddress, MemoryBridge, MemoryController, MemoryReadAuxCols, MemoryReadRecord},
+    openvm_stark_backend::{
+        air::BaseAir,
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/adapters/branch.rs
+// for full implementation details.
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32BranchAdapterCols<T> {
+        pub from_state: ExecutionState<T>,
+        pub rs1_ptr: T,
+        pub rs2_ptr: T,
+        pub reads_aux: [MemoryReadAuxCols<T>; 2],
+    }
+}
+
+/// Reads instructions of the form OP a, b, c, d, e where if(\[a:4\]_d op \[b:4\]_e) pc += c.
+/// Operands d and e can only be 1. The core decides the next pc.
+#[derive(Clone, Copy)]
+pub struct Rv32BranchAdapterAir {
+    pub memory_bridge: MemoryBridge,
+    pub execution_bridge: ExecutionBridge,
+}
+
+impl BaseAir<F> for Rv32BranchAdapterAir {
+    fn width(&self) -> usize {
+        Rv32BranchAdapterCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> VmAdapterAir<AB> for Rv32BranchAdapterAir {
+    type Interface =
+        BasicAdapterInterface<AB::Expr, ImmInstruction<AB::Expr>, 2, 0, RV32_REGISTER_NUM_LIMBS, 0>;
+
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    ) {
+        let local: &Rv32BranchAdapterCols<AB::Var> = local.borrow();
+        let ImmInstruction { is_valid, opcode, immediate } = ctx.instruction;
+        let timestamp = local.from_state.timestamp;
+        let mut timestamp_delta: usize = 0;
+        let mut timestamp_pp = || {
+            timestamp_delta += 1;
+            timestamp + AB::F::from_canonical_usize(timestamp_delta - 1)
+        };
+
+        for ((ptr, data), aux) in
+            [local.rs1_ptr, local.rs2_ptr].into_iter().zip(ctx.reads).zip(&local.reads_aux)
+        {
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), ptr),
+                    data,
+                    timestamp_pp(),
+                    aux,
+                )
+                .eval(builder, is_valid.clone());
+        }
+
+        let to_pc =
+            ctx.to_pc.unwrap_or(local.from_state.pc + AB::F::from_canonical_u32(DEFAULT_PC_STEP));
+        // The operands are `[rs1_ptr, rs2_ptr, imm, RV32_REGISTER_AS, RV32_REGISTER_AS]`.
+        self.execution_bridge
+            .execute(
+                opcode,
+                [
+                    local.rs1_ptr.into(),
+                    local.rs2_ptr.into(),
+                    immediate,
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                ],
+                local.from_state,
+                ExecutionState {
+                    pc: to_pc,
+                    timestamp: timestamp + AB::F::from_canonical_usize(timestamp_delta),
+                },
+            )
+            .eval(builder, is_valid);
+    }
+
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var {
+        let cols: &Rv32BranchAdapterCols<AB::Var> = local.borrow();
+        cols.from_state.pc
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32BranchReadRecord {
+    /// Read register value from address space d = 1
+    pub rs1: MemoryReadRecord,
+    /// Read register value from address space e = 1
+    pub rs2: MemoryReadRecord,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32BranchWriteRecord {
+    pub from_state: ExecutionState<u32>,
+}
+
+/// Reads `rs1` and `rs2` for the core and moves to the pc it decides.
+pub struct Rv32BranchAdapterChip {
+    pub air: Rv32BranchAdapterAir,
+}
+
+impl Rv32BranchAdapterChip {
+    pub fn new(memory_bridge: MemoryBridge, execution_bridge: ExecutionBridge) -> Self {
+        Self { air: Rv32BranchAdapterAir { memory_bridge, execution_bridge } }
+    }
+}
+
+impl VmAdapterChip for Rv32BranchAdapterChip {
+    type ReadRecord = Rv32BranchReadRecord;
+    type WriteRecord = Rv32BranchWriteRecord;
+    type Air = Rv32BranchAdapterAir;
+    type Interface = BasicAdapterInterface<F, ImmInstruction<F>, 2, 0, RV32_REGISTER_NUM_LIMBS, 0>;
+
+    fn check(
+        &self,
+        memory: &MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        let Instruction { a, b, .. } = *instruction;
+        check_register_accesses(memory, from_state.pc, &[a, b], 2)
+    }
+
+    fn preprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord) {
+        let Instruction { a, b, d, e, .. } = *instruction;
+        assert_eq!(d.as_canonical_u32(), RV32_REGISTER_AS, "rs1 must be a register");
+        assert_eq!(e.as_canonical_u32(), RV32_REGISTER_AS, "rs2 must be a register");
+
+        let rs1 = memory.read(RV32_REGISTER_AS, a.as_canonical_u32());
+        let rs2 = memory.read(RV32_REGISTER_AS, b.as_canonical_u32());
+        ([rs1.data, rs2.data], Rv32BranchReadRecord { rs1, rs2 })
+    }
+
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        _instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        _read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord) {
+        let to_pc = output.to_pc.unwrap_or(from_state.pc + DEFAULT_PC_STEP);
+        (ExecutionState::new(to_pc, memory.timestamp()), Rv32BranchWriteRecord { from_state })
+    }
+
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    ) {
+        let row_slice: &mut Rv32BranchAdapterCols<F> = row_slice.borrow_mut();
+        row_slice.from_state = write_record.from_state.map(F::from_canonical_u32);
+        row_slice.rs1_ptr = F::from_canonical_u32(read_record.rs1.address.pointer);
+        row_slice.rs2_ptr = F::from_canonical_u32(read_record.rs2.address.pointer);
+        memory.fill_read_aux(&read_record.rs1, &mut row_slice.reads_aux[0]);
+        memory.fill_read_aux(&read_record.rs2, &mut row_slice.reads_aux[1]);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
@@ -0,0 +1,240 @@
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::{
+    adapters::{RV32_REGISTER_AS, check_rd_write, check_register_accesses, compose},
+    aligned_borrow,
+    core::{PC_BITS, RV32_REGISTER_NUM_LIMBS},
+    execution::{DEFAULT_PC_STEP, ExecutionBridge, ExecutionError, ExecutionState},
+    instructions::Instruction,
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, BasicAdapterInterface, SignedImmInstruction,
+        VmAdapterAir, VmAdapterChip, VmAdapterInterface,
+    },
+    jalr::run_jalr,
+    memory::{
+        MemoryAddress, MemoryBridge, MemoryController, MemoryReadAuxCols, MemoryReadRecord,
+        MemoryWriteAuxCols, MemoryWriteRecord,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/adapters/jalr.rs
+// for full implementation details.
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32JalrAdapterCols<T> {
+        pub from_state: ExecutionState<T>,
+        pub rs1_ptr: T,
+        pub rs1_aux_cols: MemoryReadAuxCols<T>,
+        pub rd_ptr: T,
+        pub rd_aux_cols: MemoryWriteAuxCols<T, RV32_REGISTER_NUM_LIMBS>,
+        pub needs_write: T,
+    }
+}
+
+/// Reads `[b:4]_1` and writes `[a:4]_1` for instructions of the form
+/// `OP a, b, c, 1, 0, f, g` where `c` holds the lower 16 bits of the immediate and `g` its sign.
+/// The operand `f` marks that `rd` is written, which it is not when `rd` is `x0`.
+#[derive(Clone, Copy, Debug)]
+pub struct Rv32JalrAdapterAir {
+    pub memory_bridge: MemoryBridge,
+    pub execution_bridge: ExecutionBridge,
+}
+
+impl BaseAir<F> for Rv32JalrAdapterAir {
+    fn width(&self) -> usize {
+        Rv32JalrAdapterCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> VmAdapterAir<AB> for Rv32JalrAdapterAir {
+    type Interface = BasicAdapterInterface<
+        AB::Expr,
+        SignedImmInstruction<AB::Expr>,
+        1,
+        1,
+        RV32_REGISTER_NUM_LIMBS,
+        RV32_REGISTER_NUM_LIMBS,
+    >;
+
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    ) {
+        let local_cols: &Rv32JalrAdapterCols<AB::Var> = local.borrow();
+        let SignedImmInstruction { is_valid, opcode, immediate, imm_sign } = ctx.instruction;
+        let [rs1_data] = ctx.reads;
+        let [rd_data] = ctx.writes;
+        let timestamp = local_cols.from_state.timestamp;
+        let register_as = AB::F::from_canonical_u32(RV32_REGISTER_AS);
+        let needs_write = local_cols.needs_write;
+        builder.assert_bool(needs_write);
+        builder.when(needs_write).assert_one(is_valid.clone());
+
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(register_as, local_cols.rs1_ptr),
+                rs1_data,
+                timestamp,
+                &local_cols.rs1_aux_cols,
+            )
+            .eval(builder, is_valid.clone());
+        self.memory_bridge
+            .write(
+                MemoryAddress::new(register_as, local_cols.rd_ptr),
+                rd_data,
+                timestamp + AB::Expr::ONE,
+                &local_cols.rd_aux_cols,
+            )
+            .eval(builder, needs_write);
+
+        let to_pc = ctx
+            .to_pc
+            .unwrap_or(local_cols.from_state.pc + AB::F::from_canonical_u32(DEFAULT_PC_STEP));
+        // The operands are `[rd_ptr, rs1_ptr, imm, RV32_REGISTER_AS, 0, needs_write, imm_sign]`.
+        self.execution_bridge
+            .execute(
+                opcode,
+                [
+                    local_cols.rd_ptr.into(),
+                    local_cols.rs1_ptr.into(),
+                    immediate,
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::ZERO,
+                    needs_write.into(),
+                    imm_sign,
+                ],
+                local_cols.from_state,
+                ExecutionState { pc: to_pc, timestamp: timestamp + AB::F::from_canonical_u32(2) },
+            )
+            .eval(builder, is_valid);
+    }
+
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var {
+        let local_cols: &Rv32JalrAdapterCols<AB::Var> = local.borrow();
+        local_cols.from_state.pc
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32JalrReadRecord {
+    pub rs1: MemoryReadRecord,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32JalrWriteRecord {
+    pub from_state: ExecutionState<u32>,
+    pub rd_ptr: u32,
+    /// The write of rd, if any
+    pub rd: Option<MemoryWriteRecord>,
+}
+
+/// Reads `rs1 = b` for the core, writes its output to `rd = a` if `f = 1` and moves to the pc it
+/// decides.
+pub struct Rv32JalrAdapterChip {
+    pub air: Rv32JalrAdapterAir,
+}
+
+impl Rv32JalrAdapterChip {
+    pub fn new(memory_bridge: MemoryBridge, execution_bridge: ExecutionBridge) -> Self {
+        Self { air: Rv32JalrAdapterAir { memory_bridge, execution_bridge } }
+    }
+}
+
+impl VmAdapterChip for Rv32JalrAdapterChip {
+    type ReadRecord = Rv32JalrReadRecord;
+    type WriteRecord = Rv32JalrWriteRecord;
+    type Air = Rv32JalrAdapterAir;
+    type Interface = BasicAdapterInterface<
+        F,
+        SignedImmInstruction<F>,
+        1,
+        1,
+        RV32_REGISTER_NUM_LIMBS,
+        RV32_REGISTER_NUM_LIMBS,
+    >;
+
+    /// Rejects the jumps beyond [PC_BITS] bits, which the core chip panics on.
+    fn check(
+        &self,
+        memory: &MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        let Instruction { a, b, c, f, g, .. } = *instruction;
+        check_register_accesses(memory, from_state.pc, &[a, b], 2)?;
+        if f == F::ONE {
+            check_rd_write(instruction, from_state.pc)?;
+        }
+        let rs1 = compose(&memory.unsafe_read(RV32_REGISTER_AS, b.as_canonical_u32()));
+        let imm = c.as_canonical_u32().wrapping_add(g.as_canonical_u32().wrapping_mul(0xffff_0000));
+        let (to_pc, _) = run_jalr(from_state.pc, rs1, imm);
+        match to_pc < (1 << PC_BITS) {
+            true => Ok(()),
+            false => Err(ExecutionError::PcOutOfBounds { pc: from_state.pc, to_pc }),
+        }
+    }
+
+    fn preprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord) {
+        let Instruction { b, d, f, .. } = *instruction;
+        assert_eq!(d.as_canonical_u32(), RV32_REGISTER_AS, "rs1 must be a register");
+        let f = f.as_canonical_u32();
+        assert!(f <= 1, "f {f} is not a bit");
+
+        let rs1 = memory.read(RV32_REGISTER_AS, b.as_canonical_u32());
+        ([rs1.data], Rv32JalrReadRecord { rs1 })
+    }
+
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        _read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord) {
+        let [rd_data] = output.writes;
+        let rd_ptr = instruction.a.as_canonical_u32();
+        let rd = (instruction.f == F::ONE).then(|| memory.write(RV32_REGISTER_AS, rd_ptr, rd_data));
+        if rd.is_none() {
+            memory.increment_timestamp_by(1);
+        }
+        let to_pc = output.to_pc.unwrap_or(from_state.pc + DEFAULT_PC_STEP);
+        let record = Rv32JalrWriteRecord { from_state, rd_ptr, rd };
+        (ExecutionState::new(to_pc, memory.timestamp()), record)
+    }
+
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    ) {
+        let adapter_cols: &mut Rv32JalrAdapterCols<F> = row_slice.borrow_mut();
+        adapter_cols.from_state = write_record.from_state.map(F::from_canonical_u32);
+        adapter_cols.rs1_ptr = F::from_canonical_u32(read_record.rs1.address.pointer);
+        memory.fill_read_aux(&read_record.rs1, &mut adapter_cols.rs1_aux_cols);
+        adapter_cols.rd_ptr = F::from_canonical_u32(write_record.rd_ptr);
+        if let Some(rd) = &write_record.rd {
+            memory.fill_write_aux(rd, &mut adapter_cols.rd_aux_cols);
+        }
+        adapter_cols.needs_write = F::from_bool(write_record.rd.is_some());
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
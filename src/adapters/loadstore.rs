@@ -0,0 +1,460 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+    marker::PhantomData,
+};
+
+use crate::{
+    adapters::{
+        RV32_MEMORY_AS, RV32_REGISTER_AS, check_rd_write, check_register_accesses, compose,
+    },
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    execution::{DEFAULT_PC_STEP, ExecutionBridge, ExecutionError, ExecutionState},
+    instructions::{Instruction, Rv32LoadStoreOpcode},
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, LoadStoreInstruction, VmAdapterAir,
+        VmAdapterChip, VmAdapterInterface,
+    },
+    loadstore::{access_width, is_load_opcode},
+    memory::{
+        MemoryAddress, MemoryBaseAuxCols, MemoryBridge, MemoryController, MemoryReadAuxCols,
+        MemoryReadRecord, MemoryWriteAuxCols, MemoryWriteRecord,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    utils::select,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/adapters/loadstore.rs
+// for full implementation details.
+
+/// The interface of [Rv32LoadStoreAdapterAir]. The reads are the previous data of the written
+/// block, which are columns since they complete the write aux columns, and the read data.
+pub struct Rv32LoadStoreAdapterAirInterface<AB: InteractionBuilder>(PhantomData<AB>);
+
+impl<AB: InteractionBuilder> VmAdapterInterface<AB::Expr> for Rv32LoadStoreAdapterAirInterface<AB> {
+    type Reads = ([AB::Var; RV32_REGISTER_NUM_LIMBS], [AB::Expr; RV32_REGISTER_NUM_LIMBS]);
+    type Writes = [[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1];
+    type ProcessedInstruction = LoadStoreInstruction<AB::Expr>;
+}
+
+/// The interface of [Rv32LoadStoreAdapterChip]. The reads are the previous data of the written
+/// block and the read data as in [Rv32LoadStoreAdapterAirInterface], and the shift amount of the
+/// effective address.
+pub struct Rv32LoadStoreAdapterRuntimeInterface<T>(PhantomData<T>);
+
+impl<T> VmAdapterInterface<T> for Rv32LoadStoreAdapterRuntimeInterface<T> {
+    type Reads = ([[T; RV32_REGISTER_NUM_LIMBS]; 2], T);
+    type Writes = [[T; RV32_REGISTER_NUM_LIMBS]; 1];
+    type ProcessedInstruction = ();
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32LoadStoreAdapterCols<T> {
+        pub from_state: ExecutionState<T>,
+        pub rs1_ptr: T,
+        pub rs1_data: [T; RV32_REGISTER_NUM_LIMBS],
+        pub rs1_aux_cols: MemoryReadAuxCols<T>,
+        // Will write to rd for loads and read from rs2 for stores
+        pub rd_rs2_ptr: T,
+        pub read_data_aux: MemoryReadAuxCols<T>,
+        // The lower 16 bits of the immediate, sign-extended by imm_sign
+        pub imm: T,
+        pub imm_sign: T,
+        // The limbs of the effective address rs1 + imm
+        pub mem_ptr_limbs: [T; RV32_REGISTER_NUM_LIMBS],
+        // The previous data are provided by the core to complete the write aux columns
+        pub write_base_aux: MemoryBaseAuxCols<T>,
+        // Whether rd or the memory is written, which only loads into x0 are not
+        pub needs_write: T,
+    }
+}
+
+/// Reads `rs1`, computes the effective address `rs1 + imm` and, for instructions of the form
+/// `OP a, b, c, 1, 2, f, g` where `c` holds the lower 16 bits of the immediate and `g` its sign,
+/// - loads the aligned memory block holding the address into `rd = a` if `f = 1`,
+/// - stores `rs2 = a` into the aligned memory block holding the address, where `f = 1`.
+///
+/// The effective address must fit in `pointer_max_bits` bits, and less its shift amount, which
+/// the core decides, it must be a multiple of four.
+#[derive(Clone, Copy)]
+pub struct Rv32LoadStoreAdapterAir {
+    pub memory_bridge: MemoryBridge,
+    pub execution_bridge: ExecutionBridge,
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+    pub pointer_max_bits: usize,
+}
+
+impl BaseAir<F> for Rv32LoadStoreAdapterAir {
+    fn width(&self) -> usize {
+        Rv32LoadStoreAdapterCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> VmAdapterAir<AB> for Rv32LoadStoreAdapterAir {
+    type Interface = Rv32LoadStoreAdapterAirInterface<AB>;
+
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    ) {
+        let local_cols: &Rv32LoadStoreAdapterCols<AB::Var> = local.borrow();
+        let LoadStoreInstruction {
+            is_valid,
+            opcode,
+            is_load,
+            load_shift_amount,
+            store_shift_amount,
+        } = ctx.instruction;
+        let (prev_data, read_data) = ctx.reads;
+        let [write_data] = ctx.writes;
+        let shift_amount = load_shift_amount.clone() + store_shift_amount.clone();
+
+        let timestamp = local_cols.from_state.timestamp;
+        let mut timestamp_delta: usize = 0;
+        let mut timestamp_pp = || {
+            timestamp_delta += 1;
+            timestamp + AB::F::from_canonical_usize(timestamp_delta - 1)
+        };
+
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), local_cols.rs1_ptr),
+                local_cols.rs1_data,
+                timestamp_pp(),
+                &local_cols.rs1_aux_cols,
+            )
+            .eval(builder, is_valid.clone());
+
+        // Constrain mem_ptr = rs1 + imm as a u32 addition with two limbs of 16 bits, where the
+        // upper limb of the immediate is its sign extension.
+        builder.assert_bool(local_cols.imm_sign);
+        let half = 2 * RV32_CELL_BITS;
+        let inv = AB::F::from_canonical_u32(1 << half).inverse();
+        let limb_pair = |limbs: &[AB::Var]| {
+            limbs[0] + limbs[1] * AB::F::from_canonical_u32(1 << RV32_CELL_BITS)
+        };
+        let rs1 = &local_cols.rs1_data;
+        let mem_ptr_limbs = &local_cols.mem_ptr_limbs;
+
+        let carry = (limb_pair(&rs1[..2]) + local_cols.imm - limb_pair(&mem_ptr_limbs[..2])) * inv;
+        builder.when(is_valid.clone()).assert_bool(carry.clone());
+
+        let imm_extend_limb = local_cols.imm_sign * AB::F::from_canonical_u32((1 << half) - 1);
+        let carry =
+            (limb_pair(&rs1[2..]) + imm_extend_limb + carry - limb_pair(&mem_ptr_limbs[2..])) * inv;
+        builder.when(is_valid.clone()).assert_bool(carry);
+
+        // Range check the limbs of mem_ptr to RV32_CELL_BITS bits, where the limbs above
+        // pointer_max_bits are scaled up to a full limb, as for AUIPC. The least significant limb
+        // less the shift amount is additionally divided by four, which must keep it in range.
+        let [scale_1, scale_2, scale_3] = self.limb_scales().map(AB::F::from_canonical_u32);
+        self.bitwise_lookup_bus
+            .send_range::<AB::Expr>(
+                (mem_ptr_limbs[0] - shift_amount) * AB::F::from_canonical_u32(4).inverse(),
+                mem_ptr_limbs[0].into(),
+            )
+            .eval(builder, is_valid.clone());
+        self.bitwise_lookup_bus
+            .send_range::<AB::Expr>(mem_ptr_limbs[1] * scale_1, mem_ptr_limbs[2] * scale_2)
+            .eval(builder, is_valid.clone());
+        self.bitwise_lookup_bus
+            .send_range::<AB::Expr>(mem_ptr_limbs[3] * scale_3, AB::F::ZERO)
+            .eval(builder, is_valid.clone());
+
+        let mem_ptr = mem_ptr_limbs.iter().enumerate().fold(AB::Expr::ZERO, |acc, (i, &limb)| {
+            acc + limb * AB::F::from_canonical_u32(1 << (i * RV32_CELL_BITS))
+        });
+        let register_as = AB::F::from_canonical_u32(RV32_REGISTER_AS);
+        let memory_as = AB::F::from_canonical_u32(RV32_MEMORY_AS);
+
+        // Loads read the memory block and write rd, while stores read rs2 and write the memory
+        // block. The shift amounts are separate so that the pointers stay of degree two.
+        let read_as = select::<AB::Expr>(is_load.clone(), memory_as, register_as);
+        let read_ptr = select::<AB::Expr>(is_load.clone(), mem_ptr.clone(), local_cols.rd_rs2_ptr)
+            - load_shift_amount;
+        self.memory_bridge
+            .read(
+                MemoryAddress::new(read_as, read_ptr),
+                read_data,
+                timestamp_pp(),
+                &local_cols.read_data_aux,
+            )
+            .eval(builder, is_valid.clone());
+
+        // Stores always write, and loads write unless rd is x0.
+        let needs_write = local_cols.needs_write;
+        builder.assert_bool(needs_write);
+        builder.when(needs_write).assert_one(is_valid.clone());
+        builder.when(is_valid.clone() - is_load.clone()).assert_one(needs_write);
+
+        let write_aux_cols = MemoryWriteAuxCols { base: local_cols.write_base_aux, prev_data };
+        let write_as = select::<AB::Expr>(is_load.clone(), register_as, memory_as);
+        let write_ptr =
+            select::<AB::Expr>(is_load, local_cols.rd_rs2_ptr, mem_ptr) - store_shift_amount;
+        self.memory_bridge
+            .write(
+                MemoryAddress::new(write_as, write_ptr),
+                write_data,
+                timestamp_pp(),
+                &write_aux_cols,
+            )
+            .eval(builder, needs_write);
+
+        let to_pc = ctx
+            .to_pc
+            .unwrap_or(local_cols.from_state.pc + AB::F::from_canonical_u32(DEFAULT_PC_STEP));
+        // The operands are `[rd_rs2_ptr, rs1_ptr, imm, RV32_REGISTER_AS, RV32_MEMORY_AS,
+        // needs_write, imm_sign]`.
+        self.execution_bridge
+            .execute(
+                opcode,
+                [
+                    local_cols.rd_rs2_ptr.into(),
+                    local_cols.rs1_ptr.into(),
+                    local_cols.imm.into(),
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::from_canonical_u32(RV32_MEMORY_AS),
+                    needs_write.into(),
+                    local_cols.imm_sign.into(),
+                ],
+                local_cols.from_state,
+                ExecutionState {
+                    pc: to_pc,
+                    timestamp: timestamp + AB::F::from_canonical_usize(timestamp_delta),
+                },
+            )
+            .eval(builder, is_valid);
+    }
+
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var {
+        let local_cols: &Rv32LoadStoreAdapterCols<AB::Var> = local.borrow();
+        local_cols.from_state.pc
+    }
+}
+
+impl Rv32LoadStoreAdapterAir {
+    /// The factors that scale the limbs of the effective address above the least significant one
+    /// to a full limb when they fit in `pointer_max_bits` bits.
+    pub fn limb_scales(&self) -> [u32; RV32_REGISTER_NUM_LIMBS - 1] {
+        array::from_fn(|i| {
+            let limb_bits = self.pointer_max_bits.saturating_sub((i + 1) * RV32_CELL_BITS);
+            1 << (RV32_CELL_BITS - limb_bits.min(RV32_CELL_BITS))
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32LoadStoreReadRecord {
+    pub rs1: MemoryReadRecord,
+    /// The read of the memory block for loads, and of rs2 for stores
+    pub read: MemoryReadRecord,
+    pub rd_rs2_ptr: u32,
+    pub imm: u32,
+    pub imm_sign: bool,
+    pub mem_ptr_limbs: [u32; RV32_REGISTER_NUM_LIMBS],
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32LoadStoreWriteRecord {
+    pub from_state: ExecutionState<u32>,
+    /// The write of rd for loads, and of the memory block for stores, if any
+    pub write: Option<MemoryWriteRecord>,
+}
+
+/// Performs the memory accesses of loads and stores on the aligned memory block holding the
+/// effective address, and rejects the accesses that are not aligned to their width.
+pub struct Rv32LoadStoreAdapterChip {
+    pub air: Rv32LoadStoreAdapterAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32LoadStoreAdapterChip {
+    pub fn new(
+        memory_bridge: MemoryBridge,
+        execution_bridge: ExecutionBridge,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        pointer_max_bits: usize,
+    ) -> Self {
+        assert!(
+            (RV32_CELL_BITS..=RV32_REGISTER_NUM_LIMBS * RV32_CELL_BITS).contains(&pointer_max_bits),
+            "pointer_max_bits {pointer_max_bits} out of range for an effective address"
+        );
+        Self {
+            air: Rv32LoadStoreAdapterAir {
+                memory_bridge,
+                execution_bridge,
+                bitwise_lookup_bus: bitwise_lookup_chip.bus(),
+                pointer_max_bits,
+            },
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+impl VmAdapterChip for Rv32LoadStoreAdapterChip {
+    type ReadRecord = Rv32LoadStoreReadRecord;
+    type WriteRecord = Rv32LoadStoreWriteRecord;
+    type Air = Rv32LoadStoreAdapterAir;
+    type Interface = Rv32LoadStoreAdapterRuntimeInterface<F>;
+
+    /// Rejects the addresses beyond `pointer_max_bits` and the accesses that are not aligned to
+    /// their width, which [Rv32LoadStoreAdapterChip::preprocess] panics on.
+    fn check(
+        &self,
+        memory: &MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        let Instruction { opcode, a, b, c, f, g, .. } = *instruction;
+        let Some(local_opcode) = opcode.local_opcode::<Rv32LoadStoreOpcode>() else {
+            return Ok(());
+        };
+        check_register_accesses(memory, from_state.pc, &[a, b], 3)?;
+        if is_load_opcode(local_opcode) && f == F::ONE {
+            check_rd_write(instruction, from_state.pc)?;
+        }
+        let rs1 = memory.unsafe_read(RV32_REGISTER_AS, b.as_canonical_u32());
+        let address = effective_address(&rs1, c.as_canonical_u32(), g.as_canonical_u32());
+        let pc = from_state.pc;
+        let pointer_max_bits = self.air.pointer_max_bits;
+        if pointer_max_bits < 32 && address >= (1 << pointer_max_bits) {
+            return Err(ExecutionError::AddressOutOfBounds { pc, address });
+        }
+        let width = access_width(local_opcode);
+        if !address.is_multiple_of(width as u32) {
+            return Err(ExecutionError::MisalignedAccess { pc, address, width });
+        }
+        Ok(())
+    }
+
+    fn preprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord) {
+        let Instruction { opcode, a, b, c, d, e, f, g } = *instruction;
+        let local_opcode: Rv32LoadStoreOpcode = opcode
+            .local_opcode()
+            .unwrap_or_else(|| panic!("opcode {opcode} is not a load or store opcode"));
+        assert_eq!(d.as_canonical_u32(), RV32_REGISTER_AS, "rd and rs1 must be registers");
+        assert_eq!(e.as_canonical_u32(), RV32_MEMORY_AS, "the memory must be main memory");
+        let f = f.as_canonical_u32();
+        assert!(f <= 1, "f {f} is not a bit");
+        assert!(f == 1 || is_load_opcode(local_opcode), "the memory must be written");
+
+        let rs1 = memory.read(RV32_REGISTER_AS, b.as_canonical_u32());
+        let imm = c.as_canonical_u32();
+        let imm_sign = g.as_canonical_u32();
+        assert!(imm < (1 << (2 * RV32_CELL_BITS)), "imm {imm} out of range for 16 bits");
+        assert!(imm_sign <= 1, "imm_sign {imm_sign} is not a bit");
+        let ptr_val = effective_address(&rs1.data, imm, imm_sign);
+
+        let pointer_max_bits = self.air.pointer_max_bits;
+        assert!(
+            pointer_max_bits == 32 || ptr_val < (1 << pointer_max_bits),
+            "address {ptr_val:#x} out of range for {pointer_max_bits} bits"
+        );
+        assert!(
+            ptr_val.is_multiple_of(access_width(local_opcode) as u32),
+            "unaligned {local_opcode:?} at address {ptr_val:#x}"
+        );
+        let shift_amount = ptr_val % 4;
+        let mem_ptr_limbs = array::from_fn(|i| (ptr_val >> (i * RV32_CELL_BITS)) & RV32_LIMB_MAX);
+        let scales = self.air.limb_scales();
+        self.bitwise_lookup_chip
+            .request_range((mem_ptr_limbs[0] - shift_amount) / 4, mem_ptr_limbs[0]);
+        self.bitwise_lookup_chip
+            .request_range(mem_ptr_limbs[1] * scales[0], mem_ptr_limbs[2] * scales[1]);
+        self.bitwise_lookup_chip.request_range(mem_ptr_limbs[3] * scales[2], 0);
+
+        let rd_rs2_ptr = a.as_canonical_u32();
+        let block_ptr = ptr_val - shift_amount;
+        let (read, prev_data) = if is_load_opcode(local_opcode) {
+            let read = memory.read(RV32_MEMORY_AS, block_ptr);
+            (read, memory.unsafe_read(RV32_REGISTER_AS, rd_rs2_ptr))
+        } else {
+            let read = memory.read(RV32_REGISTER_AS, rd_rs2_ptr);
+            (read, memory.unsafe_read(RV32_MEMORY_AS, block_ptr))
+        };
+
+        let record = Rv32LoadStoreReadRecord {
+            rs1,
+            read,
+            rd_rs2_ptr,
+            imm,
+            imm_sign: imm_sign == 1,
+            mem_ptr_limbs,
+        };
+        (([prev_data, read.data], F::from_canonical_u32(shift_amount)), record)
+    }
+
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord) {
+        let local_opcode: Rv32LoadStoreOpcode = instruction.opcode.local_opcode().unwrap();
+        let [write_data] = output.writes;
+        let write = if instruction.f == F::ZERO {
+            memory.increment_timestamp_by(1);
+            None
+        } else if is_load_opcode(local_opcode) {
+            Some(memory.write(RV32_REGISTER_AS, read_record.rd_rs2_ptr, write_data))
+        } else {
+            let ptr_val = compose(&read_record.mem_ptr_limbs.map(F::from_canonical_u32));
+            Some(memory.write(RV32_MEMORY_AS, ptr_val - ptr_val % 4, write_data))
+        };
+        let to_pc = output.to_pc.unwrap_or(from_state.pc + DEFAULT_PC_STEP);
+        (
+            ExecutionState::new(to_pc, memory.timestamp()),
+            Rv32LoadStoreWriteRecord { from_state, write },
+        )
+    }
+
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    ) {
+        let adapter_cols: &mut Rv32LoadStoreAdapterCols<F> = row_slice.borrow_mut();
+        adapter_cols.from_state = write_record.from_state.map(F::from_canonical_u32);
+        adapter_cols.rs1_ptr = F::from_canonical_u32(read_record.rs1.address.pointer);
+        adapter_cols.rs1_data = read_record.rs1.data;
+        memory.fill_read_aux(&read_record.rs1, &mut adapter_cols.rs1_aux_cols);
+        adapter_cols.rd_rs2_ptr = F::from_canonical_u32(read_record.rd_rs2_ptr);
+        memory.fill_read_aux(&read_record.read, &mut adapter_cols.read_data_aux);
+        adapter_cols.imm = F::from_canonical_u32(read_record.imm);
+        adapter_cols.imm_sign = F::from_bool(read_record.imm_sign);
+        adapter_cols.mem_ptr_limbs = read_record.mem_ptr_limbs.map(F::from_canonical_u32);
+        if let Some(write) = &write_record.write {
+            memory.fill_write_base_aux(write, &mut adapter_cols.write_base_aux);
+        }
+        adapter_cols.needs_write = F::from_bool(write_record.write.is_some());
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// The address `rs1 + imm`, where `imm` holds the lower 16 bits of the immediate and `imm_sign`
+/// its sign.
+fn effective_address(rs1: &[F; RV32_REGISTER_NUM_LIMBS], imm: u32, imm_sign: u32) -> u32 {
+    compose(rs1).wrapping_add(imm + imm_sign * 0xffff_0000)
+}
@@ -0,0 +1,68 @@
+use core::array;
+
+use crate::{
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    execution::ExecutionError,
+    instructions::Instruction,
+    memory::MemoryController,
+    openvm_stark_backend::field::{F, FieldAlgebra, PrimeField32},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/adapters/mod.rs
+// for full implementation details.
+
+mod alu;
+mod branch;
+mod jalr;
+mod loadstore;
+mod mul;
+mod rdwrite;
+
+pub use alu::*;
+pub use branch::*;
+pub use jalr::*;
+pub use loadstore::*;
+pub use mul::*;
+pub use rdwrite::*;
+
+/// The address space of the 32 registers, each stored in [RV32_REGISTER_NUM_LIMBS] cells.
+pub const RV32_REGISTER_AS: u32 = 1;
+/// The address space of the byte-addressed main memory.
+pub const RV32_MEMORY_AS: u32 = 2;
+
+/// Composes the little-endian limbs of a register into a `u32`.
+pub fn compose(limbs: &[F; RV32_REGISTER_NUM_LIMBS]) -> u32 {
+    limbs
+        .iter()
+        .enumerate()
+        .fold(0, |acc, (i, limb)| acc | (limb.as_canonical_u32() << (i * RV32_CELL_BITS)))
+}
+
+/// Decomposes a `u32` into the little-endian limbs of a register.
+pub fn decompose(value: u32) -> [F; RV32_REGISTER_NUM_LIMBS] {
+    array::from_fn(|i| F::from_canonical_u32((value >> (i * RV32_CELL_BITS)) & RV32_LIMB_MAX))
+}
+
+/// Rejects an instruction at `pc` that writes its output to `rd = a` when `rd` is `x0`. The
+/// decoder turns these writes into nops or clears `f`, so only hand-built instructions get here.
+pub fn check_rd_write(instruction: &Instruction<F>, pc: u32) -> Result<(), ExecutionError> {
+    match instruction.a == F::ZERO {
+        true => Err(ExecutionError::ZeroRegisterWrite { pc }),
+        false => Ok(()),
+    }
+}
+
+/// Rejects an instruction at `pc` reading or writing the registers at `pointers` with `accesses`
+/// memory accesses in all, if the memory controller would panic on one of them.
+pub fn check_register_accesses(
+    memory: &MemoryController,
+    pc: u32,
+    pointers: &[F],
+    accesses: u32,
+) -> Result<(), ExecutionError> {
+    for pointer in pointers {
+        memory.check_pointer(pc, pointer.as_canonical_u32())?;
+    }
+    memory.check_timestamps(pc, accesses)
+}
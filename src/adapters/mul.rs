@@ -0,0 +1,222 @@
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::{
+    adapters::{RV32_REGISTER_AS, check_rd_write, check_register_accesses},
+    aligned_borrow,
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::{DEFAULT_PC_STEP, ExecutionBridge, ExecutionError, ExecutionState},
+    instructions::Instruction,
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, BasicAdapterInterface, MinimalInstruction,
+        VmAdapterAir, VmAdapterChip, VmAdapterInterface,
+    },
+    memory::{
+        MemoryAddress, MemoryBridge, MemoryController, MemoryReadAuxCols, MemoryReadRecord,
+        MemoryWriteAuxCols, MemoryWriteRecord,
+    },
+    openvm_stark_backend::{
+        air::BaseAir,
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/adapters/mul.rs
+// for full implementation details.
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32MultAdapterCols<T> {
+        pub from_state: ExecutionState<T>,
+        pub rd_ptr: T,
+        pub rs1_ptr: T,
+        pub rs2_ptr: T,
+        pub reads_aux: [MemoryReadAuxCols<T>; 2],
+        pub writes_aux: MemoryWriteAuxCols<T, RV32_REGISTER_NUM_LIMBS>,
+    }
+}
+
+/// Reads instructions of the form OP a, b, c, d where \[a:4\]_d = \[b:4\]_d op \[c:4\]_d.
+/// Operand d can only be 1, and there is no immediate support.
+#[derive(Clone, Copy, Debug)]
+pub struct Rv32MultAdapterAir {
+    pub memory_bridge: MemoryBridge,
+    pub execution_bridge: ExecutionBridge,
+}
+
+impl BaseAir<F> for Rv32MultAdapterAir {
+    fn width(&self) -> usize {
+        Rv32MultAdapterCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> VmAdapterAir<AB> for Rv32MultAdapterAir {
+    type Interface = BasicAdapterInterface<
+        AB::Expr,
+        MinimalInstruction<AB::Expr>,
+        2,
+        1,
+        RV32_REGISTER_NUM_LIMBS,
+        RV32_REGISTER_NUM_LIMBS,
+    >;
+
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    ) {
+        let local: &Rv32MultAdapterCols<AB::Var> = local.borrow();
+        let MinimalInstruction { is_valid, opcode } = ctx.instruction;
+        let [rs1_data, rs2_data] = ctx.reads;
+        let [rd_data] = ctx.writes;
+        let timestamp = local.from_state.timestamp;
+        let mut timestamp_delta: usize = 0;
+        let mut timestamp_pp = || {
+            timestamp_delta += 1;
+            timestamp + AB::F::from_canonical_usize(timestamp_delta - 1)
+        };
+
+        for ((ptr, data), aux) in [local.rs1_ptr, local.rs2_ptr]
+            .into_iter()
+            .zip([rs1_data, rs2_data])
+            .zip(&local.reads_aux)
+        {
+            self.memory_bridge
+                .read(
+                    MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), ptr),
+                    data,
+                    timestamp_pp(),
+                    aux,
+                )
+                .eval(builder, is_valid.clone());
+        }
+        self.memory_bridge
+            .write(
+                MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), local.rd_ptr),
+                rd_data,
+                timestamp_pp(),
+                &local.writes_aux,
+            )
+            .eval(builder, is_valid.clone());
+
+        self.execution_bridge
+            .execute_and_increment_pc(
+                opcode,
+                [
+                    local.rd_ptr.into(),
+                    local.rs1_ptr.into(),
+                    local.rs2_ptr.into(),
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::ZERO,
+                ],
+                local.from_state,
+                AB::F::from_canonical_usize(timestamp_delta),
+            )
+            .eval(builder, is_valid);
+    }
+
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var {
+        let cols: &Rv32MultAdapterCols<AB::Var> = local.borrow();
+        cols.from_state.pc
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32MultReadRecord {
+    /// Reads from operand registers
+    pub rs1: MemoryReadRecord,
+    pub rs2: MemoryReadRecord,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32MultWriteRecord {
+    pub from_state: ExecutionState<u32>,
+    /// Write to destination register
+    pub rd: MemoryWriteRecord,
+}
+
+/// Reads `rs1` and `rs2` and writes the output of the core to `rd`.
+pub struct Rv32MultAdapterChip {
+    pub air: Rv32MultAdapterAir,
+}
+
+impl Rv32MultAdapterChip {
+    pub fn new(memory_bridge: MemoryBridge, execution_bridge: ExecutionBridge) -> Self {
+        Self { air: Rv32MultAdapterAir { memory_bridge, execution_bridge } }
+    }
+}
+
+impl VmAdapterChip for Rv32MultAdapterChip {
+    type ReadRecord = Rv32MultReadRecord;
+    type WriteRecord = Rv32MultWriteRecord;
+    type Air = Rv32MultAdapterAir;
+    type Interface = BasicAdapterInterface<
+        F,
+        MinimalInstruction<F>,
+        2,
+        1,
+        RV32_REGISTER_NUM_LIMBS,
+        RV32_REGISTER_NUM_LIMBS,
+    >;
+
+    fn check(
+        &self,
+        memory: &MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        let Instruction { a, b, c, .. } = *instruction;
+        check_register_accesses(memory, from_state.pc, &[a, b, c], 3)?;
+        check_rd_write(instruction, from_state.pc)
+    }
+
+    fn preprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord) {
+        let Instruction { b, c, d, .. } = *instruction;
+        assert_eq!(d.as_canonical_u32(), RV32_REGISTER_AS, "rd, rs1 and rs2 must be registers");
+
+        let rs1 = memory.read(RV32_REGISTER_AS, b.as_canonical_u32());
+        let rs2 = memory.read(RV32_REGISTER_AS, c.as_canonical_u32());
+        ([rs1.data, rs2.data], Rv32MultReadRecord { rs1, rs2 })
+    }
+
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        _read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord) {
+        let [rd_data] = output.writes;
+        let rd = memory.write(RV32_REGISTER_AS, instruction.a.as_canonical_u32(), rd_data);
+        let to_pc = output.to_pc.unwrap_or(from_state.pc + DEFAULT_PC_STEP);
+        (ExecutionState::new(to_pc, memory.timestamp()), Rv32MultWriteRecord { from_state, rd })
+    }
+
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    ) {
+        let row_slice: &mut Rv32MultAdapterCols<F> = row_slice.borrow_mut();
+        row_slice.from_state = write_record.from_state.map(F::from_canonical_u32);
+        row_slice.rd_ptr = F::from_canonical_u32(write_record.rd.address.pointer);
+        row_slice.rs1_ptr = F::from_canonical_u32(read_record.rs1.address.pointer);
+        row_slice.rs2_ptr = F::from_canonical_u32(read_record.rs2.address.pointer);
+        memory.fill_read_aux(&read_record.rs1, &mut row_slice.reads_aux[0]);
+        memory.fill_read_aux(&read_record.rs2, &mut row_slice.reads_aux[1]);
+        memory.fill_write_aux(&write_record.rd, &mut row_slice.writes_aux);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
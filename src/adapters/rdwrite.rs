@@ -0,0 +1,356 @@
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::{
+    adapters::{RV32_REGISTER_AS, check_rd_write, check_register_accesses},
+    aligned_borrow,
+    core::{PC_BITS, RV32_REGISTER_NUM_LIMBS},
+    execution::{DEFAULT_PC_STEP, ExecutionBridge, ExecutionError, ExecutionState},
+    instructions::{Instruction, LocalOpcode, Rv32JalLuiOpcode::JAL},
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, BasicAdapterInterface, ImmInstruction,
+        VmAdapterAir, VmAdapterChip, VmAdapterInterface,
+    },
+    memory::{
+        MemoryAddress, MemoryBridge, MemoryController, MemoryWriteAuxCols, MemoryWriteRecord,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/adapters/rdwrite.rs
+// for full implementation details.
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32RdWriteAdapterCols<T> {
+        pub from_state: ExecutionState<T>,
+        pub rd_ptr: T,
+        pub rd_aux_cols: MemoryWriteAuxCols<T, RV32_REGISTER_NUM_LIMBS>,
+    }
+}
+
+/// This adapter doesn't read anything, and writes to `[a:4]_1`, where `a` is the register
+/// pointer `rd_ptr`.
+#[derive(Clone, Copy, Debug)]
+pub struct Rv32RdWriteAdapterAir {
+    pub memory_bridge: MemoryBridge,
+    pub execution_bridge: ExecutionBridge,
+}
+
+impl BaseAir<F> for Rv32RdWriteAdapterAir {
+    fn width(&self) -> usize {
+        Rv32RdWriteAdapterCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> VmAdapterAir<AB> for Rv32RdWriteAdapterAir {
+    type Interface =
+        BasicAdapterInterface<AB::Expr, ImmInstruction<AB::Expr>, 0, 1, 0, RV32_REGISTER_NUM_LIMBS>;
+
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    ) {
+        let local_cols: &Rv32RdWriteAdapterCols<AB::Var> = local.borrow();
+        let is_valid = ctx.instruction.is_valid.clone();
+        self.conditional_eval(builder, local_cols, ctx, is_valid, AB::Expr::ZERO);
+    }
+
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var {
+        let local_cols: &Rv32RdWriteAdapterCols<AB::Var> = local.borrow();
+        local_cols.from_state.pc
+    }
+}
+
+impl Rv32RdWriteAdapterAir {
+    /// Writes rd when `needs_write` is set, and executes the instruction with the operands
+    /// `[rd_ptr, 0, imm, RV32_REGISTER_AS, 0, f]`. The timestamp advances by one either way.
+    fn conditional_eval<AB: InteractionBuilder<F = F>>(
+        &self,
+        builder: &mut AB,
+        local_cols: &Rv32RdWriteAdapterCols<AB::Var>,
+        ctx: AdapterAirContext<AB::Expr, <Self as VmAdapterAir<AB>>::Interface>,
+        needs_write: AB::Expr,
+        f: AB::Expr,
+    ) {
+        let ImmInstruction { is_valid, opcode, immediate } = ctx.instruction;
+        let [rd_data] = ctx.writes;
+        let timestamp = local_cols.from_state.timestamp;
+
+        self.memory_bridge
+            .write(
+                MemoryAddress::new(AB::F::from_canonical_u32(RV32_REGISTER_AS), local_cols.rd_ptr),
+                rd_data,
+                timestamp,
+                &local_cols.rd_aux_cols,
+            )
+            .eval(builder, needs_write);
+
+        let to_pc = ctx
+            .to_pc
+            .unwrap_or(local_cols.from_state.pc + AB::F::from_canonical_u32(DEFAULT_PC_STEP));
+        self.execution_bridge
+            .execute(
+                opcode,
+                [
+                    local_cols.rd_ptr.into(),
+                    AB::Expr::ZERO,
+                    immediate,
+                    AB::Expr::from_canonical_u32(RV32_REGISTER_AS),
+                    AB::Expr::ZERO,
+                    f,
+                ],
+                local_cols.from_state,
+                ExecutionState { pc: to_pc, timestamp: timestamp + AB::Expr::ONE },
+            )
+            .eval(builder, is_valid);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32RdWriteWriteRecord {
+    pub from_state: ExecutionState<u32>,
+    pub rd_ptr: u32,
+    /// The write of rd, if any
+    pub rd: Option<MemoryWriteRecord>,
+}
+
+/// Writes the output of the core to the register `rd_ptr = a`.
+pub struct Rv32RdWriteAdapterChip {
+    pub air: Rv32RdWriteAdapterAir,
+}
+
+impl Rv32RdWriteAdapterChip {
+    pub fn new(memory_bridge: MemoryBridge, execution_bridge: ExecutionBridge) -> Self {
+        Self { air: Rv32RdWriteAdapterAir { memory_bridge, execution_bridge } }
+    }
+
+    /// Rejects a JAL whose target is not a valid pc.
+    fn check_to_pc(
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        if instruction.opcode != JAL.global_opcode() {
+            return Ok(());
+        }
+        let to_pc = (F::from_canonical_u32(from_state.pc) + instruction.c).as_canonical_u32();
+        match to_pc < (1 << PC_BITS) {
+            true => Ok(()),
+            false => Err(ExecutionError::PcOutOfBounds { pc: from_state.pc, to_pc }),
+        }
+    }
+
+    /// Writes rd if `needs_write`, and otherwise only advances the timestamp past the write.
+    fn conditional_postprocess(
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, <Self as VmAdapterChip>::Interface>,
+        needs_write: bool,
+    ) -> (ExecutionState<u32>, Rv32RdWriteWriteRecord) {
+        debug_assert_eq!(from_state.timestamp, memory.timestamp());
+        let [rd_data] = output.writes;
+        let rd_ptr = instruction.a.as_canonical_u32();
+        let rd = needs_write.then(|| memory.write(RV32_REGISTER_AS, rd_ptr, rd_data));
+        if !needs_write {
+            memory.increment_timestamp_by(1);
+        }
+        let to_pc = output.to_pc.unwrap_or(from_state.pc + DEFAULT_PC_STEP);
+        let record = Rv32RdWriteWriteRecord { from_state, rd_ptr, rd };
+        (ExecutionState::new(to_pc, memory.timestamp()), record)
+    }
+}
+
+impl VmAdapterChip for Rv32RdWriteAdapterChip {
+    type ReadRecord = ();
+    type WriteRecord = Rv32RdWriteWriteRecord;
+    type Air = Rv32RdWriteAdapterAir;
+    type Interface = BasicAdapterInterface<F, ImmInstruction<F>, 0, 1, 0, RV32_REGISTER_NUM_LIMBS>;
+
+    /// Rejects the JALs beyond [PC_BITS] bits, which the core chip panics on. The immediate is
+    /// a field element that may encode a negative offset, so a jump below zero wraps beyond.
+    fn check(
+        &self,
+        memory: &MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        check_register_accesses(memory, from_state.pc, &[instruction.a], 1)?;
+        check_rd_write(instruction, from_state.pc)?;
+        Self::check_to_pc(instruction, from_state)
+    }
+
+    fn preprocess(
+        &mut self,
+        _memory: &mut MemoryController,
+        _instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord) {
+        ([], ())
+    }
+
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        _read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord) {
+        Self::conditional_postprocess(memory, instruction, from_state, output, true)
+    }
+
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        _read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    ) {
+        let adapter_cols: &mut Rv32RdWriteAdapterCols<F> = row_slice.borrow_mut();
+        adapter_cols.from_state = write_record.from_state.map(F::from_canonical_u32);
+        adapter_cols.rd_ptr = F::from_canonical_u32(write_record.rd_ptr);
+        if let Some(rd) = &write_record.rd {
+            memory.fill_write_aux(rd, &mut adapter_cols.rd_aux_cols);
+        }
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32CondRdWriteAdapterCols<T> {
+        pub inner: Rv32RdWriteAdapterCols<T>,
+        pub needs_write: T,
+    }
+}
+
+/// Like [Rv32RdWriteAdapterAir], for instructions of the form `OP a, 0, c, 1, 0, f` that only
+/// write `[a:4]_1` when `f = 1`, such as the JALs whose rd is `x0`.
+#[derive(Clone, Copy, Debug)]
+pub struct Rv32CondRdWriteAdapterAir {
+    pub inner: Rv32RdWriteAdapterAir,
+}
+
+impl BaseAir<F> for Rv32CondRdWriteAdapterAir {
+    fn width(&self) -> usize {
+        Rv32CondRdWriteAdapterCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> VmAdapterAir<AB> for Rv32CondRdWriteAdapterAir {
+    type Interface =
+        BasicAdapterInterface<AB::Expr, ImmInstruction<AB::Expr>, 0, 1, 0, RV32_REGISTER_NUM_LIMBS>;
+
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    ) {
+        let local_cols: &Rv32CondRdWriteAdapterCols<AB::Var> = local.borrow();
+        let needs_write = local_cols.needs_write;
+        builder.assert_bool(needs_write);
+        builder.when(needs_write).assert_one(ctx.instruction.is_valid.clone());
+        self.inner.conditional_eval(
+            builder,
+            &local_cols.inner,
+            ctx,
+            needs_write.into(),
+            needs_write.into(),
+        );
+    }
+
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var {
+        let local_cols: &Rv32CondRdWriteAdapterCols<AB::Var> = local.borrow();
+        local_cols.inner.from_state.pc
+    }
+}
+
+/// Writes the output of the core to the register `rd_ptr = a` when the operand `f` is one.
+pub struct Rv32CondRdWriteAdapterChip {
+    pub air: Rv32CondRdWriteAdapterAir,
+    inner: Rv32RdWriteAdapterChip,
+}
+
+impl Rv32CondRdWriteAdapterChip {
+    pub fn new(memory_bridge: MemoryBridge, execution_bridge: ExecutionBridge) -> Self {
+        let inner = Rv32RdWriteAdapterChip::new(memory_bridge, execution_bridge);
+        Self { air: Rv32CondRdWriteAdapterAir { inner: inner.air }, inner }
+    }
+}
+
+impl VmAdapterChip for Rv32CondRdWriteAdapterChip {
+    type ReadRecord = ();
+    type WriteRecord = Rv32RdWriteWriteRecord;
+    type Air = Rv32CondRdWriteAdapterAir;
+    type Interface = BasicAdapterInterface<F, ImmInstruction<F>, 0, 1, 0, RV32_REGISTER_NUM_LIMBS>;
+
+    fn check(
+        &self,
+        memory: &MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        check_register_accesses(memory, from_state.pc, &[instruction.a], 1)?;
+        if instruction.f == F::ONE {
+            check_rd_write(instruction, from_state.pc)?;
+        }
+        Rv32RdWriteAdapterChip::check_to_pc(instruction, from_state)
+    }
+
+    fn preprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord) {
+        let f = instruction.f.as_canonical_u32();
+        assert!(f <= 1, "f {f} is not a bit");
+        self.inner.preprocess(memory, instruction)
+    }
+
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        _read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord) {
+        let needs_write = instruction.f == F::ONE;
+        Rv32RdWriteAdapterChip::conditional_postprocess(
+            memory,
+            instruction,
+            from_state,
+            output,
+            needs_write,
+        )
+    }
+
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    ) {
+        let needs_write = F::from_bool(write_record.rd.is_some());
+        let inner_width = Rv32RdWriteAdapterCols::<F>::width();
+        let inner_row = &mut row_slice[..inner_width];
+        self.inner.generate_trace_row(inner_row, read_record, write_record, memory);
+        let adapter_cols: &mut Rv32CondRdWriteAdapterCols<F> = row_slice.borrow_mut();
+        adapter_cols.needs_write = needs_write;
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
@@ -0,0 +1,203 @@
+use core::{fmt, mem};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/derive/src/lib.rs
+// for full implementation details.
+
+/// A column struct that can be viewed in place over a row `&[T]` of the trace.
+///
+/// Implement this with [aligned_borrow!](crate::aligned_borrow!), which also declares the struct
+/// `#[repr(C)]` and implements `Borrow<Cols<T>>` and `BorrowMut<Cols<T>>` for `[T]`.
+///
+/// # Safety
+///
+/// `Self` must be `#[repr(C)]` and every field must be a `T`, another `AlignedBorrow<T>` struct, or
+/// an array of either, so that `Self` has the layout of `[T; Self::WIDTH]`. The macro checks this
+/// with [ColumnField]. The size and alignment are also validated by [try_borrow] and
+/// [try_borrow_mut] before any cast, so a wrong `WIDTH` or alignment is rejected there instead of
+/// causing undefined behavior.
+pub unsafe trait AlignedBorrow<T>: Sized {
+    /// The number of columns, i.e. the number of `T`s in `Self`.
+    const WIDTH: usize;
+}
+
+/// A type that may be a field of an [AlignedBorrow] struct over the cell type `T`: `T` itself,
+/// another [AlignedBorrow] struct, or an array of either.
+///
+/// The trait is sealed, so a field such as `Cell<T>` is rejected even though it has the layout of
+/// `T`: borrowing the struct from a shared `&[T]` would otherwise allow writing through it. `Kind`
+/// only keeps the implementations apart and is always inferred.
+pub trait ColumnField<T, Kind>: sealed::Sealed<T, Kind> {}
+
+impl<T, Kind, C: sealed::Sealed<T, Kind>> ColumnField<T, Kind> for C {}
+
+mod sealed {
+    use super::AlignedBorrow;
+
+    pub struct Cell;
+    pub struct Nested;
+    pub struct CellArray;
+    pub struct NestedArray;
+
+    pub trait Sealed<T, Kind> {}
+
+    impl<T> Sealed<T, Cell> for T {}
+    impl<T, C: AlignedBorrow<T>> Sealed<T, Nested> for C {}
+    impl<T, const N: usize> Sealed<T, CellArray> for [T; N] {}
+    impl<T, C: AlignedBorrow<T>, const N: usize> Sealed<T, NestedArray> for [C; N] {}
+}
+
+/// Fails to compile unless `C` is a [ColumnField] of `T`. Used by [aligned_borrow!].
+#[doc(hidden)]
+pub const fn assert_column_field<T, C: ColumnField<T, Kind>, Kind>() {}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BorrowError {
+    /// The row does not have exactly `WIDTH` elements.
+    WrongLength { expected: usize, actual: usize },
+    /// The column struct is not the size of `WIDTH` `T`s.
+    WrongSize { expected: usize, actual: usize },
+    /// The row is not aligned for the column struct.
+    Misaligned { align: usize },
+}
+
+impl fmt::Display for BorrowError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongLength { expected, actual } => {
+                write!(f, "Wrong row length: expected {expected}, got {actual}")
+            }
+            Self::WrongSize { expected, actual } => {
+                write!(f, "Wrong column struct size: expected {expected} bytes, got {actual}")
+            }
+            Self::Misaligned { align } => write!(f, "Row is not aligned to {align} bytes"),
+        }
+    }
+}
+
+impl std::error::Error for BorrowError {}
+
+fn check_layout<T, C: AlignedBorrow<T>>(row: &[T]) -> Result<(), BorrowError> {
+    if row.len() != C::WIDTH {
+        return Err(BorrowError::WrongLength { expected: C::WIDTH, actual: row.len() });
+    }
+    let expected = C::WIDTH * mem::size_of::<T>();
+    if mem::size_of::<C>() != expected {
+        return Err(BorrowError::WrongSize { expected, actual: mem::size_of::<C>() });
+    }
+    if !row.as_ptr().cast::<C>().is_aligned() {
+        return Err(BorrowError::Misaligned { align: mem::align_of::<C>() });
+    }
+    Ok(())
+}
+
+/// Views `row` as the column struct `C` without copying.
+pub fn try_borrow<T, C: AlignedBorrow<T>>(row: &[T]) -> Result<&C, BorrowError> {
+    check_layout::<T, C>(row)?;
+    // SAFETY: `row` covers exactly `size_of::<C>()` bytes and is aligned for `C`, and by the
+    // contract of `AlignedBorrow` every byte pattern of `[T; WIDTH]` is a valid `C`. The returned
+    // reference borrows `row`, so the usual aliasing rules carry over.
+    Ok(unsafe { &*row.as_ptr().cast::<C>() })
+}
+
+/// Views `row` as the mutable column struct `C` without copying.
+pub fn try_borrow_mut<T, C: AlignedBorrow<T>>(row: &mut [T]) -> Result<&mut C, BorrowError> {
+    check_layout::<T, C>(row)?;
+    // SAFETY: as in `try_borrow`, and the returned reference uniquely borrows `row`.
+    Ok(unsafe { &mut *row.as_mut_ptr().cast::<C>() })
+}
+
+/// Declares a `#[repr(C)]` column struct generic over its cell type and implements
+/// [AlignedBorrow] for it, along with `width()` and `Borrow`/`BorrowMut` from `[T]`.
+///
+/// The borrows panic with a [BorrowError] if the row has the wrong length, so a caller that wants
+/// to handle the error should use [try_borrow] and [try_borrow_mut].
+///
+/// ```
+/// use core::borrow::Borrow;
+/// miri_test::aligned_borrow! {
+///     pub struct ExampleCols<T, const N: usize> {
+///         pub is_valid: T,
+///         pub limbs: [T; N],
+///     }
+/// }
+/// assert_eq!(ExampleCols::<u8, 3>::width(), 4);
+/// let row = [1u32, 2, 3, 4];
+/// let cols: &ExampleCols<u32, 3> = row[..].borrow();
+/// assert_eq!(cols.limbs, [2, 3, 4]);
+/// ```
+///
+/// Fields that are not made of the cell type are rejected:
+///
+/// ```compile_fail
+/// miri_test::aligned_borrow! {
+///     pub struct BadCols<T> {
+///         pub value: T,
+///         pub flag: bool,
+///     }
+/// }
+/// ```
+///
+/// So are fields with interior mutability, even when they have the layout of the cell type:
+///
+/// ```compile_fail
+/// use core::cell::Cell;
+/// miri_test::aligned_borrow! {
+///     pub struct CellCols<T> {
+///         pub value: T,
+///         pub cell: Cell<T>,
+///     }
+/// }
+/// ```
+#[macro_export]
+macro_rules! aligned_borrow {
+    (
+        $(#[$attr:meta])*
+        $vis:vis struct $name:ident<$t:ident $(, const $c:ident: usize)* $(,)?> {
+            $($(#[$field_attr:meta])* $field_vis:vis $field:ident: $ty:ty),* $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[repr(C)]
+        $vis struct $name<$t $(, const $c: usize)*> {
+            $($(#[$field_attr])* $field_vis $field: $ty),*
+        }
+
+        // SAFETY: the struct is `#[repr(C)]` and every field is a `ColumnField`, which fails to
+        // compile otherwise.
+        unsafe impl<$t $(, const $c: usize)*> $crate::aligned_borrow::AlignedBorrow<$t>
+            for $name<$t $(, $c)*>
+        {
+            const WIDTH: usize = {
+                $($crate::aligned_borrow::assert_column_field::<$t, $ty, _>();)*
+                let width = ::core::mem::size_of::<$name<u8 $(, $c)*>>();
+                // Tripling the size of `T` triples the size of the struct only if every byte of
+                // it belongs to a `T`.
+                assert!(
+                    ::core::mem::size_of::<$name<[u8; 3] $(, $c)*>>() == 3 * width,
+                    concat!("every field of ", stringify!($name), " must be made of its cell type"),
+                );
+                width
+            };
+        }
+
+        impl<$t $(, const $c: usize)*> $name<$t $(, $c)*> {
+            /// The number of columns.
+            pub const fn width() -> usize {
+                <Self as $crate::aligned_borrow::AlignedBorrow<$t>>::WIDTH
+            }
+        }
+
+        impl<$t $(, const $c: usize)*> ::core::borrow::Borrow<$name<$t $(, $c)*>> for [$t] {
+            fn borrow(&self) -> &$name<$t $(, $c)*> {
+                $crate::aligned_borrow::try_borrow(self).unwrap_or_else(|e| panic!("{e}"))
+            }
+        }
+
+        impl<$t $(, const $c: usize)*> ::core::borrow::BorrowMut<$name<$t $(, $c)*>> for [$t] {
+            fn borrow_mut(&mut self) -> &mut $name<$t $(, $c)*> {
+                $crate::aligned_borrow::try_borrow_mut(self).unwrap_or_else(|e| panic!("{e}"))
+            }
+        }
+    };
+}
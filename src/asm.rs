@@ -0,0 +1,556 @@
+use core::fmt;
+use std::{collections::HashMap, error::Error};
+
+use crate::{
+    adapters::RV32_REGISTER_AS,
+    core::RV32_REGISTER_NUM_LIMBS,
+    decoder::{
+        DecodeError, FUNCT7_ALT, FUNCT7_MULDIV, RV32_OPCODE_AUIPC, RV32_OPCODE_BRANCH,
+        RV32_OPCODE_JAL, RV32_OPCODE_JALR, RV32_OPCODE_LOAD, RV32_OPCODE_LUI, RV32_OPCODE_OP,
+        RV32_OPCODE_OP_IMM, RV32_OPCODE_STORE, Rv32Decoder, b_imm, funct3, funct7, i_imm, j_imm,
+        opcode, rd, rs1, rs2, s_imm, u_imm,
+    },
+    instructions::{
+        BaseAluOpcode, BranchEqualOpcode, BranchLessThanOpcode, DivRemOpcode, Instruction,
+        LessThanOpcode, LocalOpcode, MulHOpcode, MulOpcode, Rv32AuipcOpcode, Rv32JalLuiOpcode,
+        Rv32JalrOpcode, Rv32LoadStoreOpcode, ShiftOpcode, VmOpcode,
+    },
+    loadstore::is_load_opcode,
+    openvm_stark_backend::field::{F, FieldAlgebra, PrimeField32},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/transpiler/src/rrs.rs
+// for full implementation details.
+
+/// The ABI names of the registers `x0` to `x31`. `fp` is also accepted for `s0`.
+pub const ABI_NAMES: [&str; 32] = [
+    "zero", "ra", "sp", "gp", "tp", "t0", "t1", "t2", "s0", "s1", "a0", "a1", "a2", "a3", "a4",
+    "a5", "a6", "a7", "s2", "s3", "s4", "s5", "s6", "s7", "s8", "s9", "s10", "s11", "t3", "t4",
+    "t5", "t6",
+];
+
+/// How the operands of an instruction are written and encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    /// `rd, rs1, rs2`
+    R,
+    /// `rd, rs1, imm`
+    I,
+    /// `rd, rs1, shamt`, where funct7 selects the shift.
+    Shift,
+    /// `rd, imm(rs1)`
+    Load,
+    /// `rs2, imm(rs1)`
+    Store,
+    /// `rs1, rs2, target`
+    Branch,
+    /// `rd, imm`, where `imm` is the upper 20 bits.
+    U,
+    /// `rd, target`
+    Jal,
+    /// `rd, imm(rs1)`
+    Jalr,
+}
+
+/// `(mnemonic, format, opcode, funct3, funct7)` of every RV32IM instruction with a core. funct7
+/// is only encoded for the R and shift formats.
+#[rustfmt::skip]
+const INSTRUCTIONS: [(&str, Format, u32, u32, u32); 45] = [
+    ("add", Format::R, RV32_OPCODE_OP, 0b000, 0),
+    ("sub", Format::R, RV32_OPCODE_OP, 0b000, FUNCT7_ALT),
+    ("sll", Format::R, RV32_OPCODE_OP, 0b001, 0),
+    ("slt", Format::R, RV32_OPCODE_OP, 0b010, 0),
+    ("sltu", Format::R, RV32_OPCODE_OP, 0b011, 0),
+    ("xor", Format::R, RV32_OPCODE_OP, 0b100, 0),
+    ("srl", Format::R, RV32_OPCODE_OP, 0b101, 0),
+    ("sra", Format::R, RV32_OPCODE_OP, 0b101, FUNCT7_ALT),
+    ("or", Format::R, RV32_OPCODE_OP, 0b110, 0),
+    ("and", Format::R, RV32_OPCODE_OP, 0b111, 0),
+    ("mul", Format::R, RV32_OPCODE_OP, 0b000, FUNCT7_MULDIV),
+    ("mulh", Format::R, RV32_OPCODE_OP, 0b001, FUNCT7_MULDIV),
+    ("mulhsu", Format::R, RV32_OPCODE_OP, 0b010, FUNCT7_MULDIV),
+    ("mulhu", Format::R, RV32_OPCODE_OP, 0b011, FUNCT7_MULDIV),
+    ("div", Format::R, RV32_OPCODE_OP, 0b100, FUNCT7_MULDIV),
+    ("divu", Format::R, RV32_OPCODE_OP, 0b101, FUNCT7_MULDIV),
+    ("rem", Format::R, RV32_OPCODE_OP, 0b110, FUNCT7_MULDIV),
+    ("remu", Format::R, RV32_OPCODE_OP, 0b111, FUNCT7_MULDIV),
+    ("addi", Format::I, RV32_OPCODE_OP_IMM, 0b000, 0),
+    ("slti", Format::I, RV32_OPCODE_OP_IMM, 0b010, 0),
+    ("sltiu", Format::I, RV32_OPCODE_OP_IMM, 0b011, 0),
+    ("xori", Format::I, RV32_OPCODE_OP_IMM, 0b100, 0),
+    ("ori", Format::I, RV32_OPCODE_OP_IMM, 0b110, 0),
+    ("andi", Format::I, RV32_OPCODE_OP_IMM, 0b111, 0),
+    ("slli", Format::Shift, RV32_OPCODE_OP_IMM, 0b001, 0),
+    ("srli", Format::Shift, RV32_OPCODE_OP_IMM, 0b101, 0),
+    ("srai", Format::Shift, RV32_OPCODE_OP_IMM, 0b101, FUNCT7_ALT),
+    ("lb", Format::Load, RV32_OPCODE_LOAD, 0b000, 0),
+    ("lh", Format::Load, RV32_OPCODE_LOAD, 0b001, 0),
+    ("lw", Format::Load, RV32_OPCODE_LOAD, 0b010, 0),
+    ("lbu", Format::Load, RV32_OPCODE_LOAD, 0b100, 0),
+    ("lhu", Format::Load, RV32_OPCODE_LOAD, 0b101, 0),
+    ("sb", Format::Store, RV32_OPCODE_STORE, 0b000, 0),
+    ("sh", Format::Store, RV32_OPCODE_STORE, 0b001, 0),
+    ("sw", Format::Store, RV32_OPCODE_STORE, 0b010, 0),
+    ("beq", Format::Branch, RV32_OPCODE_BRANCH, 0b000, 0),
+    ("bne", Format::Branch, RV32_OPCODE_BRANCH, 0b001, 0),
+    ("blt", Format::Branch, RV32_OPCODE_BRANCH, 0b100, 0),
+    ("bge", Format::Branch, RV32_OPCODE_BRANCH, 0b101, 0),
+    ("bltu", Format::Branch, RV32_OPCODE_BRANCH, 0b110, 0),
+    ("bgeu", Format::Branch, RV32_OPCODE_BRANCH, 0b111, 0),
+    ("lui", Format::U, RV32_OPCODE_LUI, 0, 0),
+    ("auipc", Format::U, RV32_OPCODE_AUIPC, 0, 0),
+    ("jal", Format::Jal, RV32_OPCODE_JAL, 0, 0),
+    ("jalr", Format::Jalr, RV32_OPCODE_JALR, 0b000, 0),
+];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AsmError {
+    /// Neither an RV32IM instruction with a core nor a supported pseudo-instruction.
+    UnknownMnemonic {
+        line: usize,
+        mnemonic: String,
+    },
+    /// The instruction takes `expected` operands.
+    OperandCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidRegister {
+        line: usize,
+        operand: String,
+    },
+    /// An operand that should be a number, or a memory operand `imm(rs1)`.
+    InvalidImmediate {
+        line: usize,
+        operand: String,
+    },
+    /// An immediate that does not fit in its field, or a jump to an odd offset.
+    ImmediateOutOfRange {
+        line: usize,
+        imm: i64,
+    },
+    /// A label that is not an identifier.
+    InvalidLabel {
+        line: usize,
+        label: String,
+    },
+    DuplicateLabel {
+        line: usize,
+        label: String,
+    },
+    UnknownLabel {
+        line: usize,
+        label: String,
+    },
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnknownMnemonic { line, mnemonic } => {
+                write!(f, "line {line}: unknown mnemonic {mnemonic}")
+            }
+            Self::OperandCount { line, expected, found } => {
+                write!(f, "line {line}: expected {expected} operands, found {found}")
+            }
+            Self::InvalidRegister { line, operand } => {
+                write!(f, "line {line}: invalid register {operand}")
+            }
+            Self::InvalidImmediate { line, operand } => {
+                write!(f, "line {line}: invalid immediate {operand}")
+            }
+            Self::ImmediateOutOfRange { line, imm } => {
+                write!(f, "line {line}: immediate {imm} out of range")
+            }
+            Self::InvalidLabel { line, label } => write!(f, "line {line}: invalid label {label}"),
+            Self::DuplicateLabel { line, label } => {
+                write!(f, "line {line}: duplicate label {label}")
+            }
+            Self::UnknownLabel { line, label } => write!(f, "line {line}: unknown label {label}"),
+        }
+    }
+}
+
+impl Error for AsmError {}
+
+/// An instruction of the source, with its 1-based line number.
+struct Statement<'a> {
+    line: usize,
+    mnemonic: &'a str,
+    operands: Vec<String>,
+}
+
+/// Assembles RV32IM assembly into instruction words.
+///
+/// Every line holds optional `label:`s followed by an optional instruction, and `#` starts a
+/// comment. Registers are written `x0` to `x31` or by their [ABI_NAMES]. Immediates are decimal,
+/// or hexadecimal and binary with the `0x` and `0b` prefixes. Branch and jump targets are labels
+/// or byte offsets. The pseudo-instructions `nop`, `mv`, `j`, `jal` with the target only, `jr`
+/// and `ret` are expanded to a single instruction each. `li` is expanded to `addi` if its
+/// 32-bit immediate sign extends from 12 bits, and otherwise to `lui` followed by `addi` unless
+/// the lower 12 bits are zero.
+pub fn assemble(source: &str) -> Result<Vec<u32>, AsmError> {
+    let mut labels = HashMap::new();
+    let mut statements = Vec::new();
+    for (index, text) in source.lines().enumerate() {
+        let line = index + 1;
+        let mut text = text.split('#').next().unwrap_or_default().trim();
+        while let Some((label, rest)) = text.split_once(':') {
+            let label = label.trim();
+            if !is_identifier(label) {
+                return Err(AsmError::InvalidLabel { line, label: label.to_string() });
+            }
+            if labels.insert(label, statements.len()).is_some() {
+                return Err(AsmError::DuplicateLabel { line, label: label.to_string() });
+            }
+            text = rest.trim();
+        }
+        if text.is_empty() {
+            continue;
+        }
+        let (mnemonic, operands) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+        let operands: Vec<String> = match operands.trim() {
+            "" => Vec::new(),
+            operands => operands.split(',').map(|operand| operand.trim().into()).collect(),
+        };
+        match (mnemonic, operands.as_slice()) {
+            ("li", [rd, imm]) if let Some((upper, lower)) = split_li(imm) => {
+                let base = match upper {
+                    0 => "zero".to_string(),
+                    _ => {
+                        let operands = vec![rd.clone(), format!("{upper:#x}")];
+                        statements.push(Statement { line, mnemonic: "lui", operands });
+                        rd.clone()
+                    }
+                };
+                if upper == 0 || lower != 0 {
+                    let operands = vec![rd.clone(), base, lower.to_string()];
+                    statements.push(Statement { line, mnemonic: "addi", operands });
+                }
+            }
+            _ => statements.push(Statement { line, mnemonic, operands }),
+        }
+    }
+
+    statements
+        .iter()
+        .enumerate()
+        .map(|(index, statement)| encode(statement, index, &labels))
+        .collect()
+}
+
+/// Assembles RV32IM assembly into the [Instruction]s of the default [Rv32Decoder]. See
+/// [assemble].
+pub fn assemble_instructions(source: &str) -> Result<Vec<Instruction<F>>, AsmError> {
+    let words = assemble(source)?;
+    Ok(Rv32Decoder::default().decode_all(&words).expect("assembled words decode"))
+}
+
+/// Prints an instruction word as the assembly that [assemble] turns back into it, with ABI
+/// register names and byte offsets as jump targets.
+pub fn disassemble(word: u32) -> Result<String, DecodeError> {
+    Rv32Decoder::default().decode(word)?;
+    let &(mnemonic, format, ..) = INSTRUCTIONS
+        .iter()
+        .find(|&&(_, format, op, f3, f7)| {
+            op == opcode(word)
+                && (f3 == funct3(word) || matches!(format, Format::U | Format::Jal))
+                && (f7 == funct7(word) || !matches!(format, Format::R | Format::Shift))
+        })
+        .expect("decoded words have a mnemonic");
+    let shamt = rs2(word);
+    let [rd, rs1, rs2] = [rd(word), rs1(word), rs2(word)].map(|reg| ABI_NAMES[reg]);
+    Ok(match format {
+        Format::R => format!("{mnemonic} {rd}, {rs1}, {rs2}"),
+        Format::I => format!("{mnemonic} {rd}, {rs1}, {}", i_imm(word)),
+        Format::Shift => format!("{mnemonic} {rd}, {rs1}, {shamt}"),
+        Format::Load | Format::Jalr => format!("{mnemonic} {rd}, {}({rs1})", i_imm(word)),
+        Format::Store => format!("{mnemonic} {rs2}, {}({rs1})", s_imm(word)),
+        Format::Branch => format!("{mnemonic} {rs1}, {rs2}, {}", b_imm(word)),
+        Format::U => format!("{mnemonic} {rd}, {:#x}", u_imm(word) >> 12),
+        Format::Jal => format!("{mnemonic} {rd}, {}", j_imm(word)),
+    })
+}
+
+/// Disassembles consecutive instruction words into one line each, stopping at the first illegal
+/// one.
+pub fn disassemble_all(words: &[u32]) -> Result<String, DecodeError> {
+    words.iter().map(|&word| Ok(disassemble(word)? + "\n")).collect()
+}
+
+/// Prints an [Instruction] of the default [Rv32Decoder] as the assembly that
+/// [assemble_instructions] turns back into it, or returns `None` if it is not an RV32IM
+/// instruction with registers as operands. The nops that the writes to `x0` decode to are printed
+/// as the JALs they are.
+pub fn disassemble_instruction(instruction: &Instruction<F>) -> Option<String> {
+    let Instruction { opcode, a, b, c, e, g, .. } = *instruction;
+    let is_imm = e != F::from_canonical_u32(RV32_REGISTER_AS);
+    // The names of an ALU opcode with a register and an immediate as its second operand.
+    let alu = |(name, imm_name), imm_format| match is_imm {
+        true => (imm_name, imm_format),
+        false => (name, Format::R),
+    };
+    let (mnemonic, format) = if let Some(local_opcode) = opcode.local_opcode::<BaseAluOpcode>() {
+        let names = [("add", "addi"), ("sub", ""), ("xor", "xori"), ("or", "ori"), ("and", "andi")];
+        alu(names[local_opcode.local_usize()], Format::I)
+    } else if let Some(local_opcode) = opcode.local_opcode::<ShiftOpcode>() {
+        let names = [("sll", "slli"), ("srl", "srli"), ("sra", "srai")];
+        alu(names[local_opcode.local_usize()], Format::Shift)
+    } else if let Some(local_opcode) = opcode.local_opcode::<LessThanOpcode>() {
+        let names = [("slt", "slti"), ("sltu", "sltiu")];
+        alu(names[local_opcode.local_usize()], Format::I)
+    } else if let Some(name) = local_name::<MulOpcode>(opcode, &["mul"])
+        .or_else(|| local_name::<MulHOpcode>(opcode, &["mulh", "mulhsu", "mulhu"]))
+        .or_else(|| local_name::<DivRemOpcode>(opcode, &["div", "divu", "rem", "remu"]))
+    {
+        (name, Format::R)
+    } else if let Some(local_opcode) = opcode.local_opcode::<Rv32LoadStoreOpcode>() {
+        let names = ["lw", "lbu", "lhu", "sw", "sh", "sb", "lb", "lh"];
+        let format = if is_load_opcode(local_opcode) { Format::Load } else { Format::Store };
+        (names[local_opcode.local_usize()], format)
+    } else if let Some(name) = local_name::<BranchEqualOpcode>(opcode, &["beq", "bne"])
+        .or_else(|| local_name::<BranchLessThanOpcode>(opcode, &["blt", "bltu", "bge", "bgeu"]))
+    {
+        (name, Format::Branch)
+    } else if let Some(local_opcode) = opcode.local_opcode::<Rv32JalLuiOpcode>() {
+        match local_opcode {
+            Rv32JalLuiOpcode::JAL => ("jal", Format::Jal),
+            Rv32JalLuiOpcode::LUI => ("lui", Format::U),
+        }
+    } else if opcode == Rv32JalrOpcode::JALR.global_opcode() {
+        ("jalr", Format::Jalr)
+    } else if opcode == Rv32AuipcOpcode::AUIPC.global_opcode() {
+        ("auipc", Format::U)
+    } else {
+        return None;
+    };
+    if mnemonic.is_empty() {
+        return None;
+    }
+
+    let register = |operand: F| {
+        let pointer = operand.as_canonical_u32() as usize;
+        match pointer % RV32_REGISTER_NUM_LIMBS {
+            0 => ABI_NAMES.get(pointer / RV32_REGISTER_NUM_LIMBS).copied(),
+            _ => None,
+        }
+    };
+    // Branch and jump offsets are signed field elements.
+    let offset = |operand: F| match operand.as_canonical_u32() {
+        value if value < F::ORDER_U32 / 2 => i64::from(value),
+        value => -i64::from(F::ORDER_U32 - value),
+    };
+    let c_value = c.as_canonical_u32();
+    // The ALU immediates are sign extended from 24 bits, and those of loads, stores and JALR are
+    // split into their lower 16 bits and their sign.
+    let alu_imm = ((c_value << 8) as i32) >> 8;
+    let imm = c_value.wrapping_add(g.as_canonical_u32().wrapping_mul(0xffff_0000)) as i32;
+    let [reg_a, reg_b] = [register(a)?, register(b)?];
+    Some(match format {
+        Format::R => format!("{mnemonic} {reg_a}, {reg_b}, {}", register(c)?),
+        Format::I => format!("{mnemonic} {reg_a}, {reg_b}, {alu_imm}"),
+        Format::Shift => format!("{mnemonic} {reg_a}, {reg_b}, {c_value}"),
+        Format::Load | Format::Store | Format::Jalr => {
+            format!("{mnemonic} {reg_a}, {imm}({reg_b})")
+        }
+        Format::Branch => format!("{mnemonic} {reg_a}, {reg_b}, {}", offset(c)),
+        // AUIPC holds the upper 24 bits of its immediate, whose lowest limb is zero.
+        Format::U if mnemonic == "auipc" => format!("{mnemonic} {reg_a}, {:#x}", c_value >> 4),
+        Format::U => format!("{mnemonic} {reg_a}, {c_value:#x}"),
+        Format::Jal => format!("{mnemonic} {reg_a}, {}", offset(c)),
+    })
+}
+
+/// The name of `opcode` among `names`, which are in the order of the opcode class, if it is in
+/// the class.
+fn local_name<Opcode: LocalOpcode>(
+    opcode: VmOpcode,
+    names: &[&'static str],
+) -> Option<&'static str> {
+    opcode.local_opcode::<Opcode>().map(|local_opcode| names[local_opcode.local_usize()])
+}
+
+/// Encodes the `index`-th statement, expanding pseudo-instructions.
+fn encode(
+    statement: &Statement,
+    index: usize,
+    labels: &HashMap<&str, usize>,
+) -> Result<u32, AsmError> {
+    let Statement { line, mnemonic, ref operands } = *statement;
+    let operands: Vec<&str> = operands.iter().map(String::as_str).collect();
+    let pseudo_operands = match mnemonic {
+        "nop" | "ret" => Some(0),
+        "j" | "jr" => Some(1),
+        "mv" | "li" => Some(2),
+        _ => None,
+    };
+    if let Some(expected) = pseudo_operands
+        && operands.len() != expected
+    {
+        return Err(AsmError::OperandCount { line, expected, found: operands.len() });
+    }
+    let (mnemonic, operands): (&str, Vec<String>) = match (mnemonic, operands.as_slice()) {
+        ("nop", []) => ("addi", vec!["x0".into(), "x0".into(), "0".into()]),
+        ("mv", &[rd, rs]) => ("addi", vec![rd.into(), rs.into(), "0".into()]),
+        ("li", &[rd, imm]) => ("addi", vec![rd.into(), "x0".into(), imm.into()]),
+        ("j", &[target]) => ("jal", vec!["x0".into(), target.into()]),
+        ("jal", &[target]) => ("jal", vec!["ra".into(), target.into()]),
+        ("jr", &[rs]) => ("jalr", vec!["x0".into(), format!("0({rs})")]),
+        ("ret", []) => ("jalr", vec!["x0".into(), "0(ra)".into()]),
+        _ => (mnemonic, operands.iter().map(|&operand| operand.into()).collect()),
+    };
+    let operands: Vec<&str> = operands.iter().map(String::as_str).collect();
+    let &(_, format, opcode, funct3, funct7) = INSTRUCTIONS
+        .iter()
+        .find(|(name, ..)| *name == mnemonic)
+        .ok_or_else(|| AsmError::UnknownMnemonic { line, mnemonic: mnemonic.to_string() })?;
+
+    let register = |operand: &str| {
+        parse_register(operand)
+            .ok_or_else(|| AsmError::InvalidRegister { line, operand: operand.to_string() })
+    };
+    let immediate = |operand: &str, range: (i64, i64)| {
+        let imm = parse_immediate(operand)
+            .ok_or_else(|| AsmError::InvalidImmediate { line, operand: operand.to_string() })?;
+        match (range.0..=range.1).contains(&imm) {
+            true => Ok(imm as u32),
+            false => Err(AsmError::ImmediateOutOfRange { line, imm }),
+        }
+    };
+    // The 12-bit immediate and the base register of a memory operand `imm(rs1)`.
+    let memory = |operand: &str| {
+        let invalid = || AsmError::InvalidImmediate { line, operand: operand.to_string() };
+        let (imm, rs1) =
+            operand.strip_suffix(')').and_then(|o| o.split_once('(')).ok_or_else(invalid)?;
+        let imm = match imm.trim() {
+            "" => 0,
+            imm => immediate(imm, (-2048, 2047))?,
+        };
+        Ok::<_, AsmError>((imm, register(rs1.trim())?))
+    };
+    // The byte offset to a label or a numeric target, which must be even.
+    let target = |operand: &str, bits: u32| {
+        let offset = match labels.get(operand) {
+            Some(&label_index) => 4 * (label_index as i64 - index as i64),
+            None if is_identifier(operand) => {
+                return Err(AsmError::UnknownLabel { line, label: operand.to_string() });
+            }
+            None => parse_immediate(operand)
+                .ok_or_else(|| AsmError::InvalidImmediate { line, operand: operand.to_string() })?,
+        };
+        match offset % 2 == 0 && (-(1 << (bits - 1))..(1 << (bits - 1))).contains(&offset) {
+            true => Ok(offset as u32),
+            false => Err(AsmError::ImmediateOutOfRange { line, imm: offset }),
+        }
+    };
+
+    let expected = match format {
+        Format::R | Format::I | Format::Shift | Format::Branch => 3,
+        _ => 2,
+    };
+    if operands.len() != expected {
+        return Err(AsmError::OperandCount { line, expected, found: operands.len() });
+    }
+    let funct3 = funct3 << 12;
+    Ok(opcode
+        | match format {
+            Format::R => {
+                let [rd, rs1, rs2] = [operands[0], operands[1], operands[2]].map(register);
+                (funct7 << 25) | (rs2? << 20) | (rs1? << 15) | funct3 | (rd? << 7)
+            }
+            Format::I | Format::Shift => {
+                let imm = match format {
+                    Format::I => immediate(operands[2], (-2048, 2047))?,
+                    _ => (funct7 << 5) | immediate(operands[2], (0, 31))?,
+                };
+                (imm << 20)
+                    | (register(operands[1])? << 15)
+                    | funct3
+                    | (register(operands[0])? << 7)
+            }
+            Format::Load | Format::Jalr => {
+                let rd = register(operands[0])?;
+                let (imm, rs1) = memory(operands[1])?;
+                (imm << 20) | (rs1 << 15) | funct3 | (rd << 7)
+            }
+            Format::Store => {
+                let rs2 = register(operands[0])?;
+                let (imm, rs1) = memory(operands[1])?;
+                let imm = imm & 0xfff;
+                ((imm >> 5) << 25) | (rs2 << 20) | (rs1 << 15) | funct3 | ((imm & 0x1f) << 7)
+            }
+            Format::Branch => {
+                let [rs1, rs2] = [register(operands[0])?, register(operands[1])?];
+                let imm = target(operands[2], 13)?;
+                (((imm >> 12) & 1) << 31)
+                    | (((imm >> 5) & 0x3f) << 25)
+                    | (rs2 << 20)
+                    | (rs1 << 15)
+                    | funct3
+                    | (((imm >> 1) & 0xf) << 8)
+                    | (((imm >> 11) & 1) << 7)
+            }
+            Format::U => {
+                (immediate(operands[1], (0, 0xf_ffff))? << 12) | (register(operands[0])? << 7)
+            }
+            Format::Jal => {
+                let rd = register(operands[0])?;
+                let imm = target(operands[1], 21)?;
+                (((imm >> 20) & 1) << 31)
+                    | (((imm >> 1) & 0x3ff) << 21)
+                    | (((imm >> 11) & 1) << 20)
+                    | (imm & 0x000f_f000)
+                    | (rd << 7)
+            }
+        })
+}
+
+/// Splits the immediate of `li` into the upper 20 bits loaded by `lui` and the sign-extended lower
+/// 12 bits added by `addi`, if it fits in 32 bits.
+fn split_li(operand: &str) -> Option<(u32, i32)> {
+    let imm = parse_immediate(operand)?;
+    if !(i64::from(i32::MIN)..=i64::from(u32::MAX)).contains(&imm) {
+        return None;
+    }
+    let imm = imm as u32;
+    let lower = ((imm << 20) as i32) >> 20;
+    Some((imm.wrapping_sub(lower as u32) >> 12, lower))
+}
+
+/// The number of the register written `x0` to `x31` or by its ABI name.
+fn parse_register(operand: &str) -> Option<u32> {
+    if let Some(reg) = operand.strip_prefix('x').and_then(|reg| reg.parse::<u32>().ok()) {
+        return (reg < 32 && !operand[1..].starts_with('+')).then_some(reg);
+    }
+    match operand {
+        "fp" => Some(8),
+        _ => ABI_NAMES.iter().position(|&name| name == operand).map(|reg| reg as u32),
+    }
+}
+
+/// A decimal, `0x` hexadecimal or `0b` binary number with an optional minus sign.
+fn parse_immediate(operand: &str) -> Option<i64> {
+    let (negative, digits) = match operand.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, operand),
+    };
+    let (radix, digits) = if let Some(hex) = digits.strip_prefix("0x") {
+        (16, hex)
+    } else if let Some(binary) = digits.strip_prefix("0b") {
+        (2, binary)
+    } else {
+        (10, digits)
+    };
+    if digits.starts_with(['+', '-']) {
+        return None;
+    }
+    let value = i64::from_str_radix(digits, radix).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+fn is_identifier(text: &str) -> bool {
+    text.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_' || c == '.')
+        && text.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
@@ -0,0 +1,270 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32BaseAluAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    instructions::{BaseAluOpcode, Instruction, LocalOpcode},
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, MinimalInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    utils::not,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/base_alu/core.rs
+// for full implementation details.
+
+pub type Rv32BaseAluChip = VmChipWrapper<Rv32BaseAluAdapterChip, Rv32BaseAluCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32BaseAluCoreCols<T> {
+        pub a: [T; RV32_REGISTER_NUM_LIMBS],
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+        pub c: [T; RV32_REGISTER_NUM_LIMBS],
+
+        pub opcode_add_flag: T,
+        pub opcode_sub_flag: T,
+        pub opcode_xor_flag: T,
+        pub opcode_or_flag: T,
+        pub opcode_and_flag: T,
+    }
+}
+
+impl<T: Copy> Rv32BaseAluCoreCols<T> {
+    /// The opcode flags in the order of [BaseAluOpcode].
+    fn flags(&self) -> [T; BaseAluOpcode::COUNT] {
+        [
+            self.opcode_add_flag,
+            self.opcode_sub_flag,
+            self.opcode_xor_flag,
+            self.opcode_or_flag,
+            self.opcode_and_flag,
+        ]
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Rv32BaseAluCoreAir {
+    pub bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+}
+
+impl BaseAir<F> for Rv32BaseAluCoreAir {
+    fn width(&self) -> usize {
+        Rv32BaseAluCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32BaseAluCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<MinimalInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32BaseAluCoreCols<AB::Var> = local_core.borrow();
+        let flags = cols.flags();
+
+        let is_valid = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| {
+            builder.assert_bool(flag);
+            acc + flag
+        });
+        builder.assert_bool(is_valid.clone());
+
+        let a = &cols.a;
+        let b = &cols.b;
+        let c = &cols.c;
+
+        // For ADD, define carry[i] = (b[i] + c[i] + carry[i - 1] - a[i]) / 2^RV32_CELL_BITS. If
+        // each carry[i] is boolean and 0 <= a[i] < 2^RV32_CELL_BITS, it can be proven that
+        // a[i] = (b[i] + c[i]) % 2^RV32_CELL_BITS as necessary. The same holds for SUB when
+        // carry[i] is (a[i] + c[i] - b[i] + carry[i - 1]) / 2^RV32_CELL_BITS.
+        let mut carry_add: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+        let mut carry_sub: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+        let carry_divide = AB::F::from_canonical_usize(1 << RV32_CELL_BITS).inverse();
+
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            // We explicitly separate the constraints for ADD and SUB in order to keep degree
+            // cubic.
+            carry_add[i] = AB::Expr::from(carry_divide)
+                * (b[i] + c[i] - a[i]
+                    + if i > 0 { carry_add[i - 1].clone() } else { AB::Expr::ZERO });
+            builder.when(cols.opcode_add_flag).assert_bool(carry_add[i].clone());
+            carry_sub[i] = AB::Expr::from(carry_divide)
+                * (a[i] + c[i] - b[i]
+                    + if i > 0 { carry_sub[i - 1].clone() } else { AB::Expr::ZERO });
+            builder.when(cols.opcode_sub_flag).assert_bool(carry_sub[i].clone());
+        }
+
+        // Interaction with BitwiseOperationLookup to range check a for ADD and SUB, and constrain
+        // a's correctness for XOR, OR, and AND. With x ^ y = x + y - 2 (x & y) and
+        // x | y = (x ^ y) + (x & y), AND and OR are checked through the XOR of b and c. For ADD
+        // and SUB, a ^ a = 0 only range checks a.
+        let bitwise = cols.opcode_xor_flag + cols.opcode_or_flag + cols.opcode_and_flag;
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            let x = not::<AB::Expr>(bitwise.clone()) * a[i] + bitwise.clone() * b[i];
+            let y = not::<AB::Expr>(bitwise.clone()) * a[i] + bitwise.clone() * c[i];
+            let x_xor_y = cols.opcode_xor_flag * a[i]
+                + cols.opcode_or_flag * ((AB::Expr::from_canonical_u32(2) * a[i]) - b[i] - c[i])
+                + cols.opcode_and_flag * (b[i] + c[i] - (AB::Expr::from_canonical_u32(2) * a[i]));
+            self.bus.send_xor::<AB::Expr>(x, y, x_xor_y).eval(builder, is_valid.clone());
+        }
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            flags.iter().zip(BaseAluOpcode::ALL).fold(AB::Expr::ZERO, |acc, (&flag, opcode)| {
+                acc + flag * AB::Expr::from_canonical_usize(opcode.local_usize())
+            }),
+        );
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: [cols.b.map(Into::into), cols.c.map(Into::into)].into(),
+            writes: [cols.a.map(Into::into)].into(),
+            instruction: MinimalInstruction { is_valid, opcode: expected_opcode }.into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        BaseAluOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32BaseAluCoreRecord<F> {
+    pub opcode: BaseAluOpcode,
+    pub a: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub c: [F; RV32_REGISTER_NUM_LIMBS],
+}
+
+/// Executes ADD, SUB, XOR, OR and AND and fills the core columns of [Rv32BaseAluCoreAir].
+pub struct Rv32BaseAluCoreChip {
+    pub air: Rv32BaseAluCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32BaseAluCoreChip {
+    pub fn new(bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>) -> Self {
+        Self { air: Rv32BaseAluCoreAir { bus: bitwise_lookup_chip.bus() }, bitwise_lookup_chip }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32BaseAluCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32BaseAluCoreRecord<F>;
+    type Air = Rv32BaseAluCoreAir;
+
+    /// Executes `a = b op c` and requests the bitwise lookups that [Rv32BaseAluCoreAir] sends for
+    /// it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let local_opcode: BaseAluOpcode = instruction
+            .opcode
+            .local_opcode()
+            .unwrap_or_else(|| panic!("opcode {} is not a base ALU opcode", instruction.opcode));
+        let [b, c] = reads.into();
+        let b_limbs = b.map(|x| x.as_canonical_u32());
+        let c_limbs = c.map(|x| x.as_canonical_u32());
+        let a_limbs = run_alu(local_opcode, &b_limbs, &c_limbs);
+
+        match local_opcode {
+            BaseAluOpcode::ADD | BaseAluOpcode::SUB => {
+                for a_val in a_limbs {
+                    self.bitwise_lookup_chip.request_xor(a_val, a_val);
+                }
+            }
+            BaseAluOpcode::XOR | BaseAluOpcode::OR | BaseAluOpcode::AND => {
+                for (b_val, c_val) in b_limbs.into_iter().zip(c_limbs) {
+                    self.bitwise_lookup_chip.request_xor(b_val, c_val);
+                }
+            }
+        }
+
+        let a = a_limbs.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext::without_pc([a]);
+        (output, Rv32BaseAluCoreRecord { opcode: local_opcode, a, b, c })
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32BaseAluCoreCols<F> = row_slice.borrow_mut();
+        row_slice.a = record.a;
+        row_slice.b = record.b;
+        row_slice.c = record.c;
+        row_slice.opcode_add_flag = F::from_bool(record.opcode == BaseAluOpcode::ADD);
+        row_slice.opcode_sub_flag = F::from_bool(record.opcode == BaseAluOpcode::SUB);
+        row_slice.opcode_xor_flag = F::from_bool(record.opcode == BaseAluOpcode::XOR);
+        row_slice.opcode_or_flag = F::from_bool(record.opcode == BaseAluOpcode::OR);
+        row_slice.opcode_and_flag = F::from_bool(record.opcode == BaseAluOpcode::AND);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the limbs of `x op y` for the little-endian limbs `x` and `y`.
+pub fn run_alu(
+    opcode: BaseAluOpcode,
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    match opcode {
+        BaseAluOpcode::ADD => run_add(x, y),
+        BaseAluOpcode::SUB => run_subtract(x, y),
+        BaseAluOpcode::XOR => array::from_fn(|i| x[i] ^ y[i]),
+        BaseAluOpcode::OR => array::from_fn(|i| x[i] | y[i]),
+        BaseAluOpcode::AND => array::from_fn(|i| x[i] & y[i]),
+    }
+}
+
+fn run_add(
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    let mut carry = 0;
+    array::from_fn(|i| {
+        let sum = x[i] + y[i] + carry;
+        carry = sum >> RV32_CELL_BITS;
+        sum & RV32_LIMB_MAX
+    })
+}
+
+fn run_subtract(
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    let mut borrow = 0;
+    array::from_fn(|i| {
+        let rhs = y[i] + borrow;
+        borrow = u32::from(x[i] < rhs);
+        (x[i] + (borrow << RV32_CELL_BITS) - rhs) & RV32_LIMB_MAX
+    })
+}
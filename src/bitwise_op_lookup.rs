@@ -0,0 +1,178 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    bus::{BitwiseOperation, BitwiseOperationLookupBus},
+    openvm_stark_backend::{
+        air::{Air, BaseAir, PairBuilder},
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::{Interaction, InteractionBuilder},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/bitwise_op_lookup/mod.rs
+// for full implementation details.
+
+/// One multiplicity column per [BitwiseOperation].
+pub const NUM_BITWISE_OP_LOOKUP_COLS: usize = BitwiseOperation::COUNT;
+/// The operands `x`, `y` followed by the result of every non-range [BitwiseOperation].
+pub const NUM_BITWISE_OP_LOOKUP_PREPROCESSED_COLS: usize = 2 + BitwiseOperation::COUNT - 1;
+
+#[derive(Clone, Copy)]
+pub struct BitwiseOperationLookupAir<const NUM_BITS: usize> {
+    pub bus: BitwiseOperationLookupBus<NUM_BITS>,
+}
+
+impl<const NUM_BITS: usize> BitwiseOperationLookupAir<NUM_BITS> {
+    pub const fn new(bus: BitwiseOperationLookupBus<NUM_BITS>) -> Self {
+        Self { bus }
+    }
+
+    /// The table has one row per pair `(x, y)` of `NUM_BITS`-bit operands.
+    pub const fn height() -> usize {
+        1 << (2 * NUM_BITS)
+    }
+}
+
+impl<const NUM_BITS: usize> BaseAir<F> for BitwiseOperationLookupAir<NUM_BITS> {
+    fn width(&self) -> usize {
+        NUM_BITWISE_OP_LOOKUP_COLS
+    }
+
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<F>> {
+        let values = (0..Self::height() as u32)
+            .flat_map(|row| {
+                let (x, y) = (row >> NUM_BITS, row & ((1 << NUM_BITS) - 1));
+                [x, y]
+                    .into_iter()
+                    .chain(BitwiseOperation::ALL[1..].iter().map(move |op| op.compute(x, y)))
+                    .map(F::from_canonical_u32)
+            })
+            .collect();
+        Some(RowMajorMatrix::new(values, NUM_BITWISE_OP_LOOKUP_PREPROCESSED_COLS))
+    }
+}
+
+impl<const NUM_BITS: usize, AB> Air<AB> for BitwiseOperationLookupAir<NUM_BITS>
+where
+    AB: InteractionBuilder + PairBuilder<F = F>,
+{
+    fn eval(&self, builder: &mut AB) {
+        let preprocessed = builder.preprocessed();
+        let prep_local = preprocessed.row_slice(0);
+        let (x, y, results) = (prep_local[0], prep_local[1], &prep_local[2..]);
+
+        let main = builder.main();
+        let local = main.row_slice(0);
+
+        for (i, op) in BitwiseOperation::ALL.into_iter().enumerate() {
+            let z =
+                if op == BitwiseOperation::Range { AB::Expr::ZERO } else { results[i - 1].into() };
+            self.bus.receive(x, y, z, op).eval(builder, local[i]);
+        }
+    }
+}
+
+/// A [BitwiseOperationLookupChip] shared by the chips that request lookups from it.
+pub type SharedBitwiseOperationLookupChip<const NUM_BITS: usize> =
+    Arc<BitwiseOperationLookupChip<NUM_BITS>>;
+
+/// Host-side counterpart of [BitwiseOperationLookupAir] that records the multiplicity of every
+/// requested `(x, y, op)` lookup.
+pub struct BitwiseOperationLookupChip<const NUM_BITS: usize> {
+    pub air: BitwiseOperationLookupAir<NUM_BITS>,
+    counts: [Vec<AtomicU32>; BitwiseOperation::COUNT],
+}
+
+impl<const NUM_BITS: usize> BitwiseOperationLookupChip<NUM_BITS> {
+    pub fn new(bus: BitwiseOperationLookupBus<NUM_BITS>) -> Self {
+        let num_rows = BitwiseOperationLookupAir::<NUM_BITS>::height();
+        Self {
+            air: BitwiseOperationLookupAir::new(bus),
+            counts: core::array::from_fn(|_| (0..num_rows).map(|_| AtomicU32::new(0)).collect()),
+        }
+    }
+
+    pub fn bus(&self) -> BitwiseOperationLookupBus<NUM_BITS> {
+        self.air.bus
+    }
+
+    pub fn air_width(&self) -> usize {
+        NUM_BITWISE_OP_LOOKUP_COLS
+    }
+
+    pub fn request_range(&self, x: u32, y: u32) {
+        self.request(BitwiseOperation::Range, x, y);
+    }
+
+    pub fn request_xor(&self, x: u32, y: u32) -> u32 {
+        self.request(BitwiseOperation::Xor, x, y)
+    }
+
+    pub fn request_and(&self, x: u32, y: u32) -> u32 {
+        self.request(BitwiseOperation::And, x, y)
+    }
+
+    pub fn request_or(&self, x: u32, y: u32) -> u32 {
+        self.request(BitwiseOperation::Or, x, y)
+    }
+
+    /// Records one lookup of `(x, y, op)` and returns the result `z`.
+    pub fn request(&self, op: BitwiseOperation, x: u32, y: u32) -> u32 {
+        let upper_bound = 1 << NUM_BITS;
+        assert!(x < upper_bound, "x {x} out of range for {NUM_BITS} bits");
+        assert!(y < upper_bound, "y {y} out of range for {NUM_BITS} bits");
+        self.counts[op as usize][Self::row_index(x, y)].fetch_add(1, Ordering::Relaxed);
+        op.compute(x, y)
+    }
+
+    /// Records the lookups of `interaction`, i.e. `count` lookups of its message `[x, y, z, op]`.
+    /// This rebuilds the multiplicities from whatever a possibly forged trace sends to the bus.
+    ///
+    /// Panics if the message of a lookup with a nonzero count is not a row of the table.
+    pub fn request_interaction(&self, interaction: &Interaction<F>) {
+        assert_eq!(interaction.bus_index, self.bus().inner.index, "not a bitwise lookup");
+        if interaction.count == F::ZERO {
+            return;
+        }
+        let message = interaction.message.iter().map(|v| v.as_canonical_u32()).collect::<Vec<_>>();
+        let &[x, y, z, op] = message.as_slice() else {
+            panic!("bitwise lookup {message:?} does not have 4 fields");
+        };
+        let op = BitwiseOperation::ALL.into_iter().find(|&o| o as u32 == op);
+        let upper_bound = 1 << NUM_BITS;
+        let op = op
+            .filter(|op| x < upper_bound && y < upper_bound && z == op.compute(x, y))
+            .unwrap_or_else(|| panic!("bitwise lookup {message:?} is not in the table"));
+        for _ in 0..interaction.count.as_canonical_u32() {
+            self.request(op, x, y);
+        }
+    }
+
+    pub fn clear(&self) {
+        for count in self.counts.iter().flatten() {
+            count.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Generates the multiplicity trace and resets all counts.
+    pub fn generate_trace(&self) -> RowMajorMatrix<F> {
+        let num_rows = BitwiseOperationLookupAir::<NUM_BITS>::height();
+        let values = (0..num_rows)
+            .flat_map(|row| {
+                self.counts.iter().map(move |counts| {
+                    F::from_canonical_u32(counts[row].swap(0, Ordering::Relaxed))
+                })
+            })
+            .collect();
+        RowMajorMatrix::new(values, NUM_BITWISE_OP_LOOKUP_COLS)
+    }
+
+    fn row_index(x: u32, y: u32) -> usize {
+        ((x << NUM_BITS) + y) as usize
+    }
+}
@@ -0,0 +1,256 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32BranchAdapterChip,
+    aligned_borrow,
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{BranchEqualOpcode, Instruction, LocalOpcode},
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, ImmInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    utils::not,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/branch_eq/core.rs
+// for full implementation details.
+
+pub type Rv32BranchEqualChip = VmChipWrapper<Rv32BranchAdapterChip, Rv32BranchEqualCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32BranchEqualCoreCols<T> {
+        pub a: [T; RV32_REGISTER_NUM_LIMBS],
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+
+        // Boolean result of a op b. Should branch if and only if cmp_result = 1.
+        pub cmp_result: T,
+        pub imm: T,
+
+        pub opcode_beq_flag: T,
+        pub opcode_bne_flag: T,
+
+        pub diff_inv_marker: [T; RV32_REGISTER_NUM_LIMBS],
+    }
+}
+
+/// Constrains `cmp_result = (a == b)` for BEQ and `cmp_result = (a != b)` for BNE, and jumps to
+/// `from_pc + imm` if `cmp_result` and to `from_pc + 4` otherwise.
+#[derive(Clone, Copy, Debug)]
+pub struct Rv32BranchEqualCoreAir {
+    pub pc_step: u32,
+}
+
+impl Default for Rv32BranchEqualCoreAir {
+    fn default() -> Self {
+        Self { pc_step: DEFAULT_PC_STEP }
+    }
+}
+
+impl BaseAir<F> for Rv32BranchEqualCoreAir {
+    fn width(&self) -> usize {
+        Rv32BranchEqualCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32BranchEqualCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; 0]; 0]>,
+    I::ProcessedInstruction: From<ImmInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32BranchEqualCoreCols<AB::Var> = local_core.borrow();
+        let flags = [cols.opcode_beq_flag, cols.opcode_bne_flag];
+
+        let is_valid = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| {
+            builder.assert_bool(flag);
+            acc + flag
+        });
+        builder.assert_bool(is_valid.clone());
+        builder.assert_bool(cols.cmp_result);
+
+        let a = &cols.a;
+        let b = &cols.b;
+        let inv_marker = &cols.diff_inv_marker;
+
+        // 1 if cmp_result indicates a and b are equal, 0 otherwise
+        let cmp_eq = cols.cmp_result * cols.opcode_beq_flag
+            + not::<AB::Expr>(cols.cmp_result) * cols.opcode_bne_flag;
+        let mut sum = cmp_eq.clone();
+
+        // For BEQ, inv_marker is used to check equality of a and b:
+        // - If a == b, all inv_marker values must be 0 (sum = 0)
+        // - If a != b, inv_marker contains 0s for all positions except ONE position i where
+        //   a[i] != b[i]
+        // - At this position, inv_marker[i] contains the multiplicative inverse of (a[i] - b[i])
+        // - This ensures inv_marker[i] * (a[i] - b[i]) = 1, making the sum = 1
+        // Note: There might be multiple valid inv_marker if a != b. But as long as the trace can
+        // provide at least one, that's sufficient to prove a != b.
+        //
+        // Note:
+        // - If cmp_eq == 0, then it is impossible to have sum != 0 if a == b.
+        // - If cmp_eq == 1, then (a[i] - b[i]) == 0 for all i, so sum = cmp_eq = 1.
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            sum += (a[i] - b[i]) * inv_marker[i];
+            builder.assert_zero(cmp_eq.clone() * (a[i] - b[i]));
+        }
+        builder.when(is_valid.clone()).assert_one(sum);
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            flags.iter().zip(BranchEqualOpcode::ALL).fold(
+                AB::Expr::ZERO,
+                |acc, (&flag, opcode)| {
+                    acc + flag * AB::Expr::from_canonical_usize(opcode.local_usize())
+                },
+            ),
+        );
+
+        let to_pc = from_pc
+            + cols.cmp_result * cols.imm
+            + not::<AB::Expr>(cols.cmp_result) * AB::Expr::from_canonical_u32(self.pc_step);
+
+        AdapterAirContext {
+            to_pc: Some(to_pc),
+            reads: [cols.a.map(Into::into), cols.b.map(Into::into)].into(),
+            writes: [].into(),
+            instruction: ImmInstruction {
+                is_valid,
+                opcode: expected_opcode,
+                immediate: cols.imm.into(),
+            }
+            .into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        BranchEqualOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32BranchEqualCoreRecord<F> {
+    pub opcode: BranchEqualOpcode,
+    pub a: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub cmp_result: F,
+    pub imm: F,
+    pub diff_inv_val: F,
+    pub diff_idx: usize,
+}
+
+/// Executes BEQ and BNE and fills the core columns of [Rv32BranchEqualCoreAir].
+pub struct Rv32BranchEqualCoreChip {
+    pub air: Rv32BranchEqualCoreAir,
+}
+
+impl Rv32BranchEqualCoreChip {
+    pub fn new() -> Self {
+        Self { air: Rv32BranchEqualCoreAir::default() }
+    }
+}
+
+impl Default for Rv32BranchEqualCoreChip {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32BranchEqualCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: Default,
+{
+    type Record = Rv32BranchEqualCoreRecord<F>;
+    type Air = Rv32BranchEqualCoreAir;
+
+    /// Compares `a` and `b` and jumps by the immediate `c`, a field element that may encode a
+    /// negative offset, if the branch is taken.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let branch_eq_opcode: BranchEqualOpcode =
+            instruction.opcode.local_opcode().unwrap_or_else(|| {
+                panic!("opcode {} is not a branch equal opcode", instruction.opcode)
+            });
+        let imm = instruction.c;
+        let [a, b] = reads.into();
+        let x = a.map(|x| x.as_canonical_u32());
+        let y = b.map(|y| y.as_canonical_u32());
+        let (cmp_result, diff_idx, diff_inv_val) = run_eq(branch_eq_opcode, &x, &y);
+
+        let to_pc = if cmp_result {
+            (F::from_canonical_u32(from_pc) + imm).as_canonical_u32()
+        } else {
+            from_pc + self.air.pc_step
+        };
+        let output = AdapterRuntimeContext { to_pc: Some(to_pc), writes: Default::default() };
+        let record = Rv32BranchEqualCoreRecord {
+            opcode: branch_eq_opcode,
+            a,
+            b,
+            cmp_result: F::from_bool(cmp_result),
+            imm,
+            diff_inv_val,
+            diff_idx,
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32BranchEqualCoreCols<F> = row_slice.borrow_mut();
+        row_slice.a = record.a;
+        row_slice.b = record.b;
+        row_slice.cmp_result = record.cmp_result;
+        row_slice.imm = record.imm;
+        row_slice.opcode_beq_flag = F::from_bool(record.opcode == BranchEqualOpcode::BEQ);
+        row_slice.opcode_bne_flag = F::from_bool(record.opcode == BranchEqualOpcode::BNE);
+        row_slice.diff_inv_marker =
+            array::from_fn(|i| if i == record.diff_idx { record.diff_inv_val } else { F::ZERO });
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns whether the branch is taken, the index of the first limb where `x` and `y` differ and
+/// the inverse of their difference there, or `0` and zero if they are equal.
+pub fn run_eq(
+    local_opcode: BranchEqualOpcode,
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> (bool, usize, F) {
+    for i in 0..RV32_REGISTER_NUM_LIMBS {
+        if x[i] != y[i] {
+            return (
+                local_opcode == BranchEqualOpcode::BNE,
+                i,
+                (F::from_canonical_u32(x[i]) - F::from_canonical_u32(y[i])).inverse(),
+            );
+        }
+    }
+    (local_opcode == BranchEqualOpcode::BEQ, 0, F::ZERO)
+}
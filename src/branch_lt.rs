@@ -0,0 +1,338 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32BranchAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS},
+    execution::DEFAULT_PC_STEP,
+    instructions::{BranchLessThanOpcode, Instruction, LocalOpcode},
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, ImmInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    less_than::{msb_to_field, run_less_than},
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    utils::not,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/branch_lt/core.rs
+// for full implementation details.
+
+pub type Rv32BranchLessThanChip = VmChipWrapper<Rv32BranchAdapterChip, Rv32BranchLessThanCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32BranchLessThanCoreCols<T> {
+        pub a: [T; RV32_REGISTER_NUM_LIMBS],
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+
+        // Boolean result of a op b. Should branch if and only if cmp_result = 1.
+        pub cmp_result: T,
+        pub imm: T,
+
+        pub opcode_blt_flag: T,
+        pub opcode_bltu_flag: T,
+        pub opcode_bge_flag: T,
+        pub opcode_bgeu_flag: T,
+
+        // Most significant limb of a and b respectively as a field element, will be range
+        // checked to be within [-128, 127) if signed and [0, 256) if unsigned.
+        pub a_msb_f: T,
+        pub b_msb_f: T,
+
+        // 1 if a < b, 0 otherwise.
+        pub cmp_lt: T,
+
+        // 1 at the most significant index i such that a[i] != b[i], otherwise 0. If such an i
+        // exists, diff_val = b[i] - a[i] if a[i] < b[i] or a[i] - b[i] else.
+        pub diff_marker: [T; RV32_REGISTER_NUM_LIMBS],
+        pub diff_val: T,
+        // The inverse of diff_val if a != b, which proves that diff_val is non-zero.
+        pub diff_inv: T,
+    }
+}
+
+/// Constrains `cmp_lt = a < b`, as signed integers for BLT and BGE and as unsigned integers for
+/// BLTU and BGEU, and jumps to `from_pc + imm` if the branch is taken and to `from_pc + 4`
+/// otherwise.
+#[derive(Clone, Copy)]
+pub struct Rv32BranchLessThanCoreAir {
+    pub bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+    pub pc_step: u32,
+}
+
+impl BaseAir<F> for Rv32BranchLessThanCoreAir {
+    fn width(&self) -> usize {
+        Rv32BranchLessThanCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32BranchLessThanCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; 0]; 0]>,
+    I::ProcessedInstruction: From<ImmInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32BranchLessThanCoreCols<AB::Var> = local_core.borrow();
+        let flags = [
+            cols.opcode_blt_flag,
+            cols.opcode_bltu_flag,
+            cols.opcode_bge_flag,
+            cols.opcode_bgeu_flag,
+        ];
+
+        let is_valid = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| {
+            builder.assert_bool(flag);
+            acc + flag
+        });
+        builder.assert_bool(is_valid.clone());
+        builder.assert_bool(cols.cmp_result);
+
+        let lt = cols.opcode_blt_flag + cols.opcode_bltu_flag;
+        let ge = cols.opcode_bge_flag + cols.opcode_bgeu_flag;
+        let signed = cols.opcode_blt_flag + cols.opcode_bge_flag;
+        builder.assert_eq(
+            cols.cmp_lt,
+            cols.cmp_result * lt.clone() + not::<AB::Expr>(cols.cmp_result) * ge.clone(),
+        );
+
+        let a = &cols.a;
+        let b = &cols.b;
+        let marker = &cols.diff_marker;
+        let mut prefix_sum = AB::Expr::ZERO;
+
+        // Check if a_msb_f and b_msb_f are signed values of a[NUM_LIMBS - 1] and
+        // b[NUM_LIMBS - 1] in prime field F.
+        let a_diff = a[RV32_REGISTER_NUM_LIMBS - 1] - cols.a_msb_f;
+        let b_diff = b[RV32_REGISTER_NUM_LIMBS - 1] - cols.b_msb_f;
+        builder.assert_zero(
+            a_diff.clone() * (AB::Expr::from_canonical_u32(1 << RV32_CELL_BITS) - a_diff),
+        );
+        builder.assert_zero(
+            b_diff.clone() * (AB::Expr::from_canonical_u32(1 << RV32_CELL_BITS) - b_diff),
+        );
+
+        for i in (0..RV32_REGISTER_NUM_LIMBS).rev() {
+            let diff = (if i == RV32_REGISTER_NUM_LIMBS - 1 {
+                cols.b_msb_f - cols.a_msb_f
+            } else {
+                b[i] - a[i]
+            }) * (AB::Expr::from_canonical_u8(2) * cols.cmp_lt - AB::Expr::ONE);
+            prefix_sum += marker[i].into();
+            builder.assert_bool(marker[i]);
+            builder.assert_zero(not::<AB::Expr>(prefix_sum.clone()) * diff.clone());
+            builder.when(marker[i]).assert_eq(cols.diff_val, diff);
+        }
+        // - If a != b, then prefix_sum = 1 so marker[i] must be 1 iff i is the first index where
+        //   diff != 0. Constrains that diff == diff_val where diff_val is non-zero.
+        // - If a == b, then prefix_sum = 0 and cmp_lt = 0. Here, prefix_sum cannot be 1 because
+        //   all diff are zero, making diff == diff_val fail.
+
+        builder.assert_bool(prefix_sum.clone());
+        builder.when(not::<AB::Expr>(prefix_sum.clone())).assert_zero(cols.cmp_lt);
+
+        // Check if a_msb_f and b_msb_f are in [-128, 127) if signed, [0, 256) if unsigned.
+        self.bus
+            .send_range::<AB::Expr>(
+                cols.a_msb_f
+                    + AB::Expr::from_canonical_u32(1 << (RV32_CELL_BITS - 1)) * signed.clone(),
+                cols.b_msb_f + AB::Expr::from_canonical_u32(1 << (RV32_CELL_BITS - 1)) * signed,
+            )
+            .eval(builder, is_valid.clone());
+
+        // diff_val is non-zero by its inverse, and the range check ensures it is positive, i.e.
+        // that cmp_lt has the right sign.
+        builder.when(prefix_sum.clone()).assert_one(cols.diff_val * cols.diff_inv);
+        self.bus.send_range::<AB::Expr>(cols.diff_val, AB::F::ZERO).eval(builder, prefix_sum);
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            flags.iter().zip(BranchLessThanOpcode::ALL).fold(
+                AB::Expr::ZERO,
+                |acc, (&flag, opcode)| {
+                    acc + flag * AB::Expr::from_canonical_usize(opcode.local_usize())
+                },
+            ),
+        );
+
+        let to_pc = from_pc
+            + cols.cmp_result * cols.imm
+            + not::<AB::Expr>(cols.cmp_result) * AB::Expr::from_canonical_u32(self.pc_step);
+
+        AdapterAirContext {
+            to_pc: Some(to_pc),
+            reads: [cols.a.map(Into::into), cols.b.map(Into::into)].into(),
+            writes: [].into(),
+            instruction: ImmInstruction {
+                is_valid,
+                opcode: expected_opcode,
+                immediate: cols.imm.into(),
+            }
+            .into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        BranchLessThanOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32BranchLessThanCoreRecord<F> {
+    pub opcode: BranchLessThanOpcode,
+    pub a: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub cmp_result: F,
+    pub cmp_lt: F,
+    pub imm: F,
+    pub a_msb_f: F,
+    pub b_msb_f: F,
+    pub diff_val: F,
+    /// [RV32_REGISTER_NUM_LIMBS] if `a == b`.
+    pub diff_idx: usize,
+}
+
+/// Executes BLT, BLTU, BGE and BGEU and fills the core columns of [Rv32BranchLessThanCoreAir].
+pub struct Rv32BranchLessThanCoreChip {
+    pub air: Rv32BranchLessThanCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32BranchLessThanCoreChip {
+    pub fn new(bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>) -> Self {
+        Self {
+            air: Rv32BranchLessThanCoreAir {
+                bus: bitwise_lookup_chip.bus(),
+                pc_step: DEFAULT_PC_STEP,
+            },
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32BranchLessThanCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: Default,
+{
+    type Record = Rv32BranchLessThanCoreRecord<F>;
+    type Air = Rv32BranchLessThanCoreAir;
+
+    /// Compares `a` and `b`, jumps by the immediate `c` if the branch is taken and requests the
+    /// range checks that [Rv32BranchLessThanCoreAir] sends for it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let blt_opcode: BranchLessThanOpcode =
+            instruction.opcode.local_opcode().unwrap_or_else(|| {
+                panic!("opcode {} is not a branch less than opcode", instruction.opcode)
+            });
+        let imm = instruction.c;
+        let [a, b] = reads.into();
+        let a_limbs = a.map(|x| x.as_canonical_u32());
+        let b_limbs = b.map(|x| x.as_canonical_u32());
+        let (cmp_result, diff_idx, a_sign, b_sign) = run_cmp(blt_opcode, &a_limbs, &b_limbs);
+        let signed = matches!(blt_opcode, BranchLessThanOpcode::BLT | BranchLessThanOpcode::BGE);
+        let ge = matches!(blt_opcode, BranchLessThanOpcode::BGE | BranchLessThanOpcode::BGEU);
+        let cmp_lt = cmp_result ^ ge;
+
+        let (a_msb_f, a_msb_range) =
+            msb_to_field(a_limbs[RV32_REGISTER_NUM_LIMBS - 1], signed, a_sign);
+        let (b_msb_f, b_msb_range) =
+            msb_to_field(b_limbs[RV32_REGISTER_NUM_LIMBS - 1], signed, b_sign);
+        let diff_val = if diff_idx == RV32_REGISTER_NUM_LIMBS {
+            0
+        } else if diff_idx == RV32_REGISTER_NUM_LIMBS - 1 {
+            if cmp_lt { b_msb_f - a_msb_f } else { a_msb_f - b_msb_f }.as_canonical_u32()
+        } else if cmp_lt {
+            b_limbs[diff_idx] - a_limbs[diff_idx]
+        } else {
+            a_limbs[diff_idx] - b_limbs[diff_idx]
+        };
+
+        self.bitwise_lookup_chip.request_range(a_msb_range, b_msb_range);
+        if diff_idx != RV32_REGISTER_NUM_LIMBS {
+            self.bitwise_lookup_chip.request_range(diff_val, 0);
+        }
+
+        let to_pc = if cmp_result {
+            (F::from_canonical_u32(from_pc) + imm).as_canonical_u32()
+        } else {
+            from_pc + self.air.pc_step
+        };
+        let output = AdapterRuntimeContext { to_pc: Some(to_pc), writes: Default::default() };
+        let record = Rv32BranchLessThanCoreRecord {
+            opcode: blt_opcode,
+            a,
+            b,
+            cmp_result: F::from_bool(cmp_result),
+            cmp_lt: F::from_bool(cmp_lt),
+            imm,
+            a_msb_f,
+            b_msb_f,
+            diff_val: F::from_canonical_u32(diff_val),
+            diff_idx,
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32BranchLessThanCoreCols<F> = row_slice.borrow_mut();
+        row_slice.a = record.a;
+        row_slice.b = record.b;
+        row_slice.cmp_result = record.cmp_result;
+        row_slice.cmp_lt = record.cmp_lt;
+        row_slice.imm = record.imm;
+        row_slice.a_msb_f = record.a_msb_f;
+        row_slice.b_msb_f = record.b_msb_f;
+        row_slice.diff_val = record.diff_val;
+        row_slice.diff_inv = match record.diff_idx {
+            RV32_REGISTER_NUM_LIMBS => F::ZERO,
+            _ => record.diff_val.inverse(),
+        };
+        row_slice.opcode_blt_flag = F::from_bool(record.opcode == BranchLessThanOpcode::BLT);
+        row_slice.opcode_bltu_flag = F::from_bool(record.opcode == BranchLessThanOpcode::BLTU);
+        row_slice.opcode_bge_flag = F::from_bool(record.opcode == BranchLessThanOpcode::BGE);
+        row_slice.opcode_bgeu_flag = F::from_bool(record.opcode == BranchLessThanOpcode::BGEU);
+        row_slice.diff_marker = array::from_fn(|i| F::from_bool(i == record.diff_idx));
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns whether the branch is taken, the index of the most significant limb where `x` and
+/// `y` differ ([RV32_REGISTER_NUM_LIMBS] if they are equal), and the signs of `x` and `y`.
+pub fn run_cmp(
+    local_opcode: BranchLessThanOpcode,
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> (bool, usize, bool, bool) {
+    let signed = matches!(local_opcode, BranchLessThanOpcode::BLT | BranchLessThanOpcode::BGE);
+    let ge = matches!(local_opcode, BranchLessThanOpcode::BGE | BranchLessThanOpcode::BGEU);
+    let (cmp_lt, diff_idx, x_sign, y_sign) = run_less_than(signed, x, y);
+    (cmp_lt ^ ge, diff_idx, x_sign, y_sign)
+}
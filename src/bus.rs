@@ -1,69 +1,110 @@
-use crate::openvm_stark_backend::{
-    field::{F, FieldAlgebra},
-    interaction::{BusIndex, InteractionBuilder, LookupBus},
+use crate::{
+    execution::ExecutionState,
+    instructions::NUM_OPERANDS,
+    memory::MemoryAddress,
+    openvm_stark_backend::{
+        field::FieldAlgebra,
+        interaction::{
+            BusIndex, InteractionBuilder, LookupBus, LookupInteraction, PermutationCheckBus,
+        },
+    },
 };
 
 // Please refer to
 // https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/bitwise_op_lookup/bus.rs#L83
 // for full implementation details.
 
-#[derive(Clone)]
-pub struct BitwiseOperationLookupBusInteraction<T> {
-    pub x: T,
-    pub y: T,
-    pub z: T,
-    pub op: T,
-    pub bus: LookupBus,
-    is_lookup: bool,
+/// The operation selector sent as the last field of a bitwise lookup.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum BitwiseOperation {
+    /// Range checks `x` and `y`. The result `z` is always zero.
+    Range = 0,
+    Xor = 1,
+    And = 2,
+    Or = 3,
 }
 
-impl<T: FieldAlgebra> BitwiseOperationLookupBusInteraction<T> {
-    pub fn eval<AB>(self, builder: &mut AB, count: impl Into<AB::Expr>)
-    where
-        AB: InteractionBuilder<Expr = T>,
-    {
-        let key = [self.x, self.y, self.z, self.op];
-        if self.is_lookup {
-            self.bus.lookup_key(builder, key, count);
-        } else {
-            self.bus.add_key_with_lookups(builder, key, count);
+impl BitwiseOperation {
+    pub const COUNT: usize = 4;
+
+    pub const ALL: [BitwiseOperation; Self::COUNT] = [Self::Range, Self::Xor, Self::And, Self::Or];
+
+    pub fn to_field<T: FieldAlgebra>(self) -> T {
+        T::from_canonical_u8(self as u8)
+    }
+
+    /// Computes `z` for the operands `x` and `y`.
+    pub fn compute(self, x: u32, y: u32) -> u32 {
+        match self {
+            Self::Range => 0,
+            Self::Xor => x ^ y,
+            Self::And => x & y,
+            Self::Or => x | y,
         }
     }
 }
 
-#[derive(Clone)]
-pub struct BitwiseOperationLookupBus {
+/// Bus for bitwise operations on `NUM_BITS`-bit operands.
+#[derive(Clone, Copy)]
+pub struct BitwiseOperationLookupBus<const NUM_BITS: usize> {
     pub inner: LookupBus,
 }
 
-impl BitwiseOperationLookupBus {
+impl<const NUM_BITS: usize> BitwiseOperationLookupBus<NUM_BITS> {
     pub const fn new(index: BusIndex) -> Self {
         Self { inner: LookupBus::new(index) }
     }
 
+    pub const fn num_bits(&self) -> usize {
+        NUM_BITS
+    }
+
     #[must_use]
-    pub fn send_range<T>(
+    pub fn send_range<T>(&self, x: impl Into<T>, y: impl Into<T>) -> LookupInteraction<T, 4>
+    where
+        T: FieldAlgebra,
+    {
+        self.push(x, y, T::ZERO, BitwiseOperation::Range, true)
+    }
+
+    #[must_use]
+    pub fn send_xor<T>(
         &self,
         x: impl Into<T>,
         y: impl Into<T>,
-    ) -> BitwiseOperationLookupBusInteraction<T>
+        z: impl Into<T>,
+    ) -> LookupInteraction<T, 4>
     where
         T: FieldAlgebra,
     {
-        self.push(x, y, T::ZERO, T::ZERO, true)
+        self.push(x, y, z, BitwiseOperation::Xor, true)
     }
 
     #[must_use]
-    pub fn send_xor<T>(
+    pub fn send_and<T>(
         &self,
         x: impl Into<T>,
         y: impl Into<T>,
         z: impl Into<T>,
-    ) -> BitwiseOperationLookupBusInteraction<T>
+    ) -> LookupInteraction<T, 4>
     where
         T: FieldAlgebra,
     {
-        self.push(x, y, z, T::ONE, true)
+        self.push(x, y, z, BitwiseOperation::And, true)
+    }
+
+    #[must_use]
+    pub fn send_or<T>(
+        &self,
+        x: impl Into<T>,
+        y: impl Into<T>,
+        z: impl Into<T>,
+    ) -> LookupInteraction<T, 4>
+    where
+        T: FieldAlgebra,
+    {
+        self.push(x, y, z, BitwiseOperation::Or, true)
     }
 
     #[must_use]
@@ -72,8 +113,11 @@ impl BitwiseOperationLookupBus {
         x: impl Into<T>,
         y: impl Into<T>,
         z: impl Into<T>,
-        op: impl Into<T>,
-    ) -> BitwiseOperationLookupBusInteraction<T> {
+        op: BitwiseOperation,
+    ) -> LookupInteraction<T, 4>
+    where
+        T: FieldAlgebra,
+    {
         self.push(x, y, z, op, false)
     }
 
@@ -82,16 +126,274 @@ impl BitwiseOperationLookupBus {
         x: impl Into<T>,
         y: impl Into<T>,
         z: impl Into<T>,
-        op: impl Into<T>,
+        op: BitwiseOperation,
         is_lookup: bool,
-    ) -> BitwiseOperationLookupBusInteraction<T> {
-        BitwiseOperationLookupBusInteraction {
-            x: x.into(),
-            y: y.into(),
-            z: z.into(),
-            op: op.into(),
+    ) -> LookupInteraction<T, 4>
+    where
+        T: FieldAlgebra,
+    {
+        let key = [x.into(), y.into(), z.into(), op.to_field()];
+        if is_lookup { self.inner.lookup(key) } else { self.inner.add_key(key) }
+    }
+}
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/var_range/bus.rs
+// for full implementation details.
+
+/// Bus for range checking `value` against `2^max_bits` for any `max_bits <= range_max_bits`.
+#[derive(Clone, Copy, Debug)]
+pub struct VariableRangeCheckerBus {
+    pub inner: LookupBus,
+    pub range_max_bits: usize,
+}
+
+impl VariableRangeCheckerBus {
+    /// Panics unless `range_max_bits` is in `1..32`, so that limbs of that width are nonempty and
+    /// their mask fits in a `u32`.
+    pub const fn new(index: BusIndex, range_max_bits: usize) -> Self {
+        assert!(
+            0 < range_max_bits && range_max_bits < u32::BITS as usize,
+            "range_max_bits must be in 1..32"
+        );
+        Self { inner: LookupBus::new(index), range_max_bits }
+    }
+
+    /// Range checks that `value` is in `[0, 2^max_bits)`.
+    #[must_use]
+    pub fn range_check<T>(&self, value: impl Into<T>, max_bits: usize) -> LookupInteraction<T, 2>
+    where
+        T: FieldAlgebra,
+    {
+        assert!(
+            max_bits <= self.range_max_bits,
+            "max_bits {max_bits} exceeds range_max_bits {}",
+            self.range_max_bits
+        );
+        self.push(value, T::from_canonical_usize(max_bits), true)
+    }
+
+    #[must_use]
+    pub fn send<T>(&self, value: impl Into<T>, max_bits: impl Into<T>) -> LookupInteraction<T, 2> {
+        self.push(value, max_bits, true)
+    }
+
+    #[must_use]
+    pub fn receive<T>(
+        &self,
+        value: impl Into<T>,
+        max_bits: impl Into<T>,
+    ) -> LookupInteraction<T, 2> {
+        self.push(value, max_bits, false)
+    }
+
+    pub fn push<T>(
+        &self,
+        value: impl Into<T>,
+        max_bits: impl Into<T>,
+        is_lookup: bool,
+    ) -> LookupInteraction<T, 2> {
+        let key = [value.into(), max_bits.into()];
+        if is_lookup { self.inner.lookup(key) } else { self.inner.add_key(key) }
+    }
+}
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/range_tuple/bus.rs
+// for full implementation details.
+
+/// Bus for range checking a tuple `(x_0, ..., x_{N-1})` against `x_i < sizes[i]`.
+#[derive(Clone, Copy, Debug)]
+pub struct RangeTupleCheckerBus<const N: usize> {
+    pub inner: LookupBus,
+    pub sizes: [u32; N],
+}
+
+impl<const N: usize> RangeTupleCheckerBus<N> {
+    pub const fn new(index: BusIndex, sizes: [u32; N]) -> Self {
+        Self { inner: LookupBus::new(index), sizes }
+    }
+
+    #[must_use]
+    pub fn send<T, E: Into<T>>(&self, tuple: [E; N]) -> LookupInteraction<T, N> {
+        self.push(tuple, true)
+    }
+
+    #[must_use]
+    pub fn receive<T, E: Into<T>>(&self, tuple: [E; N]) -> LookupInteraction<T, N> {
+        self.push(tuple, false)
+    }
+
+    pub fn push<T, E: Into<T>>(&self, tuple: [E; N], is_lookup: bool) -> LookupInteraction<T, N> {
+        let key = tuple.map(Into::into);
+        if is_lookup { self.inner.lookup(key) } else { self.inner.add_key(key) }
+    }
+}
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/arch/execution.rs
+// for full implementation details.
+
+/// Bus carrying the [ExecutionState] from one executed instruction to the next.
+#[derive(Clone, Copy, Debug)]
+pub struct ExecutionBus {
+    pub inner: PermutationCheckBus,
+}
+
+impl ExecutionBus {
+    pub const fn new(index: BusIndex) -> Self {
+        Self { inner: PermutationCheckBus::new(index) }
+    }
+
+    /// Receives `prev_state` and sends `next_state` with multiplicity `enabled`.
+    ///
+    /// Caller must constrain that `enabled` is boolean.
+    pub fn execute<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        enabled: impl Into<AB::Expr>,
+        prev_state: ExecutionState<impl Into<AB::Expr>>,
+        next_state: ExecutionState<impl Into<AB::Expr>>,
+    ) {
+        let enabled = enabled.into();
+        self.inner.receive(builder, [prev_state.pc, prev_state.timestamp], enabled.clone());
+        self.inner.send(builder, [next_state.pc, next_state.timestamp], enabled);
+    }
+}
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/system/memory/offline_checker/bus.rs
+// for full implementation details.
+
+/// Bus carrying `(address_space, pointer, data, timestamp)` memory accesses. Every access
+/// receives the previous state of a block and sends its new state.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryBus {
+    pub inner: PermutationCheckBus,
+}
+
+impl MemoryBus {
+    pub const fn new(index: BusIndex) -> Self {
+        Self { inner: PermutationCheckBus::new(index) }
+    }
+
+    /// Prepares a write operation through the memory bus.
+    #[must_use]
+    pub fn send<T: Clone>(
+        &self,
+        address: MemoryAddress<impl Into<T>, impl Into<T>>,
+        data: Vec<impl Into<T>>,
+        timestamp: impl Into<T>,
+    ) -> MemoryBusInteraction<T> {
+        self.push(true, address, data, timestamp)
+    }
+
+    /// Prepares a read operation through the memory bus.
+    #[must_use]
+    pub fn receive<T: Clone>(
+        &self,
+        address: MemoryAddress<impl Into<T>, impl Into<T>>,
+        data: Vec<impl Into<T>>,
+        timestamp: impl Into<T>,
+    ) -> MemoryBusInteraction<T> {
+        self.push(false, address, data, timestamp)
+    }
+
+    fn push<T: Clone>(
+        &self,
+        is_send: bool,
+        address: MemoryAddress<impl Into<T>, impl Into<T>>,
+        data: Vec<impl Into<T>>,
+        timestamp: impl Into<T>,
+    ) -> MemoryBusInteraction<T> {
+        MemoryBusInteraction {
             bus: self.inner,
-            is_lookup,
+            is_send,
+            address: MemoryAddress::new(address.address_space.into(), address.pointer.into()),
+            data: data.into_iter().map(Into::into).collect(),
+            timestamp: timestamp.into(),
+        }
+    }
+}
+
+/// A not-yet-evaluated interaction on a [MemoryBus].
+#[derive(Clone, Debug)]
+pub struct MemoryBusInteraction<T> {
+    pub bus: PermutationCheckBus,
+    pub is_send: bool,
+    pub address: MemoryAddress<T, T>,
+    pub data: Vec<T>,
+    pub timestamp: T,
+}
+
+impl<T> MemoryBusInteraction<T> {
+    /// Finalizes and sends or receives the memory operation with the specified direction over
+    /// the bus.
+    ///
+    /// Caller must constrain that `direction` is boolean.
+    pub fn eval<AB>(self, builder: &mut AB, direction: impl Into<AB::Expr>)
+    where
+        AB: InteractionBuilder,
+        T: Into<AB::Expr>,
+    {
+        let fields = [self.address.address_space, self.address.pointer]
+            .into_iter()
+            .chain(self.data)
+            .chain([self.timestamp]);
+        if self.is_send {
+            self.bus.send(builder, fields, direction);
+        } else {
+            self.bus.receive(builder, fields, direction);
         }
     }
 }
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/system/program/bus.rs
+// for full implementation details.
+
+/// Bus for looking up the instruction `(pc, opcode, a, b, c, d, e, f, g)` of the program.
+#[derive(Clone, Copy, Debug)]
+pub struct ProgramBus {
+    pub inner: LookupBus,
+}
+
+impl ProgramBus {
+    pub const fn new(index: BusIndex) -> Self {
+        Self { inner: LookupBus::new(index) }
+    }
+
+    /// Looks up the instruction at `pc`. Missing operands are padded with zeros.
+    #[must_use]
+    pub fn lookup_instruction<T: FieldAlgebra>(
+        &self,
+        pc: impl Into<T>,
+        opcode: impl Into<T>,
+        operands: impl IntoIterator<Item = impl Into<T>>,
+    ) -> LookupInteraction<T, { NUM_OPERANDS + 2 }> {
+        self.inner.lookup(Self::key(pc, opcode, operands))
+    }
+
+    /// Adds the instruction at `pc` to the program table.
+    #[must_use]
+    pub fn add_instruction<T: FieldAlgebra>(
+        &self,
+        pc: impl Into<T>,
+        opcode: impl Into<T>,
+        operands: impl IntoIterator<Item = impl Into<T>>,
+    ) -> LookupInteraction<T, { NUM_OPERANDS + 2 }> {
+        self.inner.add_key(Self::key(pc, opcode, operands))
+    }
+
+    fn key<T: FieldAlgebra>(
+        pc: impl Into<T>,
+        opcode: impl Into<T>,
+        operands: impl IntoIterator<Item = impl Into<T>>,
+    ) -> [T; NUM_OPERANDS + 2] {
+        let mut operands = operands.into_iter().map(Into::into);
+        let mut key = [pc.into(), opcode.into()].into_iter().chain(operands.by_ref());
+        let key = core::array::from_fn(|_| key.next().unwrap_or(T::ZERO));
+        assert!(operands.next().is_none(), "too many operands, expected at most {NUM_OPERANDS}");
+        key
+    }
+}
@@ -1,5 +1,27 @@
 use crate::{
-    bus::BitwiseOperationLookupBus, openvm_stark_backend::interaction::InteractionBuilder,
+    adapters::Rv32RdWriteAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    instructions::{
+        Instruction, LocalOpcode,
+        Rv32AuipcOpcode::{self, AUIPC},
+    },
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, ImmInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+        matrix::RowMajorMatrix,
+    },
+    utils::next_power_of_two_or_zero,
+};
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
 };
 
 // Please refer to
@@ -7,62 +29,262 @@ use crate::{
 // for full implementation details.
 
 pub const RV32_REGISTER_NUM_LIMBS: usize = 4;
+pub const RV32_CELL_BITS: usize = 8;
+pub const RV32_LIMB_MAX: u32 = (1 << RV32_CELL_BITS) - 1;
+/// The number of bits of a valid program counter.
+pub const PC_BITS: usize = 30;
 
-#[derive(Clone)]
-pub struct Rv32AuipcCoreCols<T> {
-    pub is_valid: T,
-    // The limbs of the immediate except the least significant limb since it is always 0
-    pub imm_limbs: [T; RV32_REGISTER_NUM_LIMBS - 1],
-    // The limbs of the PC except the most significant and the least significant limbs
-    pub pc_limbs: [T; RV32_REGISTER_NUM_LIMBS - 1],
-    pub rd_data: [T; RV32_REGISTER_NUM_LIMBS],
-}
+pub type Rv32AuipcChip = VmChipWrapper<Rv32RdWriteAdapterChip, Rv32AuipcCoreChip>;
 
-pub trait VmCoreAir<AB>
-where
-    AB: InteractionBuilder,
-{
-    /// Returns `(to_pc, interface)`.
-    fn eval(&self, builder: &mut AB, local_core: &[AB::Var], from_pc: AB::Var);
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32AuipcCoreCols<T> {
+        pub is_valid: T,
+        // The limbs of the immediate except the least significant limb since it is always 0
+        pub imm_limbs: [T; RV32_REGISTER_NUM_LIMBS - 1],
+        // The limbs of the PC except the most significant and the least significant limbs
+        pub pc_limbs: [T; RV32_REGISTER_NUM_LIMBS - 2],
+        pub rd_data: [T; RV32_REGISTER_NUM_LIMBS],
+    }
 }
 
 #[derive(Clone)]
 pub struct Rv32AuipcCoreAir {
-    pub bus: BitwiseOperationLookupBus,
+    pub bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
 }
 
-// My own implementation for borrow
-fn trusted_borrow<T: std::marker::Copy>(x: &[T]) -> Rv32AuipcCoreCols<T> {
-    // x.borrow().clone()
-    Rv32AuipcCoreCols {
-        is_valid: x[0],
-        imm_limbs: [x[1]; RV32_REGISTER_NUM_LIMBS - 1],
-        pc_limbs: [x[2]; RV32_REGISTER_NUM_LIMBS - 1],
-        rd_data: [x[3]; RV32_REGISTER_NUM_LIMBS],
+impl BaseAir<F> for Rv32AuipcCoreAir {
+    fn width(&self) -> usize {
+        Rv32AuipcCoreCols::<F>::width()
     }
 }
 
-impl<AB> VmCoreAir<AB> for Rv32AuipcCoreAir
+impl<AB, I> VmCoreAir<AB, I> for Rv32AuipcCoreAir
 where
-    AB: InteractionBuilder,
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; 0]; 0]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<ImmInstruction<AB::Expr>>,
 {
-    fn eval(&self, builder: &mut AB, local_core: &[AB::Var], from_pc: AB::Var) {
-        // let cols: &Rv32AuipcCoreCols<AB::Var> = (*local_core).borrow();
-        let cols: &Rv32AuipcCoreCols<AB::Var> = &trusted_borrow(local_core);
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32AuipcCoreCols<AB::Var> = local_core.borrow();
 
         let Rv32AuipcCoreCols { is_valid, imm_limbs, pc_limbs, rd_data } = *cols;
         builder.assert_bool(is_valid);
-        let limbs = [imm_limbs, pc_limbs].concat();
-        // Repalce the following code with the bottom code
-        // for i in 0..(RV32_REGISTER_NUM_LIMBS - 2) {
-        //     self.bus
-        //         .send_range(limbs[i * 2], limbs[i * 2 + 1])
-        //         .eval(builder, is_valid);
-        // }
-        let mut i = 0;
-        while i < RV32_REGISTER_NUM_LIMBS - 2 {
-            self.bus.send_range(limbs[i * 2], limbs[i * 2 + 1]).eval(builder, is_valid);
-            i += 1;
+
+        // We want to constrain rd = pc + imm (i32 add) where:
+        // - rd_data represents limbs of rd
+        // - pc_limbs are limbs of pc except the most and least significant limbs
+        // - imm_limbs are limbs of imm except the least significant limb
+
+        // We know that rd_data[0] is equal to the least significant limb of PC
+        // Thus, the intermediate value will be equal to PC without its most significant limb:
+        let intermed_val = pc_limbs.iter().enumerate().fold(rd_data[0].into(), |acc, (i, &val)| {
+            acc + val * AB::F::from_canonical_u32(1 << ((i + 1) * RV32_CELL_BITS))
+        });
+
+        // Compute the most significant limb of PC
+        let pc_msl = (from_pc.into() - intermed_val)
+            * AB::F::from_canonical_usize(1 << (RV32_CELL_BITS * (RV32_REGISTER_NUM_LIMBS - 1)))
+                .inverse();
+
+        // The vector pc_limbs contains the actual limbs of PC in little endian order
+        let pc_limbs: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|i| match i {
+            0 => rd_data[0].into(),
+            i if i == RV32_REGISTER_NUM_LIMBS - 1 => pc_msl.clone(),
+            i => pc_limbs[i - 1].into(),
+        });
+
+        let mut carry: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+        let carry_divide = AB::F::from_canonical_usize(1 << RV32_CELL_BITS).inverse();
+
+        // Don't need to constrain the least significant limb of the addition since we already
+        // know that rd_data[0] = pc_limbs[0] and the least significant limb of imm is 0
+        // Note: imm_limbs doesn't include the least significant limb so imm_limbs[i - 1] means
+        // the i-th limb of imm
+        for i in 1..RV32_REGISTER_NUM_LIMBS {
+            carry[i] = (pc_limbs[i].clone() + imm_limbs[i - 1] - rd_data[i] + carry[i - 1].clone())
+                * carry_divide;
+            builder.when(is_valid).assert_bool(carry[i].clone());
+        }
+
+        // Range checking of rd_data entries to RV32_CELL_BITS bits
+        for i in 0..(RV32_REGISTER_NUM_LIMBS / 2) {
+            self.bus.send_range(rd_data[i * 2], rd_data[i * 2 + 1]).eval(builder, is_valid);
+        }
+
+        // The immediate and PC limbs need range checking to ensure they're within
+        // [0, 2^RV32_CELL_BITS). Since we range check two items at a time, doing this way helps
+        // efficiently divide the limb into groups of 2.
+        // Note: we range check the limbs of the immediate except the least significant limb since
+        // it is always 0
+        // Note: we range check the limbs of the PC except the least significant limb since it is
+        // already range checked through rd_data[0]
+        let mut need_range_check: Vec<AB::Expr> = imm_limbs.map(Into::into).to_vec();
+
+        // The most significant limb of PC is range checked to
+        // [0, 2^(PC_BITS - (RV32_REGISTER_NUM_LIMBS - 1) * RV32_CELL_BITS)) by scaling it up
+        // to a full limb
+        const { assert!(PC_BITS < RV32_CELL_BITS * RV32_REGISTER_NUM_LIMBS) };
+        let msl_shift = 1 << (RV32_REGISTER_NUM_LIMBS * RV32_CELL_BITS - PC_BITS);
+        for (i, limb) in pc_limbs.into_iter().enumerate().skip(1) {
+            if i == RV32_REGISTER_NUM_LIMBS - 1 {
+                need_range_check.push(limb * AB::F::from_canonical_usize(msl_shift));
+            } else {
+                need_range_check.push(limb);
+            }
+        }
+
+        for pair in need_range_check.chunks_exact(2) {
+            self.bus
+                .send_range::<AB::Expr>(pair[0].clone(), pair[1].clone())
+                .eval(builder, is_valid);
+        }
+
+        let imm = imm_limbs.iter().enumerate().fold(AB::Expr::ZERO, |acc, (i, &val)| {
+            acc + val * AB::F::from_canonical_u32(1 << (i * RV32_CELL_BITS))
+        });
+        let expected_opcode = VmCoreAir::<AB, I>::opcode_to_global_expr(self, AUIPC);
+        AdapterAirContext {
+            to_pc: None,
+            reads: [].into(),
+            writes: [rd_data.map(Into::into)].into(),
+            instruction: ImmInstruction {
+                is_valid: is_valid.into(),
+                opcode: expected_opcode,
+                immediate: imm,
+            }
+            .into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        Rv32AuipcOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32AuipcCoreRecord<F> {
+    pub imm_limbs: [F; RV32_REGISTER_NUM_LIMBS - 1],
+    pub pc_limbs: [F; RV32_REGISTER_NUM_LIMBS - 2],
+    pub rd_data: [F; RV32_REGISTER_NUM_LIMBS],
+}
+
+/// Executes AUIPC instructions and fills the rows of [Rv32AuipcCoreAir]. The chip keeps no
+/// records: [Rv32AuipcCoreChip::execute] hands each record back to the caller, which passes them to
+/// [Rv32AuipcCoreChip::generate_trace] once execution is done.
+pub struct Rv32AuipcCoreChip {
+    pub air: Rv32AuipcCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32AuipcCoreChip {
+    pub fn new(bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>) -> Self {
+        Self { air: Rv32AuipcCoreAir { bus: bitwise_lookup_chip.bus() }, bitwise_lookup_chip }
+    }
+
+    /// Executes `rd = from_pc + (imm << 8)` and requests the range checks that [Rv32AuipcCoreAir]
+    /// sends for it. Returns the record of the row, whose `rd_data` holds the limbs of `rd`.
+    pub fn execute(&self, from_pc: u32, imm: u32) -> Rv32AuipcCoreRecord<F> {
+        assert!(from_pc < (1 << PC_BITS), "pc {from_pc} out of range for {PC_BITS} bits");
+        assert!(
+            imm < (1 << (RV32_CELL_BITS * (RV32_REGISTER_NUM_LIMBS - 1))),
+            "imm {imm} out of range for AUIPC"
+        );
+        let rd_data = run_auipc(from_pc, imm);
+
+        let imm_limbs: [u32; RV32_REGISTER_NUM_LIMBS - 1] =
+            array::from_fn(|i| (imm >> (i * RV32_CELL_BITS)) & RV32_LIMB_MAX);
+        let pc_limbs: [u32; RV32_REGISTER_NUM_LIMBS] =
+            array::from_fn(|i| (from_pc >> (i * RV32_CELL_BITS)) & RV32_LIMB_MAX);
+
+        for i in 0..(RV32_REGISTER_NUM_LIMBS / 2) {
+            self.bitwise_lookup_chip.request_range(rd_data[i * 2], rd_data[i * 2 + 1]);
+        }
+        // The same order as in `Rv32AuipcCoreAir::eval`, with the most significant limb of the PC
+        // scaled up to a full limb.
+        let mut need_range_check = imm_limbs.to_vec();
+        for (i, &limb) in pc_limbs.iter().enumerate().skip(1) {
+            if i == RV32_REGISTER_NUM_LIMBS - 1 {
+                need_range_check.push(limb << (RV32_REGISTER_NUM_LIMBS * RV32_CELL_BITS - PC_BITS));
+            } else {
+                need_range_check.push(limb);
+            }
+        }
+        for pair in need_range_check.chunks_exact(2) {
+            self.bitwise_lookup_chip.request_range(pair[0], pair[1]);
+        }
+
+        Rv32AuipcCoreRecord {
+            imm_limbs: imm_limbs.map(F::from_canonical_u32),
+            pc_limbs: array::from_fn(|i| F::from_canonical_u32(pc_limbs[i + 1])),
+            rd_data: rd_data.map(F::from_canonical_u32),
+        }
+    }
+
+    pub fn generate_trace_row(&self, row_slice: &mut [F], record: Rv32AuipcCoreRecord<F>) {
+        let core_cols: &mut Rv32AuipcCoreCols<F> = row_slice.borrow_mut();
+        core_cols.imm_limbs = record.imm_limbs;
+        core_cols.pc_limbs = record.pc_limbs;
+        core_cols.rd_data = record.rd_data;
+        core_cols.is_valid = F::ONE;
+    }
+
+    /// Generates one row per record, padded to a power of two with `is_valid = 0` rows.
+    pub fn generate_trace(&self, records: Vec<Rv32AuipcCoreRecord<F>>) -> RowMajorMatrix<F> {
+        let width = Rv32AuipcCoreCols::<F>::width();
+        let height = next_power_of_two_or_zero(records.len());
+        let mut trace = RowMajorMatrix::new(vec![F::ZERO; height * width], width);
+        for (row, record) in trace.rows_mut().zip(records) {
+            self.generate_trace_row(row, record);
         }
+        trace
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32AuipcCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32AuipcCoreRecord<F>;
+    type Air = Rv32AuipcCoreAir;
+
+    /// Executes AUIPC with `imm` taken from the operand `c`.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        from_pc: u32,
+        _reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        assert_eq!(
+            instruction.opcode,
+            AUIPC.global_opcode(),
+            "opcode {} is not AUIPC",
+            instruction.opcode
+        );
+        let record = self.execute(from_pc, instruction.c.as_canonical_u32());
+        (AdapterRuntimeContext::without_pc([record.rd_data]), record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        Rv32AuipcCoreChip::generate_trace_row(self, row_slice, record);
     }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the limbs of `rd = pc + (imm << 8)`, where `imm` holds the upper 24 bits of the
+/// immediate.
+pub fn run_auipc(pc: u32, imm: u32) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    let rd = pc.wrapping_add(imm << RV32_CELL_BITS);
+    array::from_fn(|i| (rd >> (RV32_CELL_BITS * i)) & RV32_LIMB_MAX)
 }
@@ -0,0 +1,353 @@
+use core::fmt;
+use std::error::Error;
+
+use crate::{
+    adapters::{RV32_MEMORY_AS, RV32_REGISTER_AS},
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{
+        BaseAluOpcode, BranchEqualOpcode, BranchLessThanOpcode, DivRemOpcode, Instruction,
+        LessThanOpcode, LocalOpcode, MulHOpcode, MulOpcode, Rv32AuipcOpcode, Rv32JalLuiOpcode,
+        Rv32JalrOpcode, Rv32LoadStoreOpcode, ShiftOpcode, VmOpcode,
+    },
+    openvm_stark_backend::field::{F, FieldAlgebra},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/transpiler/src/rrs.rs
+// for full implementation details.
+
+pub const RV32_OPCODE_LOAD: u32 = 0b000_0011;
+pub const RV32_OPCODE_MISC_MEM: u32 = 0b000_1111;
+pub const RV32_OPCODE_OP_IMM: u32 = 0b001_0011;
+pub const RV32_OPCODE_AUIPC: u32 = 0b001_0111;
+pub const RV32_OPCODE_STORE: u32 = 0b010_0011;
+pub const RV32_OPCODE_OP: u32 = 0b011_0011;
+pub const RV32_OPCODE_LUI: u32 = 0b011_0111;
+pub const RV32_OPCODE_BRANCH: u32 = 0b110_0011;
+pub const RV32_OPCODE_JALR: u32 = 0b110_0111;
+pub const RV32_OPCODE_JAL: u32 = 0b110_1111;
+pub const RV32_OPCODE_SYSTEM: u32 = 0b111_0011;
+
+/// The funct7 of the M extension in the OP opcode.
+pub const FUNCT7_MULDIV: u32 = 0b000_0001;
+/// The funct7 of SUB, SRA and SRAI.
+pub const FUNCT7_ALT: u32 = 0b010_0000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The two lowest bits are not `0b11`, as for the compressed instructions.
+    Compressed { word: u32 },
+    /// The major opcode in the seven lowest bits is not an RV32 opcode.
+    UnknownOpcode { word: u32, opcode: u32 },
+    /// The funct3 and funct7 fields select no instruction of the major opcode.
+    IllegalFunct { word: u32, funct3: u32, funct7: u32 },
+    /// A valid RV32I instruction that has no core, such as FENCE, ECALL or a CSR access.
+    Unsupported { word: u32, name: &'static str },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Compressed { word } => {
+                write!(f, "word {word:#010x} is not a 32-bit instruction")
+            }
+            Self::UnknownOpcode { word, opcode } => {
+                write!(f, "word {word:#010x} has unknown opcode {opcode:#09b}")
+            }
+            Self::IllegalFunct { word, funct3, funct7 } => {
+                write!(f, "word {word:#010x} has illegal funct3 {funct3:#b} and funct7 {funct7:#x}")
+            }
+            Self::Unsupported { word, name } => {
+                write!(f, "word {word:#010x} is the unsupported instruction {name}")
+            }
+        }
+    }
+}
+
+impl Error for DecodeError {}
+
+/// Decodes RV32IM instruction words into [Instruction]s with the operands the RV32 adapters
+/// expect: registers `x` are the pointers `4 * x` in [RV32_REGISTER_AS], and immediates are
+/// encoded as each core reads them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rv32Decoder {
+    /// Added to the global opcode of every decoded instruction, for VMs whose RV32IM opcode
+    /// classes do not start from their default class offsets.
+    pub offset: usize,
+}
+
+impl Rv32Decoder {
+    pub fn new(offset: usize) -> Self {
+        Self { offset }
+    }
+
+    /// Decodes a single instruction word.
+    pub fn decode(&self, word: u32) -> Result<Instruction<F>, DecodeError> {
+        if word & 0b11 != 0b11 {
+            return Err(DecodeError::Compressed { word });
+        }
+        let [rd_ptr, rs1_ptr, rs2_ptr] =
+            [rd(word), rs1(word), rs2(word)].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+        let funct3 = funct3(word);
+        let funct7 = funct7(word);
+        let illegal = DecodeError::IllegalFunct { word, funct3, funct7 };
+        let register_as = RV32_REGISTER_AS as usize;
+        // Writes to x0 are discarded: instructions that only write rd are nops, and the others
+        // have `f = 0` for their adapters to skip the write.
+        let needs_write = rd(word) != 0;
+
+        let instruction = match opcode(word) {
+            RV32_OPCODE_OP => {
+                let (opcode, e) = match (funct7, funct3) {
+                    (FUNCT7_MULDIV, _) => (muldiv_opcode(funct3), 0),
+                    (0, _) | (FUNCT7_ALT, 0b000 | 0b101) => {
+                        (alu_opcode(funct3, funct7 == FUNCT7_ALT), register_as)
+                    }
+                    _ => return Err(illegal),
+                };
+                self.instruction(opcode, [rd_ptr, rs1_ptr, rs2_ptr, register_as, e])
+            }
+            RV32_OPCODE_OP_IMM => {
+                let imm = match funct3 {
+                    // The shift amount is in the rs2 field, and funct7 selects the shift.
+                    0b001 if funct7 == 0 => rs2(word),
+                    0b101 if funct7 == 0 || funct7 == FUNCT7_ALT => rs2(word),
+                    0b001 | 0b101 => return Err(illegal),
+                    // The 12-bit immediate sign-extended to 24 bits.
+                    _ => (i_imm(word) as u32 & 0xff_ffff) as usize,
+                };
+                // Only the right shifts have a funct7, the other immediates use all 12 bits.
+                let opcode = alu_opcode(funct3, funct3 == 0b101 && funct7 == FUNCT7_ALT);
+                self.instruction(opcode, [rd_ptr, rs1_ptr, imm, register_as, 0])
+            }
+            RV32_OPCODE_LOAD => {
+                let opcode = match funct3 {
+                    0b000 => Rv32LoadStoreOpcode::LOADB,
+                    0b001 => Rv32LoadStoreOpcode::LOADH,
+                    0b010 => Rv32LoadStoreOpcode::LOADW,
+                    0b100 => Rv32LoadStoreOpcode::LOADBU,
+                    0b101 => Rv32LoadStoreOpcode::LOADHU,
+                    _ => return Err(illegal),
+                };
+                self.load_store(opcode.global_opcode(), rd_ptr, rs1_ptr, i_imm(word), needs_write)
+            }
+            RV32_OPCODE_STORE => {
+                let opcode = match funct3 {
+                    0b000 => Rv32LoadStoreOpcode::STOREB,
+                    0b001 => Rv32LoadStoreOpcode::STOREH,
+                    0b010 => Rv32LoadStoreOpcode::STOREW,
+                    _ => return Err(illegal),
+                };
+                self.load_store(opcode.global_opcode(), rs2_ptr, rs1_ptr, s_imm(word), true)
+            }
+            RV32_OPCODE_BRANCH => {
+                let opcode = match funct3 {
+                    0b000 => BranchEqualOpcode::BEQ.global_opcode(),
+                    0b001 => BranchEqualOpcode::BNE.global_opcode(),
+                    0b100 => BranchLessThanOpcode::BLT.global_opcode(),
+                    0b101 => BranchLessThanOpcode::BGE.global_opcode(),
+                    0b110 => BranchLessThanOpcode::BLTU.global_opcode(),
+                    0b111 => BranchLessThanOpcode::BGEU.global_opcode(),
+                    _ => return Err(illegal),
+                };
+                let mut instruction =
+                    self.instruction(opcode, [rs1_ptr, rs2_ptr, 0, register_as, register_as]);
+                instruction.c = signed_field(b_imm(word));
+                instruction
+            }
+            RV32_OPCODE_JAL => {
+                let opcode = Rv32JalLuiOpcode::JAL.global_opcode();
+                let mut instruction =
+                    self.instruction(opcode, [rd_ptr, 0, 0, register_as, 0, needs_write as usize]);
+                instruction.c = signed_field(j_imm(word));
+                instruction
+            }
+            RV32_OPCODE_JALR if funct3 == 0 => {
+                let imm = i_imm(word);
+                self.instruction(
+                    Rv32JalrOpcode::JALR.global_opcode(),
+                    [
+                        rd_ptr,
+                        rs1_ptr,
+                        (imm as u32 & 0xffff) as usize,
+                        register_as,
+                        0,
+                        needs_write as usize,
+                        (imm < 0) as usize,
+                    ],
+                )
+            }
+            RV32_OPCODE_JALR => return Err(illegal),
+            RV32_OPCODE_LUI => {
+                // The upper 20 bits of rd.
+                let imm = (u_imm(word) >> 12) as usize;
+                let opcode = Rv32JalLuiOpcode::LUI.global_opcode();
+                // LUI shares the chip of JAL, whose rd is only written when `f = 1`.
+                self.instruction(opcode, [rd_ptr, 0, imm, register_as, 0, 1])
+            }
+            RV32_OPCODE_AUIPC => {
+                // The upper 24 bits of the immediate, whose lowest limb is zero.
+                let imm = (u_imm(word) >> 8) as usize;
+                let opcode = Rv32AuipcOpcode::AUIPC.global_opcode();
+                self.instruction(opcode, [rd_ptr, 0, imm, register_as, 0])
+            }
+            RV32_OPCODE_MISC_MEM => return Err(DecodeError::Unsupported { word, name: "FENCE" }),
+            RV32_OPCODE_SYSTEM => {
+                let name = match (funct3, word >> 20) {
+                    (0, 0) => "ECALL",
+                    (0, 1) => "EBREAK",
+                    (0, _) => return Err(illegal),
+                    _ => "CSR",
+                };
+                return Err(DecodeError::Unsupported { word, name });
+            }
+            opcode => return Err(DecodeError::UnknownOpcode { word, opcode }),
+        };
+        let only_writes_rd = matches!(
+            opcode(word),
+            RV32_OPCODE_OP | RV32_OPCODE_OP_IMM | RV32_OPCODE_LUI | RV32_OPCODE_AUIPC
+        );
+        match only_writes_rd && !needs_write {
+            true => Ok(self.nop()),
+            false => Ok(instruction),
+        }
+    }
+
+    /// Decodes consecutive instruction words, stopping at the first illegal one.
+    pub fn decode_all(&self, words: &[u32]) -> Result<Vec<Instruction<F>>, DecodeError> {
+        words.iter().map(|&word| self.decode(word)).collect()
+    }
+
+    /// An instruction with the global opcode `opcode` shifted by the offset.
+    fn instruction<const N: usize>(
+        &self,
+        opcode: VmOpcode,
+        operands: [usize; N],
+    ) -> Instruction<F> {
+        Instruction::from_usize(VmOpcode::from_usize(opcode.as_usize() + self.offset), operands)
+    }
+
+    fn load_store(
+        &self,
+        opcode: VmOpcode,
+        rd_rs2: usize,
+        rs1: usize,
+        imm: i32,
+        needs_write: bool,
+    ) -> Instruction<F> {
+        self.instruction(
+            opcode,
+            [
+                rd_rs2,
+                rs1,
+                (imm as u32 & 0xffff) as usize,
+                RV32_REGISTER_AS as usize,
+                RV32_MEMORY_AS as usize,
+                needs_write as usize,
+                (imm < 0) as usize,
+            ],
+        )
+    }
+
+    /// A nop: without a system chip for the NOP phantom instruction of OpenVM, this is a JAL to
+    /// the next instruction that does not write rd.
+    fn nop(&self) -> Instruction<F> {
+        let opcode = Rv32JalLuiOpcode::JAL.global_opcode();
+        self.instruction(opcode, [0, 0, DEFAULT_PC_STEP as usize, RV32_REGISTER_AS as usize, 0, 0])
+    }
+}
+
+/// The global opcode of the OP or OP-IMM instruction with the given funct3, where `alt` selects
+/// SUB and SRA.
+fn alu_opcode(funct3: u32, alt: bool) -> VmOpcode {
+    match funct3 {
+        0b000 if alt => BaseAluOpcode::SUB.global_opcode(),
+        0b000 => BaseAluOpcode::ADD.global_opcode(),
+        0b001 => ShiftOpcode::SLL.global_opcode(),
+        0b010 => LessThanOpcode::SLT.global_opcode(),
+        0b011 => LessThanOpcode::SLTU.global_opcode(),
+        0b100 => BaseAluOpcode::XOR.global_opcode(),
+        0b101 if alt => ShiftOpcode::SRA.global_opcode(),
+        0b101 => ShiftOpcode::SRL.global_opcode(),
+        0b110 => BaseAluOpcode::OR.global_opcode(),
+        _ => BaseAluOpcode::AND.global_opcode(),
+    }
+}
+
+/// The global opcode of the M extension instruction with the given funct3.
+fn muldiv_opcode(funct3: u32) -> VmOpcode {
+    match funct3 {
+        0b000 => MulOpcode::MUL.global_opcode(),
+        0b001 => MulHOpcode::MULH.global_opcode(),
+        0b010 => MulHOpcode::MULHSU.global_opcode(),
+        0b011 => MulHOpcode::MULHU.global_opcode(),
+        0b100 => DivRemOpcode::DIV.global_opcode(),
+        0b101 => DivRemOpcode::DIVU.global_opcode(),
+        0b110 => DivRemOpcode::REM.global_opcode(),
+        _ => DivRemOpcode::REMU.global_opcode(),
+    }
+}
+
+/// A signed immediate as a field element, negative ones being `-|imm|`.
+fn signed_field(imm: i32) -> F {
+    match imm < 0 {
+        true => -F::from_canonical_u32(imm.unsigned_abs()),
+        false => F::from_canonical_u32(imm as u32),
+    }
+}
+
+pub fn opcode(word: u32) -> u32 {
+    word & 0x7f
+}
+
+pub fn rd(word: u32) -> usize {
+    ((word >> 7) & 0x1f) as usize
+}
+
+pub fn funct3(word: u32) -> u32 {
+    (word >> 12) & 0b111
+}
+
+pub fn rs1(word: u32) -> usize {
+    ((word >> 15) & 0x1f) as usize
+}
+
+pub fn rs2(word: u32) -> usize {
+    ((word >> 20) & 0x1f) as usize
+}
+
+pub fn funct7(word: u32) -> u32 {
+    word >> 25
+}
+
+/// The sign-extended 12-bit immediate of the I-type format.
+pub fn i_imm(word: u32) -> i32 {
+    (word as i32) >> 20
+}
+
+/// The sign-extended 12-bit immediate of the S-type format.
+pub fn s_imm(word: u32) -> i32 {
+    ((word as i32) >> 25 << 5) | ((word >> 7) & 0x1f) as i32
+}
+
+/// The sign-extended 13-bit immediate of the B-type format, whose lowest bit is zero.
+pub fn b_imm(word: u32) -> i32 {
+    ((word as i32) >> 31 << 12)
+        | (((word >> 7) & 1) << 11) as i32
+        | (((word >> 25) & 0x3f) << 5) as i32
+        | (((word >> 8) & 0xf) << 1) as i32
+}
+
+/// The 32-bit immediate of the U-type format, whose lowest 12 bits are zero.
+pub fn u_imm(word: u32) -> u32 {
+    word & 0xffff_f000
+}
+
+/// The sign-extended 21-bit immediate of the J-type format, whose lowest bit is zero.
+pub fn j_imm(word: u32) -> i32 {
+    ((word as i32) >> 31 << 20)
+        | (word & 0x000f_f000) as i32
+        | (((word >> 20) & 1) << 11) as i32
+        | (((word >> 21) & 0x3ff) << 1) as i32
+}
@@ -0,0 +1,572 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32MultAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::{BitwiseOperationLookupBus, RangeTupleCheckerBus},
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    instructions::{
+        DivRemOpcode::{self, DIV, DIVU, REM, REMU},
+        Instruction, LocalOpcode,
+    },
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, MinimalInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    less_than::run_less_than,
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    range_tuple::SharedRangeTupleCheckerChip,
+    utils::not,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/divrem/core.rs
+// for full implementation details.
+
+pub type Rv32DivRemChip = VmChipWrapper<Rv32MultAdapterChip, Rv32DivRemCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32DivRemCoreCols<T> {
+        // b = c * q + r for some 0 <= |r| < |c| and sign(r) = sign(b).
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+        pub c: [T; RV32_REGISTER_NUM_LIMBS],
+        pub q: [T; RV32_REGISTER_NUM_LIMBS],
+        pub r: [T; RV32_REGISTER_NUM_LIMBS],
+
+        // Flags to indicate special cases.
+        pub zero_divisor: T,
+        pub r_zero: T,
+
+        // Sign of b and c respectively, while q_sign = b_sign ^ c_sign if q is non-zero and is 0
+        // otherwise. sign_xor = b_sign ^ c_sign always.
+        pub b_sign: T,
+        pub c_sign: T,
+        pub q_sign: T,
+        pub sign_xor: T,
+
+        // Auxiliary columns to constrain that zero_divisor = 1 if and only if c = 0.
+        pub c_sum_inv: T,
+        // Auxiliary columns to constrain that r_zero = 1 if and only if r = 0 and zero_divisor = 0.
+        pub r_sum_inv: T,
+
+        // Auxiliary columns to constrain that 0 <= |r| < |c|. When sign_xor = 1, r_prime = -r,
+        // and r_prime = r otherwise. Each r_inv[i] is the field inverse of r_prime[i] - 2^8,
+        // which ensures each r_prime[i] is in range.
+        pub r_prime: [T; RV32_REGISTER_NUM_LIMBS],
+        pub r_inv: [T; RV32_REGISTER_NUM_LIMBS],
+        pub lt_marker: [T; RV32_REGISTER_NUM_LIMBS],
+        pub lt_diff: T,
+
+        pub opcode_div_flag: T,
+        pub opcode_divu_flag: T,
+        pub opcode_rem_flag: T,
+        pub opcode_remu_flag: T,
+    }
+}
+
+/// Constrains `b = c * q + r` with `0 <= |r| < |c|` and `r` of the sign of `b`, where `a = q`
+/// for DIV and DIVU and `a = r` for REM and REMU. The special cases of RISC-V are a zero divisor,
+/// with `q = -1` and `r = b`, and the signed overflow `-2^31 / -1`, with `q = -2^31` and `r = 0`.
+/// The pairs `(limb, carry)` of `c * q + r` are range checked through the tuple range checker.
+#[derive(Clone, Copy)]
+pub struct Rv32DivRemCoreAir {
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+    pub range_tuple_bus: RangeTupleCheckerBus<2>,
+}
+
+impl BaseAir<F> for Rv32DivRemCoreAir {
+    fn width(&self) -> usize {
+        Rv32DivRemCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32DivRemCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<MinimalInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32DivRemCoreCols<AB::Var> = local_core.borrow();
+        let flags = [
+            cols.opcode_div_flag,
+            cols.opcode_divu_flag,
+            cols.opcode_rem_flag,
+            cols.opcode_remu_flag,
+        ];
+
+        let is_valid = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| {
+            builder.assert_bool(flag);
+            acc + flag
+        });
+        builder.assert_bool(is_valid.clone());
+
+        let b = &cols.b;
+        let c = &cols.c;
+        let q = &cols.q;
+        let r = &cols.r;
+
+        // Constrain that b = (c * q + r) % 2^32 and range check each element in q, with
+        // carry[i] = (sum_{k=0}^{i} c[k] * q[i - k] + r[i] + carry[i - 1] - b[i]) / 2^8.
+        let b_ext = cols.b_sign * AB::F::from_canonical_u32(RV32_LIMB_MAX);
+        let c_ext = cols.c_sign * AB::F::from_canonical_u32(RV32_LIMB_MAX);
+        let carry_divide = AB::F::from_canonical_u32(1 << RV32_CELL_BITS).inverse();
+        let mut carry: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            let expected_limb = if i == 0 { AB::Expr::ZERO } else { carry[i - 1].clone() }
+                + (0..=i).fold(r[i].into(), |acc, k| acc + (c[k] * q[i - k]));
+            carry[i] = (expected_limb - b[i]) * carry_divide;
+        }
+
+        for (q, carry) in q.iter().zip(carry.iter()) {
+            self.range_tuple_bus
+                .send::<AB::Expr, AB::Expr>([(*q).into(), carry.clone()])
+                .eval(builder, is_valid.clone());
+        }
+
+        // Constrain that the upper limbs of c * q + r are all equal to b_ext, i.e. that the
+        // identity holds on the sign extended 64-bit integers, and range check each element in
+        // r. The limbs of r above the 32 bits are b_ext, unless r = 0.
+        let q_ext = cols.q_sign * AB::F::from_canonical_u32(RV32_LIMB_MAX);
+        let mut carry_ext: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+
+        for j in 0..RV32_REGISTER_NUM_LIMBS {
+            let expected_limb = if j == 0 {
+                carry[RV32_REGISTER_NUM_LIMBS - 1].clone()
+            } else {
+                carry_ext[j - 1].clone()
+            } + ((j + 1)..RV32_REGISTER_NUM_LIMBS)
+                .fold(AB::Expr::ZERO, |acc, k| acc + (c[k] * q[RV32_REGISTER_NUM_LIMBS + j - k]))
+                + (0..=j).fold(AB::Expr::ZERO, |acc, k| {
+                    acc + (c[k] * q_ext.clone()) + (q[k] * c_ext.clone())
+                })
+                + (AB::Expr::ONE - cols.r_zero) * b_ext.clone();
+            carry_ext[j] = (expected_limb - b_ext.clone()) * carry_divide;
+        }
+
+        for (r, carry) in r.iter().zip(carry_ext) {
+            self.range_tuple_bus
+                .send::<AB::Expr, AB::Expr>([(*r).into(), carry])
+                .eval(builder, is_valid.clone());
+        }
+
+        // Handle the special cases. At most one of a zero divisor and a zero remainder applies,
+        // where the signed overflow falls under the latter.
+        let special_case = cols.zero_divisor + cols.r_zero;
+        builder.assert_bool(special_case.clone());
+
+        // Constrain that zero_divisor = 1 if and only if c = 0, in which case q = -1. As the
+        // limbs of c are in [0, 2^8), their sum is non-zero if and only if c is.
+        builder.assert_bool(cols.zero_divisor);
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            builder.when(cols.zero_divisor).assert_zero(c[i]);
+            builder
+                .when(cols.zero_divisor)
+                .assert_eq(q[i], AB::F::from_canonical_u32(RV32_LIMB_MAX));
+        }
+        let c_sum = c.iter().fold(AB::Expr::ZERO, |acc, &c| acc + c);
+        let valid_and_not_zero_divisor = is_valid.clone() - cols.zero_divisor;
+        builder.assert_bool(valid_and_not_zero_divisor.clone());
+        builder.when(valid_and_not_zero_divisor).assert_one(c_sum * cols.c_sum_inv);
+
+        // Constrain that r_zero = 1 if and only if r = 0 and zero_divisor = 0.
+        builder.assert_bool(cols.r_zero);
+        for &r in r {
+            builder.when(cols.r_zero).assert_zero(r);
+        }
+        let r_sum = r.iter().fold(AB::Expr::ZERO, |acc, &r| acc + r);
+        let valid_and_not_special_case = is_valid.clone() - special_case.clone();
+        builder.assert_bool(valid_and_not_special_case.clone());
+        builder.when(valid_and_not_special_case.clone()).assert_one(r_sum * cols.r_sum_inv);
+
+        // The signs are the most significant bits of the top limbs of b and c, which are only
+        // used by the signed opcodes: the top limb less the sign bit is in [0, 128) exactly when
+        // doubling it keeps it in [0, 256).
+        let signed = cols.opcode_div_flag + cols.opcode_rem_flag;
+        builder.assert_bool(cols.b_sign);
+        builder.assert_bool(cols.c_sign);
+        builder.when(not::<AB::Expr>(signed.clone())).assert_zero(cols.b_sign);
+        builder.when(not::<AB::Expr>(signed.clone())).assert_zero(cols.c_sign);
+        builder.assert_eq(
+            cols.b_sign + cols.c_sign - AB::Expr::from_canonical_u32(2) * cols.b_sign * cols.c_sign,
+            cols.sign_xor,
+        );
+
+        let sign_mask = AB::F::from_canonical_u32(1 << (RV32_CELL_BITS - 1));
+        self.bitwise_lookup_bus
+            .send_range::<AB::Expr>(
+                (b[RV32_REGISTER_NUM_LIMBS - 1] - cols.b_sign * sign_mask)
+                    * AB::F::from_canonical_u32(2),
+                (c[RV32_REGISTER_NUM_LIMBS - 1] - cols.c_sign * sign_mask)
+                    * AB::F::from_canonical_u32(2),
+            )
+            .eval(builder, signed.clone());
+
+        // q_sign = b_sign ^ c_sign when q is non-zero, and q_sign = 0 when q = 0, except for a
+        // zero divisor where q = -1 is negative exactly when signed. For the signed overflow,
+        // q_sign = b_sign ^ c_sign = 0: q = 2^31 sign extends to a 64-bit c * q + r = -2^31.
+        let nonzero_q = q.iter().fold(AB::Expr::ZERO, |acc, &q| acc + q);
+        builder.assert_bool(cols.q_sign);
+        builder
+            .when(nonzero_q)
+            .when(not::<AB::Expr>(cols.zero_divisor))
+            .assert_eq(cols.q_sign, cols.sign_xor);
+        builder
+            .when(cols.q_sign - cols.sign_xor)
+            .when(not::<AB::Expr>(cols.zero_divisor))
+            .assert_zero(cols.q_sign);
+        builder.when(cols.zero_divisor).assert_eq(cols.q_sign, signed);
+
+        // Constrain r_prime: r_prime = r if sign_xor = 0. Otherwise r_prime = -r, which holds if
+        // each r[i] + r_prime[i] + carry_lt[i - 1] is in {0, 2^8} and r_prime[i] = 0 when the sum
+        // is 0. Both imply 0 <= r_prime[i] <= 2^8, and r_inv[i] excludes r_prime[i] = 2^8.
+        let r_p = &cols.r_prime;
+        let mut carry_lt: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            builder.when(not::<AB::Expr>(cols.sign_xor)).assert_eq(r[i], r_p[i]);
+
+            let last_carry = if i > 0 { carry_lt[i - 1].clone() } else { AB::Expr::ZERO };
+            carry_lt[i] = (last_carry.clone() + r[i] + r_p[i]) * carry_divide;
+            builder.when(cols.sign_xor).assert_zero(
+                (carry_lt[i].clone() - last_carry) * (carry_lt[i].clone() - AB::F::ONE),
+            );
+            builder.when(cols.sign_xor).assert_one(
+                (r_p[i] - AB::F::from_canonical_u32(1 << RV32_CELL_BITS)) * cols.r_inv[i],
+            );
+            builder
+                .when(cols.sign_xor)
+                .when(not::<AB::Expr>(carry_lt[i].clone()))
+                .assert_zero(r_p[i]);
+        }
+
+        // Constrain |r| < |c| outside of the special cases. r_prime has the sign of c, so this is
+        // r_prime < c if c is non-negative and r_prime > c otherwise, comparing the limbs from
+        // the most significant one: lt_marker marks the first that differs, by lt_diff > 0.
+        let marker = &cols.lt_marker;
+        let mut prefix_sum = special_case;
+
+        for i in (0..RV32_REGISTER_NUM_LIMBS).rev() {
+            let diff = r_p[i] * (AB::Expr::from_canonical_u32(2) * cols.c_sign - AB::Expr::ONE)
+                + c[i] * (AB::Expr::ONE - AB::Expr::from_canonical_u32(2) * cols.c_sign);
+            prefix_sum += marker[i].into();
+            builder.assert_bool(marker[i]);
+            builder.assert_zero(not::<AB::Expr>(prefix_sum.clone()) * diff.clone());
+            builder.when(marker[i]).assert_eq(cols.lt_diff, diff);
+        }
+
+        // If r_prime != c, prefix_sum = 1 marks the first limb that differs with its difference,
+        // and if r_prime = c then marking any limb makes lt_diff = 0, which the range check
+        // below rejects.
+        builder.when(is_valid.clone()).assert_one(prefix_sum);
+        self.bitwise_lookup_bus
+            .send_range::<AB::Expr>(cols.lt_diff - AB::Expr::ONE, AB::F::ZERO)
+            .eval(builder, valid_and_not_special_case);
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            flags.iter().zip(DivRemOpcode::ALL).fold(AB::Expr::ZERO, |acc, (&flag, opcode)| {
+                acc + flag * AB::Expr::from_canonical_usize(opcode.local_usize())
+            }),
+        );
+
+        let is_div = cols.opcode_div_flag + cols.opcode_divu_flag;
+        let a = array::from_fn(|i| is_div.clone() * q[i] + not::<AB::Expr>(is_div.clone()) * r[i]);
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: [cols.b.map(Into::into), cols.c.map(Into::into)].into(),
+            writes: [a].into(),
+            instruction: MinimalInstruction { is_valid, opcode: expected_opcode }.into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        DivRemOpcode::CLASS_OFFSET
+    }
+}
+
+/// The RISC-V special cases of the division, which fix its result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DivRemCoreSpecialCase {
+    None,
+    ZeroDivisor,
+    SignedOverflow,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32DivRemCoreRecord<F> {
+    pub opcode: DivRemOpcode,
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub c: [F; RV32_REGISTER_NUM_LIMBS],
+    pub q: [F; RV32_REGISTER_NUM_LIMBS],
+    pub r: [F; RV32_REGISTER_NUM_LIMBS],
+    pub zero_divisor: bool,
+    pub r_zero: bool,
+    pub b_sign: bool,
+    pub c_sign: bool,
+    pub q_sign: bool,
+    pub sign_xor: bool,
+    pub c_sum_inv: F,
+    pub r_sum_inv: F,
+    pub r_prime: [F; RV32_REGISTER_NUM_LIMBS],
+    pub r_inv: [F; RV32_REGISTER_NUM_LIMBS],
+    pub lt_diff_val: F,
+    pub lt_diff_idx: usize,
+}
+
+/// Executes DIV, DIVU, REM and REMU and fills the core columns of [Rv32DivRemCoreAir].
+pub struct Rv32DivRemCoreChip {
+    pub air: Rv32DivRemCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+    pub range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+}
+
+impl Rv32DivRemCoreChip {
+    pub fn new(
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    ) -> Self {
+        // The sign extension doubles the number of products that add up in a limb of the upper
+        // half, compared to the lower half.
+        let [limb_size, carry_size] = *range_tuple_chip.sizes();
+        assert_eq!(limb_size, 1 << RV32_CELL_BITS, "the first range must be a limb");
+        assert!(
+            carry_size as usize >= (2 * RV32_REGISTER_NUM_LIMBS) << RV32_CELL_BITS,
+            "the second range {carry_size} is too small for the carries"
+        );
+        Self {
+            air: Rv32DivRemCoreAir {
+                bitwise_lookup_bus: bitwise_lookup_chip.bus(),
+                range_tuple_bus: range_tuple_chip.bus(),
+            },
+            bitwise_lookup_chip,
+            range_tuple_chip,
+        }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32DivRemCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32DivRemCoreRecord<F>;
+    type Air = Rv32DivRemCoreAir;
+
+    /// Executes the division of `b` by `c`, writes the quotient or the remainder, and requests
+    /// the range checks that [Rv32DivRemCoreAir] sends for it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let divrem_opcode: DivRemOpcode = instruction
+            .opcode
+            .local_opcode()
+            .unwrap_or_else(|| panic!("opcode {} is not a DIV or REM opcode", instruction.opcode));
+        let is_div = matches!(divrem_opcode, DIV | DIVU);
+        let signed = matches!(divrem_opcode, DIV | REM);
+
+        let [b, c] = reads.into();
+        let b_limbs = b.map(|x| x.as_canonical_u32());
+        let c_limbs = c.map(|y| y.as_canonical_u32());
+        let (q, r, b_sign, c_sign, q_sign, case) = run_divrem(signed, &b_limbs, &c_limbs);
+
+        let carries = run_mul_carries(signed, &c_limbs, &q, &r, q_sign);
+        for (limb, carry) in q.iter().chain(&r).zip(carries) {
+            self.range_tuple_chip.add_count(&[*limb, carry]);
+        }
+
+        let sign_xor = b_sign ^ c_sign;
+        let r_prime = if sign_xor { negate(&r) } else { r };
+        let r_zero = r.iter().all(|&limb| limb == 0) && case != DivRemCoreSpecialCase::ZeroDivisor;
+
+        if signed {
+            let b_sign_mask = if b_sign { 1 << (RV32_CELL_BITS - 1) } else { 0 };
+            let c_sign_mask = if c_sign { 1 << (RV32_CELL_BITS - 1) } else { 0 };
+            self.bitwise_lookup_chip.request_range(
+                (b_limbs[RV32_REGISTER_NUM_LIMBS - 1] - b_sign_mask) << 1,
+                (c_limbs[RV32_REGISTER_NUM_LIMBS - 1] - c_sign_mask) << 1,
+            );
+        }
+
+        // |r| < |c| is checked on the most significant limb where r_prime and c differ.
+        let (lt_diff_idx, lt_diff_val) = if case == DivRemCoreSpecialCase::None && !r_zero {
+            let (_, idx, _, _) = run_less_than(false, &r_prime, &c_limbs);
+            let val =
+                if c_sign { r_prime[idx] - c_limbs[idx] } else { c_limbs[idx] - r_prime[idx] };
+            self.bitwise_lookup_chip.request_range(val - 1, 0);
+            (idx, val)
+        } else {
+            (RV32_REGISTER_NUM_LIMBS, 0)
+        };
+
+        let r_prime_f = r_prime.map(F::from_canonical_u32);
+        let eval_sum = |limbs: &[u32; RV32_REGISTER_NUM_LIMBS]| {
+            F::from_canonical_u32(limbs.iter().sum()).try_inverse().unwrap_or(F::ZERO)
+        };
+
+        let q = q.map(F::from_canonical_u32);
+        let r = r.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext::without_pc([if is_div { q } else { r }]);
+        let record = Rv32DivRemCoreRecord {
+            opcode: divrem_opcode,
+            b,
+            c,
+            q,
+            r,
+            zero_divisor: case == DivRemCoreSpecialCase::ZeroDivisor,
+            r_zero,
+            b_sign,
+            c_sign,
+            q_sign,
+            sign_xor,
+            c_sum_inv: eval_sum(&c_limbs),
+            r_sum_inv: eval_sum(&r.map(|x| x.as_canonical_u32())),
+            r_prime: r_prime_f,
+            r_inv: r_prime_f.map(|r| (r - F::from_canonical_u32(1 << RV32_CELL_BITS)).inverse()),
+            lt_diff_val: F::from_canonical_u32(lt_diff_val),
+            lt_diff_idx,
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32DivRemCoreCols<F> = row_slice.borrow_mut();
+        row_slice.b = record.b;
+        row_slice.c = record.c;
+        row_slice.q = record.q;
+        row_slice.r = record.r;
+        row_slice.zero_divisor = F::from_bool(record.zero_divisor);
+        row_slice.r_zero = F::from_bool(record.r_zero);
+        row_slice.b_sign = F::from_bool(record.b_sign);
+        row_slice.c_sign = F::from_bool(record.c_sign);
+        row_slice.q_sign = F::from_bool(record.q_sign);
+        row_slice.sign_xor = F::from_bool(record.sign_xor);
+        row_slice.c_sum_inv = record.c_sum_inv;
+        row_slice.r_sum_inv = record.r_sum_inv;
+        row_slice.r_prime = record.r_prime;
+        row_slice.r_inv = record.r_inv;
+        row_slice.lt_marker = array::from_fn(|i| F::from_bool(i == record.lt_diff_idx));
+        row_slice.lt_diff = record.lt_diff_val;
+        row_slice.opcode_div_flag = F::from_bool(record.opcode == DIV);
+        row_slice.opcode_divu_flag = F::from_bool(record.opcode == DIVU);
+        row_slice.opcode_rem_flag = F::from_bool(record.opcode == REM);
+        row_slice.opcode_remu_flag = F::from_bool(record.opcode == REMU);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Divides `x` by `y`, as two's complement integers if `signed`, rounding towards zero.
+///
+/// Returns the limbs of the quotient and of the remainder, the signs of `x` and `y`, the sign of
+/// the quotient as [Rv32DivRemCoreAir] constrains it, and the special case that applies.
+pub fn run_divrem(
+    signed: bool,
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> (
+    [u32; RV32_REGISTER_NUM_LIMBS],
+    [u32; RV32_REGISTER_NUM_LIMBS],
+    bool,
+    bool,
+    bool,
+    DivRemCoreSpecialCase,
+) {
+    let x_sign = signed && x[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1) == 1;
+    let y_sign = signed && y[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1) == 1;
+    let x_value = compose(x);
+    let y_value = compose(y);
+
+    if y_value == 0 {
+        let q = [RV32_LIMB_MAX; RV32_REGISTER_NUM_LIMBS];
+        return (q, *x, x_sign, y_sign, signed, DivRemCoreSpecialCase::ZeroDivisor);
+    }
+    if signed && x_value == 1 << 31 && y_value == u32::MAX {
+        let r = [0; RV32_REGISTER_NUM_LIMBS];
+        return (*x, r, x_sign, y_sign, false, DivRemCoreSpecialCase::SignedOverflow);
+    }
+
+    let (q, r) = if signed {
+        let (x, y) = (x_value as i32, y_value as i32);
+        ((x / y) as u32, (x % y) as u32)
+    } else {
+        (x_value / y_value, x_value % y_value)
+    };
+    let q_sign = q != 0 && (x_sign ^ y_sign);
+    (decompose(q), decompose(r), x_sign, y_sign, q_sign, DivRemCoreSpecialCase::None)
+}
+
+/// Returns the carries out of the limbs of the 64-bit `d * q + r`, with `d` and `r` sign
+/// extended if `signed` and `q` sign extended by `q_sign`: the lower half first, then the upper
+/// half.
+pub fn run_mul_carries(
+    signed: bool,
+    d: &[u32; RV32_REGISTER_NUM_LIMBS],
+    q: &[u32; RV32_REGISTER_NUM_LIMBS],
+    r: &[u32; RV32_REGISTER_NUM_LIMBS],
+    q_sign: bool,
+) -> [u32; 2 * RV32_REGISTER_NUM_LIMBS] {
+    let mut carry = [0; 2 * RV32_REGISTER_NUM_LIMBS];
+    for i in 0..RV32_REGISTER_NUM_LIMBS {
+        let mut val = r[i] + if i > 0 { carry[i - 1] } else { 0 };
+        for k in 0..=i {
+            val += d[k] * q[i - k];
+        }
+        carry[i] = val >> RV32_CELL_BITS;
+    }
+
+    let sign_ext = |sign: bool| if sign { RV32_LIMB_MAX } else { 0 };
+    let q_ext = sign_ext(q_sign);
+    let d_ext = sign_ext(signed && d[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1) == 1);
+    let r_ext = sign_ext(signed && r[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1) == 1);
+    let mut d_prefix = 0;
+    let mut q_prefix = 0;
+
+    for i in 0..RV32_REGISTER_NUM_LIMBS {
+        d_prefix += d[i];
+        q_prefix += q[i];
+        let mut val = carry[RV32_REGISTER_NUM_LIMBS + i - 1] + d_prefix * q_ext + q_prefix * d_ext;
+        val += r_ext;
+        for k in (i + 1)..RV32_REGISTER_NUM_LIMBS {
+            val += d[k] * q[RV32_REGISTER_NUM_LIMBS + i - k];
+        }
+        carry[RV32_REGISTER_NUM_LIMBS + i] = val >> RV32_CELL_BITS;
+    }
+    carry
+}
+
+fn compose(limbs: &[u32; RV32_REGISTER_NUM_LIMBS]) -> u32 {
+    limbs.iter().rev().fold(0, |acc, &limb| (acc << RV32_CELL_BITS) | limb)
+}
+
+fn decompose(value: u32) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    array::from_fn(|i| (value >> (RV32_CELL_BITS * i)) & RV32_LIMB_MAX)
+}
+
+fn negate(limbs: &[u32; RV32_REGISTER_NUM_LIMBS]) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    decompose(compose(limbs).wrapping_neg())
+}
@@ -0,0 +1,274 @@
+use core::fmt;
+use std::{collections::BTreeMap, error::Error, fs, io, path::Path};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/toolchain/transpiler/src/elf.rs
+// for full implementation details.
+
+/// The number of bytes in a word of the memory image.
+pub const WORD_SIZE: usize = 4;
+
+const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+const ELFCLASS32: u8 = 1;
+const ELFDATA2LSB: u8 = 1;
+const ET_EXEC: u16 = 2;
+const EM_RISCV: u16 = 0xf3;
+const EHDR_SIZE: usize = 52;
+const PHDR_SIZE: usize = 32;
+const SHDR_SIZE: usize = 40;
+
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const PT_INTERP: u32 = 3;
+const PT_SHLIB: u32 = 5;
+const PT_TLS: u32 = 7;
+/// The flag of executable segments.
+const PF_X: u32 = 1;
+
+const SHT_RELA: u32 = 4;
+const SHT_REL: u32 = 9;
+/// The section types that need nothing from the loader: null, program data, symbol and string
+/// tables, notes, bss, init/fini arrays and the RISC-V attributes.
+const SHT_SUPPORTED: [u32; 10] = [0, 1, 2, 3, 7, 8, 14, 15, 16, 0x7000_0003];
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ElfError {
+    /// The file could not be read.
+    Io(io::ErrorKind),
+    /// The input ends before the `len` bytes at `offset`.
+    Truncated {
+        offset: usize,
+        len: usize,
+    },
+    BadMagic,
+    /// Not a 32-bit ELF.
+    UnsupportedClass(u8),
+    /// Not a little-endian ELF.
+    UnsupportedEncoding(u8),
+    /// Not a RISC-V ELF.
+    UnsupportedMachine(u16),
+    /// Not an executable, e.g. an object file or a shared library.
+    UnsupportedType(u16),
+    /// The entry point is unaligned or beyond the memory.
+    InvalidEntry(u32),
+    /// A segment that needs dynamic linking or thread-local storage.
+    UnsupportedSegment {
+        index: usize,
+        kind: u32,
+    },
+    /// A loadable segment that is unaligned, larger in the file than in memory, or beyond the
+    /// memory.
+    InvalidSegment {
+        index: usize,
+        vaddr: u32,
+        mem_size: u32,
+    },
+    /// The executable segments do not form a single range of code.
+    NonContiguousCode {
+        vaddr: u32,
+    },
+    /// A section with relocations, which must have been applied by the linker.
+    Relocation {
+        index: usize,
+        name: String,
+    },
+    /// A section of a type that the loader does not know how to load.
+    UnsupportedSection {
+        index: usize,
+        name: String,
+        kind: u32,
+    },
+}
+
+impl fmt::Display for ElfError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(kind) => write!(f, "failed to read the ELF: {kind}"),
+            Self::Truncated { offset, len } => {
+                write!(f, "ELF truncated: {len} bytes at offset {offset:#x} are out of bounds")
+            }
+            Self::BadMagic => write!(f, "not an ELF: bad magic number"),
+            Self::UnsupportedClass(class) => write!(f, "ELF class {class} is not 32-bit"),
+            Self::UnsupportedEncoding(data) => {
+                write!(f, "ELF data encoding {data} is not little-endian")
+            }
+            Self::UnsupportedMachine(machine) => {
+                write!(f, "ELF machine {machine:#x} is not RISC-V")
+            }
+            Self::UnsupportedType(kind) => write!(f, "ELF type {kind} is not an executable"),
+            Self::InvalidEntry(entry) => write!(f, "invalid entry point {entry:#x}"),
+            Self::UnsupportedSegment { index, kind } => {
+                write!(f, "segment {index} has unsupported type {kind:#x}")
+            }
+            Self::InvalidSegment { index, vaddr, mem_size } => {
+                write!(f, "invalid segment {index} of {mem_size:#x} bytes at {vaddr:#x}")
+            }
+            Self::NonContiguousCode { vaddr } => {
+                write!(f, "executable segment at {vaddr:#x} does not continue the code")
+            }
+            Self::Relocation { index, name } => {
+                write!(f, "section {index} ({name}) has unsupported relocations")
+            }
+            Self::UnsupportedSection { index, name, kind } => {
+                write!(f, "section {index} ({name}) has unsupported type {kind:#x}")
+            }
+        }
+    }
+}
+
+impl Error for ElfError {}
+
+impl From<io::Error> for ElfError {
+    fn from(error: io::Error) -> Self {
+        Self::Io(error.kind())
+    }
+}
+
+/// A RISC-V program loaded from a statically linked ELF32 executable.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Elf {
+    /// The words of the executable segments, from `pc_base` on.
+    pub instructions: Vec<u32>,
+    /// The entry point.
+    pub pc_start: u32,
+    /// The address of the first instruction.
+    pub pc_base: u32,
+    /// The words of the other loadable segments by address, where the bytes beyond the file
+    /// data are zeros.
+    pub memory_image: BTreeMap<u32, u32>,
+}
+
+impl Elf {
+    /// Reads and decodes the ELF at `path`. See [Elf::decode].
+    pub fn load(path: impl AsRef<Path>, max_mem: u32) -> Result<Self, ElfError> {
+        Self::decode(&fs::read(path)?, max_mem)
+    }
+
+    /// Decodes a little-endian RISC-V ELF32 executable whose loadable segments fit below
+    /// `max_mem`. Relocations, dynamic linking and thread-local storage are rejected.
+    pub fn decode(input: &[u8], max_mem: u32) -> Result<Self, ElfError> {
+        let ident = bytes(input, 0, 16)?;
+        if ident[..4] != ELF_MAGIC {
+            return Err(ElfError::BadMagic);
+        }
+        if ident[4] != ELFCLASS32 {
+            return Err(ElfError::UnsupportedClass(ident[4]));
+        }
+        if ident[5] != ELFDATA2LSB {
+            return Err(ElfError::UnsupportedEncoding(ident[5]));
+        }
+        bytes(input, 0, EHDR_SIZE)?;
+        let kind = u16_at(input, 16)?;
+        if kind != ET_EXEC {
+            return Err(ElfError::UnsupportedType(kind));
+        }
+        let machine = u16_at(input, 18)?;
+        if machine != EM_RISCV {
+            return Err(ElfError::UnsupportedMachine(machine));
+        }
+        let entry = u32_at(input, 24)?;
+        if entry % WORD_SIZE as u32 != 0 || entry >= max_mem {
+            return Err(ElfError::InvalidEntry(entry));
+        }
+
+        check_sections(input)?;
+
+        let mut elf = Self { pc_start: entry, ..Self::default() };
+        let phoff = u32_at(input, 28)? as usize;
+        let phnum = u16_at(input, 44)? as usize;
+        for index in 0..phnum {
+            let header = bytes(input, phoff + index * PHDR_SIZE, PHDR_SIZE)?;
+            // The header is in bounds, and so are its fields.
+            let field = |i: usize| u32_at(header, i * 4).unwrap();
+            let kind = field(0);
+            match kind {
+                PT_LOAD => {}
+                PT_DYNAMIC | PT_INTERP | PT_SHLIB | PT_TLS => {
+                    return Err(ElfError::UnsupportedSegment { index, kind });
+                }
+                _ => continue,
+            }
+            let [offset, vaddr, _, file_size, mem_size, flags] = [1, 2, 3, 4, 5, 6].map(field);
+            if vaddr % WORD_SIZE as u32 != 0
+                || file_size > mem_size
+                || vaddr.checked_add(mem_size).is_none_or(|end| end > max_mem)
+            {
+                return Err(ElfError::InvalidSegment { index, vaddr, mem_size });
+            }
+            let data = bytes(input, offset as usize, file_size as usize)?;
+
+            let is_code = flags & PF_X != 0;
+            if is_code {
+                if elf.instructions.is_empty() {
+                    elf.pc_base = vaddr;
+                }
+                let code_end = elf.pc_base + (elf.instructions.len() * WORD_SIZE) as u32;
+                if vaddr != code_end {
+                    return Err(ElfError::NonContiguousCode { vaddr });
+                }
+            }
+            for i in (0..mem_size as usize).step_by(WORD_SIZE) {
+                let mut word = [0; WORD_SIZE];
+                let file_bytes = data.get(i..).unwrap_or_default();
+                let len = file_bytes.len().min(WORD_SIZE);
+                word[..len].copy_from_slice(&file_bytes[..len]);
+                let word = u32::from_le_bytes(word);
+                if is_code {
+                    elf.instructions.push(word);
+                } else {
+                    elf.memory_image.insert(vaddr + i as u32, word);
+                }
+            }
+        }
+        Ok(elf)
+    }
+}
+
+/// Rejects the sections that need relocation or dynamic linking.
+fn check_sections(input: &[u8]) -> Result<(), ElfError> {
+    let shoff = u32_at(input, 32)? as usize;
+    let shnum = u16_at(input, 48)? as usize;
+    let shstrndx = u16_at(input, 50)? as usize;
+    let section = |index: usize| bytes(input, shoff + index * SHDR_SIZE, SHDR_SIZE);
+    let name = |header: &[u8]| -> Result<String, ElfError> {
+        if shstrndx == 0 || shstrndx >= shnum {
+            return Ok(String::new());
+        }
+        let strtab = section(shstrndx)?;
+        let (offset, sh_name) = (u32_at(strtab, 16)?, u32_at(header, 0)?);
+        let start = offset
+            .checked_add(sh_name)
+            .ok_or(ElfError::Truncated { offset: offset as usize, len: sh_name as usize })?
+            as usize;
+        let name = input.get(start..).unwrap_or_default();
+        let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        Ok(String::from_utf8_lossy(&name[..len]).into_owned())
+    };
+
+    for index in 0..shnum {
+        let header = section(index)?;
+        let kind = u32_at(header, 4)?;
+        if kind == SHT_REL || kind == SHT_RELA {
+            return Err(ElfError::Relocation { index, name: name(header)? });
+        }
+        if !SHT_SUPPORTED.contains(&kind) {
+            return Err(ElfError::UnsupportedSection { index, name: name(header)?, kind });
+        }
+    }
+    Ok(())
+}
+
+fn bytes(input: &[u8], offset: usize, len: usize) -> Result<&[u8], ElfError> {
+    offset
+        .checked_add(len)
+        .and_then(|end| input.get(offset..end))
+        .ok_or(ElfError::Truncated { offset, len })
+}
+
+fn u16_at(input: &[u8], offset: usize) -> Result<u16, ElfError> {
+    Ok(u16::from_le_bytes(bytes(input, offset, 2)?.try_into().unwrap()))
+}
+
+fn u32_at(input: &[u8], offset: usize) -> Result<u32, ElfError> {
+    Ok(u32::from_le_bytes(bytes(input, offset, 4)?.try_into().unwrap()))
+}
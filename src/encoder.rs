@@ -0,0 +1,196 @@
+use crate::{
+    openvm_stark_backend::{
+        air::AirBuilder,
+        field::{F, Field, FieldAlgebra},
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/encoder/mod.rs
+// for full implementation details.
+
+/// Efficient encoding of circuit selectors.
+///
+/// Each flag corresponds to a point with non-negative integer coordinates in `F^k` whose
+/// coordinates sum to at most `max_flag_degree`. Such a point is stored in `k` columns, and the
+/// flag expression is the Lagrange polynomial of degree `max_flag_degree` that is one on the point
+/// and zero on every other point of the simplex. This needs far fewer columns than one boolean
+/// column per flag: with degree 2, `k` columns encode `(k + 1)(k + 2) / 2` points.
+#[derive(Clone, Debug)]
+pub struct Encoder {
+    /// The number of flags, excluding the invalid/dummy flag.
+    flag_cnt: usize,
+    /// Maximal degree of the flag expressions.
+    /// The maximal degree of the constraints in [Encoder::eval], however, is
+    /// `max_flag_degree + 1`.
+    max_flag_degree: u32,
+    /// All points in the simplex, in lexicographic order. The first one is the zero point.
+    pts: Vec<Vec<u32>>,
+    /// Whether the zero point is reserved for invalid/dummy rows, in which case no flag is set on
+    /// an all-zero row.
+    reserve_invalid: bool,
+}
+
+impl Encoder {
+    /// Creates an encoder for `cnt` flags whose expressions have degree `max_degree`.
+    /// If `reserve_invalid` is set, the zero point is reserved for rows where no flag is set.
+    pub fn new(cnt: usize, max_degree: u32, reserve_invalid: bool) -> Self {
+        assert!(max_degree > 0, "max_degree must be positive");
+        // The number of points in `k` dimensions is `binomial(k + max_degree, max_degree)`.
+        let binomial =
+            |k: u32| (1..=max_degree).fold(1u64, |acc, i| acc * (k + i) as u64 / i as u64);
+        let needed = (cnt + reserve_invalid as usize) as u64;
+        let k = (1..).find(|&k| binomial(k) >= needed).unwrap() as usize;
+
+        let mut cur = vec![0u32; k];
+        let mut pts = Vec::new();
+        loop {
+            pts.push(cur.clone());
+            if cur[0] == max_degree {
+                break;
+            }
+            // Advance to the next point in lexicographic order.
+            let mut i = k - 1;
+            while cur.iter().sum::<u32>() == max_degree {
+                cur[i] = 0;
+                i -= 1;
+            }
+            cur[i] += 1;
+        }
+        Self { flag_cnt: cnt, max_flag_degree: max_degree, pts, reserve_invalid }
+    }
+
+    /// The number of columns used for the encoding.
+    pub fn width(&self) -> usize {
+        self.pts[0].len()
+    }
+
+    pub fn flag_cnt(&self) -> usize {
+        self.flag_cnt
+    }
+
+    /// The point encoding flag `flag_idx`.
+    pub fn get_flag_pt(&self, flag_idx: usize) -> Vec<u32> {
+        assert!(flag_idx < self.flag_cnt, "flag index {flag_idx} out of range");
+        self.pts[flag_idx + self.reserve_invalid as usize].clone()
+    }
+
+    /// The Lagrange polynomial that is one on `pt` and zero on the other points of the simplex.
+    fn expression_for_point<AB: AirBuilder>(&self, pt: &[u32], vars: &[AB::Var]) -> AB::Expr {
+        assert_eq!(pt.len(), self.width(), "wrong point dimension");
+        assert_eq!(vars.len(), self.width(), "wrong number of variables");
+        let mut expr = AB::Expr::ONE;
+        let mut denom = AB::F::ONE;
+        // Vanishes on the points that are smaller than `pt` in some coordinate.
+        for (&var, &coord) in vars.iter().zip(pt) {
+            for j in 0..coord {
+                expr *= var - AB::F::from_canonical_u32(j);
+                denom *= AB::F::from_canonical_u32(coord - j);
+            }
+        }
+        // Vanishes on the remaining points, whose coordinates sum to more than `pt`'s.
+        let d = pt.iter().sum::<u32>();
+        let var_sum = vars.iter().fold(AB::Expr::ZERO, |acc, &var| acc + var);
+        for j in 0..(self.max_flag_degree - d) {
+            expr *= AB::Expr::from_canonical_u32(self.max_flag_degree - j) - var_sum.clone();
+            denom *= AB::F::from_canonical_u32(j + 1);
+        }
+        expr * denom.inverse()
+    }
+
+    /// Returns an expression of degree `max_flag_degree` that is one if `vars` encodes flag
+    /// `flag_idx` and zero otherwise.
+    pub fn get_flag_expr<AB: AirBuilder>(&self, flag_idx: usize, vars: &[AB::Var]) -> AB::Expr {
+        let pt = self.get_flag_pt(flag_idx);
+        self.expression_for_point::<AB>(&pt, vars)
+    }
+
+    /// Returns the expressions of all flags.
+    pub fn flags<AB: AirBuilder>(&self, vars: &[AB::Var]) -> Vec<AB::Expr> {
+        (0..self.flag_cnt).map(|i| self.get_flag_expr::<AB>(i, vars)).collect()
+    }
+
+    /// Returns an expression that is one if any flag is set and zero otherwise, i.e. when `vars`
+    /// is the reserved zero point. Without a reserved point some flag is always set.
+    pub fn is_valid<AB: AirBuilder>(&self, vars: &[AB::Var]) -> AB::Expr {
+        if !self.reserve_invalid {
+            return AB::Expr::ONE;
+        }
+        AB::Expr::ONE - self.expression_for_point::<AB>(&self.pts[0], vars)
+    }
+
+    /// Returns an expression that is one if any of the flags in `flag_idxs` is set.
+    pub fn contains_flag<AB: AirBuilder>(&self, vars: &[AB::Var], flag_idxs: &[usize]) -> AB::Expr {
+        flag_idxs
+            .iter()
+            .fold(AB::Expr::ZERO, |acc, &flag_idx| acc + self.get_flag_expr::<AB>(flag_idx, vars))
+    }
+
+    /// Returns `sum(flag_i * val_i)` over the given `(flag_idx, val)` pairs, which evaluates to
+    /// the value of the flag that is set, or zero if none of them is.
+    pub fn flag_with_val<AB: AirBuilder>(
+        &self,
+        vars: &[AB::Var],
+        flag_idx_vals: &[(usize, usize)],
+    ) -> AB::Expr {
+        flag_idx_vals.iter().fold(AB::Expr::ZERO, |acc, &(flag_idx, val)| {
+            acc + self.get_flag_expr::<AB>(flag_idx, vars) * AB::F::from_canonical_usize(val)
+        })
+    }
+}
+
+impl<AB: AirBuilder> SubAir<AB> for Encoder {
+    /// The encoding columns.
+    type AirContext<'a>
+        = &'a [AB::Var]
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    /// Constrains `local` to be one of the used points: each coordinate and the sum of all
+    /// coordinates lie in `[0, max_flag_degree]`, and the points that encode no flag are excluded.
+    fn eval<'a>(&'a self, builder: &'a mut AB, local: &'a [AB::Var])
+    where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        assert_eq!(local.len(), self.width(), "wrong number of variables");
+        let falling_factorial = |lin: AB::Expr| {
+            (0..=self.max_flag_degree)
+                .fold(AB::Expr::ONE, |acc, i| acc * (lin.clone() - AB::F::from_canonical_u32(i)))
+        };
+        for &var in local {
+            builder.assert_zero(falling_factorial(var.into()));
+        }
+        builder.assert_zero(falling_factorial(
+            local.iter().fold(AB::Expr::ZERO, |acc, &var| acc + var),
+        ));
+        let used = self.flag_cnt + self.reserve_invalid as usize;
+        for pt in &self.pts[used..] {
+            builder.assert_zero(self.expression_for_point::<AB>(pt, local));
+        }
+    }
+}
+
+impl TraceSubRowGenerator<F> for Encoder {
+    /// The flag to set, or `None` for the reserved invalid point.
+    type TraceContext<'a> = Option<usize>;
+    /// The encoding columns.
+    type ColsMut<'a> = &'a mut [F];
+
+    fn generate_subrow<'a>(&'a self, flag_idx: Option<usize>, cols: &'a mut [F]) {
+        assert_eq!(cols.len(), self.width(), "wrong number of columns");
+        let pt = match flag_idx {
+            Some(flag_idx) => self.get_flag_pt(flag_idx),
+            None => {
+                assert!(self.reserve_invalid, "the encoder has no invalid point");
+                self.pts[0].clone()
+            }
+        };
+        for (col, coord) in cols.iter_mut().zip(pt) {
+            *col = F::from_canonical_u32(coord);
+        }
+    }
+}
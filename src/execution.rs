@@ -0,0 +1,194 @@
+use core::fmt;
+use std::error::Error;
+
+use crate::{
+    aligned_borrow,
+    bus::{ExecutionBus, ProgramBus},
+    instructions::{Instruction, NUM_OPERANDS, VmOpcode},
+    memory::MemoryController,
+    openvm_stark_backend::{
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/arch/execution.rs
+// for full implementation details.
+
+/// The amount by which the pc advances after an instruction that does not jump.
+pub const DEFAULT_PC_STEP: u32 = 4;
+/// The timestamp of the first instruction. Timestamp 0 is reserved for the initial memory.
+pub const INITIAL_TIMESTAMP: u32 = 1;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+    pub struct ExecutionState<T> {
+        pub pc: T,
+        pub timestamp: T,
+    }
+}
+
+impl<T> ExecutionState<T> {
+    pub fn new(pc: impl Into<T>, timestamp: impl Into<T>) -> Self {
+        Self { pc: pc.into(), timestamp: timestamp.into() }
+    }
+
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> ExecutionState<U> {
+        ExecutionState { pc: f(self.pc), timestamp: f(self.timestamp) }
+    }
+}
+
+/// A fault raised while executing a program, instead of executing the faulting instruction.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExecutionError {
+    /// There is no instruction of the program at `pc`.
+    PcNotFound { pc: u32, pc_base: u32, program_len: usize },
+    /// No executor handles `opcode`.
+    DisabledOperation { pc: u32, opcode: VmOpcode },
+    /// A jump to `to_pc`, which is not a valid pc.
+    PcOutOfBounds { pc: u32, to_pc: u32 },
+    /// A memory access at `address`, which is beyond the memory.
+    AddressOutOfBounds { pc: u32, address: u32 },
+    /// A memory access of `width` bytes at `address`, which is not a multiple of `width`.
+    MisalignedAccess { pc: u32, address: u32, width: usize },
+    /// An instruction whose memory accesses, starting at `timestamp`, would not all have a
+    /// timestamp of `timestamp_max_bits`.
+    TimestampOverflow { pc: u32, timestamp: u32 },
+    /// An instruction that writes `rd = x0`, which the adapters would write like any other
+    /// register. The decoder never emits one.
+    ZeroRegisterWrite { pc: u32 },
+    /// The program did not halt within `max_steps` instructions.
+    StepLimit { max_steps: usize },
+}
+
+impl fmt::Display for ExecutionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::PcNotFound { pc, pc_base, program_len } => write!(
+                f,
+                "pc {pc:#x} not found in the program of {program_len} instructions from \
+                 {pc_base:#x}"
+            ),
+            Self::DisabledOperation { pc, opcode } => {
+                write!(f, "at pc {pc:#x}: opcode {opcode} has no executor")
+            }
+            Self::PcOutOfBounds { pc, to_pc } => {
+                write!(f, "at pc {pc:#x}: jump to {to_pc:#x} out of bounds")
+            }
+            Self::AddressOutOfBounds { pc, address } => {
+                write!(f, "at pc {pc:#x}: address {address:#x} out of bounds")
+            }
+            Self::MisalignedAccess { pc, address, width } => {
+                write!(f, "at pc {pc:#x}: {width}-byte access at misaligned address {address:#x}")
+            }
+            Self::TimestampOverflow { pc, timestamp } => {
+                write!(f, "at pc {pc:#x}: the timestamps run out from {timestamp}")
+            }
+            Self::ZeroRegisterWrite { pc } => write!(f, "at pc {pc:#x}: write to x0"),
+            Self::StepLimit { max_steps } => {
+                write!(f, "the program did not halt within {max_steps} steps")
+            }
+        }
+    }
+}
+
+impl Error for ExecutionError {}
+
+/// Executes the instructions of an opcode class and records them for trace generation.
+pub trait InstructionExecutor {
+    /// Executes `instruction` from `from_state` and returns the execution state after it, or the
+    /// fault that the instruction raises before accessing memory.
+    fn execute(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<ExecutionState<u32>, ExecutionError>;
+}
+
+/// Constrains that an instruction is part of the program and moves the execution state from
+/// `from_state` to `to_state`.
+#[derive(Clone, Copy, Debug)]
+pub struct ExecutionBridge {
+    pub execution_bus: ExecutionBus,
+    pub program_bus: ProgramBus,
+}
+
+impl ExecutionBridge {
+    pub fn new(execution_bus: ExecutionBus, program_bus: ProgramBus) -> Self {
+        Self { execution_bus, program_bus }
+    }
+
+    /// Prepares the execution of the instruction `opcode` with `operands` from `from_state` to
+    /// `to_state`. Missing operands are padded with zeros.
+    #[must_use]
+    pub fn execute<T: FieldAlgebra>(
+        &self,
+        opcode: impl Into<T>,
+        operands: impl IntoIterator<Item = impl Into<T>>,
+        from_state: ExecutionState<impl Into<T>>,
+        to_state: ExecutionState<impl Into<T>>,
+    ) -> ExecutionBridgeInteractor<T> {
+        let operands: Vec<T> = operands.into_iter().map(Into::into).collect();
+        assert!(
+            operands.len() <= NUM_OPERANDS,
+            "too many operands, expected at most {NUM_OPERANDS}"
+        );
+        ExecutionBridgeInteractor {
+            execution_bus: self.execution_bus,
+            program_bus: self.program_bus,
+            opcode: opcode.into(),
+            operands,
+            from_state: from_state.map(Into::into),
+            to_state: to_state.map(Into::into),
+        }
+    }
+
+    /// Like [ExecutionBridge::execute] for an instruction that advances the pc by
+    /// [DEFAULT_PC_STEP] and the timestamp by `timestamp_change`.
+    #[must_use]
+    pub fn execute_and_increment_pc<T: FieldAlgebra>(
+        &self,
+        opcode: impl Into<T>,
+        operands: impl IntoIterator<Item = impl Into<T>>,
+        from_state: ExecutionState<impl Into<T>>,
+        timestamp_change: impl Into<T>,
+    ) -> ExecutionBridgeInteractor<T> {
+        let from_state = from_state.map(Into::into);
+        let to_state = ExecutionState {
+            pc: from_state.pc.clone() + T::from_canonical_u32(DEFAULT_PC_STEP),
+            timestamp: from_state.timestamp.clone() + timestamp_change.into(),
+        };
+        self.execute(opcode, operands, from_state, to_state)
+    }
+}
+
+/// A not-yet-evaluated execution of an instruction, see [ExecutionBridge::execute].
+#[derive(Clone, Debug)]
+pub struct ExecutionBridgeInteractor<T> {
+    execution_bus: ExecutionBus,
+    program_bus: ProgramBus,
+    opcode: T,
+    operands: Vec<T>,
+    from_state: ExecutionState<T>,
+    to_state: ExecutionState<T>,
+}
+
+impl<T: FieldAlgebra> ExecutionBridgeInteractor<T> {
+    /// Looks up the instruction at `from_state.pc` and moves the execution state, both with
+    /// multiplicity `multiplicity`.
+    ///
+    /// Caller must constrain that `multiplicity` is boolean.
+    pub fn eval<AB>(self, builder: &mut AB, multiplicity: impl Into<AB::Expr>)
+    where
+        AB: InteractionBuilder,
+        T: Into<AB::Expr>,
+    {
+        let multiplicity = multiplicity.into();
+        self.program_bus
+            .lookup_instruction::<T>(self.from_state.pc.clone(), self.opcode, self.operands)
+            .eval(builder, multiplicity.clone());
+        self.execution_bus.execute(builder, multiplicity, self.from_state, self.to_state);
+    }
+}
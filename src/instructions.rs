@@ -0,0 +1,214 @@
+use core::fmt;
+
+use crate::openvm_stark_backend::field::FieldAlgebra;
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/toolchain/instructions/src/lib.rs
+// for full implementation details.
+
+/// A global opcode, i.e. a local opcode shifted by the class offset of its opcode class.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct VmOpcode(usize);
+
+impl VmOpcode {
+    pub const fn from_usize(value: usize) -> Self {
+        Self(value)
+    }
+
+    pub const fn as_usize(&self) -> usize {
+        self.0
+    }
+
+    /// Returns the local opcode of `Opcode` if this opcode belongs to its class.
+    pub fn local_opcode<Opcode: LocalOpcode>(&self) -> Option<Opcode> {
+        let local = self.0.checked_sub(Opcode::CLASS_OFFSET)?;
+        (local < Opcode::COUNT).then(|| Opcode::from_usize(local))
+    }
+}
+
+impl fmt::Display for VmOpcode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.0)
+    }
+}
+
+/// The number of operands `a, ..., g` of an [Instruction].
+pub const NUM_OPERANDS: usize = 7;
+
+/// An instruction of the VM: a global opcode and up to [NUM_OPERANDS] field element operands
+/// whose meaning depends on the opcode.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Instruction<F> {
+    pub opcode: VmOpcode,
+    pub a: F,
+    pub b: F,
+    pub c: F,
+    pub d: F,
+    pub e: F,
+    pub f: F,
+    pub g: F,
+}
+
+impl<F: FieldAlgebra> Instruction<F> {
+    /// Creates an instruction from its first `N` operands, padding the rest with zeros.
+    pub fn from_usize<const N: usize>(opcode: VmOpcode, operands: [usize; N]) -> Self {
+        const { assert!(N <= NUM_OPERANDS) };
+        let mut operands = operands.map(F::from_canonical_usize).into_iter();
+        let mut next = || operands.next().unwrap_or(F::ZERO);
+        Self { opcode, a: next(), b: next(), c: next(), d: next(), e: next(), f: next(), g: next() }
+    }
+
+    pub fn operands(&self) -> [F; NUM_OPERANDS] {
+        [&self.a, &self.b, &self.c, &self.d, &self.e, &self.f, &self.g].map(|x| x.clone())
+    }
+}
+
+/// An opcode class whose opcodes are numbered from zero and mapped to global opcodes by adding
+/// [LocalOpcode::CLASS_OFFSET].
+pub trait LocalOpcode: Sized {
+    const CLASS_OFFSET: usize;
+    /// The number of opcodes in the class.
+    const COUNT: usize;
+
+    /// Panics if `value >= COUNT`.
+    fn from_usize(value: usize) -> Self;
+
+    fn local_usize(&self) -> usize;
+
+    fn global_opcode(&self) -> VmOpcode {
+        VmOpcode(self.local_usize() + Self::CLASS_OFFSET)
+    }
+}
+
+/// Declares an opcode class: a `#[repr(usize)]` enum whose variants are numbered from zero, with
+/// [LocalOpcode] implemented for the given class offset.
+macro_rules! local_opcode {
+    (
+        $(#[$attr:meta])*
+        $vis:vis enum $name:ident: $offset:literal { $($variant:ident),+ $(,)? }
+    ) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        #[repr(usize)]
+        $vis enum $name {
+            $($variant),+
+        }
+
+        impl $name {
+            /// Every opcode of the class, in the order of their local opcodes.
+            pub const ALL: [Self; <Self as LocalOpcode>::COUNT] = [$(Self::$variant),+];
+        }
+
+        impl LocalOpcode for $name {
+            const CLASS_OFFSET: usize = $offset;
+            const COUNT: usize = [$(stringify!($variant)),+].len();
+
+            fn from_usize(value: usize) -> Self {
+                match Self::ALL.get(value) {
+                    Some(&opcode) => opcode,
+                    None => panic!(concat!("invalid ", stringify!($name), " {}"), value),
+                }
+            }
+
+            fn local_usize(&self) -> usize {
+                *self as usize
+            }
+        }
+    };
+}
+
+local_opcode! {
+    pub enum BaseAluOpcode: 0x200 {
+        ADD,
+        SUB,
+        XOR,
+        OR,
+        AND,
+    }
+}
+
+local_opcode! {
+    pub enum ShiftOpcode: 0x205 {
+        SLL,
+        SRL,
+        SRA,
+    }
+}
+
+local_opcode! {
+    pub enum LessThanOpcode: 0x208 {
+        SLT,
+        SLTU,
+    }
+}
+
+local_opcode! {
+    pub enum Rv32LoadStoreOpcode: 0x210 {
+        LOADW,
+        LOADBU,
+        LOADHU,
+        STOREW,
+        STOREH,
+        STOREB,
+        LOADB,
+        LOADH,
+    }
+}
+
+local_opcode! {
+    pub enum BranchEqualOpcode: 0x220 {
+        BEQ,
+        BNE,
+    }
+}
+
+local_opcode! {
+    pub enum BranchLessThanOpcode: 0x225 {
+        BLT,
+        BLTU,
+        BGE,
+        BGEU,
+    }
+}
+
+local_opcode! {
+    pub enum Rv32JalLuiOpcode: 0x230 {
+        JAL,
+        LUI,
+    }
+}
+
+local_opcode! {
+    pub enum Rv32JalrOpcode: 0x235 {
+        JALR,
+    }
+}
+
+local_opcode! {
+    pub enum Rv32AuipcOpcode: 0x240 {
+        AUIPC,
+    }
+}
+
+local_opcode! {
+    pub enum MulOpcode: 0x250 {
+        MUL,
+    }
+}
+
+local_opcode! {
+    pub enum MulHOpcode: 0x251 {
+        MULH,
+        MULHSU,
+        MULHU,
+    }
+}
+
+local_opcode! {
+    pub enum DivRemOpcode: 0x254 {
+        DIV,
+        DIVU,
+        REM,
+        REMU,
+    }
+}
@@ -0,0 +1,362 @@
+use core::marker::PhantomData;
+
+use crate::{
+    execution::{ExecutionError, ExecutionState, InstructionExecutor},
+    instructions::{Instruction, LocalOpcode},
+    memory::MemoryController,
+    openvm_stark_backend::{
+        air::{Air, BaseAir},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    utils::next_power_of_two_or_zero,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/arch/integration_api.rs
+// for full implementation details.
+
+/// The interface between a core AIR and an adapter AIR: the shapes of the data that the adapter
+/// reads and writes on behalf of the core, and of the instruction fields it passes to it.
+pub trait VmAdapterInterface<T> {
+    /// The memory read data that should be exposed for downstream use.
+    type Reads;
+    /// The memory write data that are expected to be provided by the integrator.
+    type Writes;
+    /// The parts of the instruction that should be exposed to the integrator.
+    type ProcessedInstruction;
+}
+
+/// What a core AIR hands to its adapter AIR.
+pub struct AdapterAirContext<T, I: VmAdapterInterface<T>> {
+    /// Leave as `None` to allow the adapter to decide the `to_pc` automatically.
+    pub to_pc: Option<T>,
+    pub reads: I::Reads,
+    pub writes: I::Writes,
+    pub instruction: I::ProcessedInstruction,
+}
+
+/// The AIR of an adapter: the columns and constraints that connect a core to the rest of the VM,
+/// i.e. the memory accesses, the program lookup and the execution state transition.
+pub trait VmAdapterAir<AB: InteractionBuilder>: BaseAir<AB::F> {
+    type Interface: VmAdapterInterface<AB::Expr>;
+
+    /// Constrains the adapter columns `local` against the context returned by the core.
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local: &[AB::Var],
+        ctx: AdapterAirContext<AB::Expr, Self::Interface>,
+    );
+
+    /// Return the `from_pc` expression.
+    fn get_from_pc(&self, local: &[AB::Var]) -> AB::Var;
+}
+
+pub trait VmCoreAir<AB, I>: BaseAir<AB::F>
+where
+    AB: InteractionBuilder,
+    I: VmAdapterInterface<AB::Expr>,
+{
+    /// Constrains the core columns `local_core` of an instruction executed at `from_pc` and
+    /// returns the interface for the adapter.
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I>;
+
+    /// The offset the opcodes by this core start from. This is usually just `CLASS_OFFSET` of
+    /// the opcode class handled by this core.
+    fn start_offset(&self) -> usize;
+
+    fn start_offset_expr(&self) -> AB::Expr {
+        AB::Expr::from_canonical_usize(self.start_offset())
+    }
+
+    fn expr_to_global_expr(&self, local_expr: impl Into<AB::Expr>) -> AB::Expr {
+        self.start_offset_expr() + local_expr.into()
+    }
+
+    fn opcode_to_global_expr(&self, local_opcode: impl LocalOpcode) -> AB::Expr {
+        self.expr_to_global_expr(AB::Expr::from_canonical_usize(local_opcode.local_usize()))
+    }
+}
+
+/// The AIR of a chip made of an adapter AIR and a core AIR. The main row holds the adapter
+/// columns followed by the core columns.
+#[derive(Clone, Debug)]
+pub struct VmAirWrapper<A, C> {
+    pub adapter: A,
+    pub core: C,
+}
+
+impl<A, C> VmAirWrapper<A, C> {
+    pub fn new(adapter: A, core: C) -> Self {
+        Self { adapter, core }
+    }
+}
+
+impl<T, A: BaseAir<T>, C: BaseAir<T>> BaseAir<T> for VmAirWrapper<A, C> {
+    fn width(&self) -> usize {
+        self.adapter.width() + self.core.width()
+    }
+}
+
+impl<AB, A, C> Air<AB> for VmAirWrapper<A, C>
+where
+    AB: InteractionBuilder,
+    A: VmAdapterAir<AB>,
+    C: VmCoreAir<AB, A::Interface>,
+{
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let (local_adapter, local_core) = local.split_at(self.adapter.width());
+
+        let ctx = self.core.eval(builder, local_core, self.adapter.get_from_pc(local_adapter));
+        self.adapter.eval(builder, local_adapter, ctx);
+    }
+}
+
+/// What a core chip hands to its adapter chip after executing an instruction.
+pub struct AdapterRuntimeContext<T, I: VmAdapterInterface<T>> {
+    /// Leave as `None` to allow the adapter to decide the `to_pc` automatically.
+    pub to_pc: Option<u32>,
+    pub writes: I::Writes,
+}
+
+impl<T, I: VmAdapterInterface<T>> AdapterRuntimeContext<T, I> {
+    /// Leave `to_pc` as `None` to allow the adapter to decide the `to_pc` automatically.
+    pub fn without_pc(writes: impl Into<I::Writes>) -> Self {
+        Self { to_pc: None, writes: writes.into() }
+    }
+}
+
+/// The runtime counterpart of [VmAdapterAir]: performs the memory accesses of an instruction on
+/// behalf of the core and fills the adapter columns.
+pub trait VmAdapterChip {
+    type ReadRecord;
+    type WriteRecord;
+    /// The adapter AIR, usually implementing [VmAdapterAir].
+    type Air: BaseAir<F> + Clone;
+    type Interface: VmAdapterInterface<F>;
+
+    /// Given an instruction, performs the memory reads and returns the data read together with
+    /// the record needed to fill the trace.
+    fn preprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+    ) -> (<Self::Interface as VmAdapterInterface<F>>::Reads, Self::ReadRecord);
+
+    /// Returns the fault that `instruction` raises when executed from `from_state`, without
+    /// accessing memory. Instructions that cannot fault need not override this.
+    fn check(
+        &self,
+        _memory: &MemoryController,
+        _instruction: &Instruction<F>,
+        _from_state: ExecutionState<u32>,
+    ) -> Result<(), ExecutionError> {
+        Ok(())
+    }
+
+    /// Given an instruction and the output of the core, performs the memory writes and returns
+    /// the execution state after the instruction.
+    fn postprocess(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+        output: AdapterRuntimeContext<F, Self::Interface>,
+        read_record: &Self::ReadRecord,
+    ) -> (ExecutionState<u32>, Self::WriteRecord);
+
+    /// Populates `row_slice` with the adapter columns. `row_slice` has exactly the width of the
+    /// adapter AIR.
+    fn generate_trace_row(
+        &self,
+        row_slice: &mut [F],
+        read_record: Self::ReadRecord,
+        write_record: Self::WriteRecord,
+        memory: &MemoryController,
+    );
+
+    fn air(&self) -> &Self::Air;
+}
+
+/// The runtime counterpart of [VmCoreAir]: computes the output of an instruction from the data
+/// read by the adapter and fills the core columns.
+pub trait VmCoreChip<I: VmAdapterInterface<F>> {
+    type Record;
+    /// The core AIR, usually implementing [VmCoreAir].
+    type Air: BaseAir<F> + Clone;
+
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record);
+
+    /// Populates `row_slice` with the core columns. `row_slice` has exactly the width of the core
+    /// AIR.
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record);
+
+    fn air(&self) -> &Self::Air;
+}
+
+/// A chip made of an adapter chip and a core chip, generating the trace of the corresponding
+/// [VmAirWrapper].
+pub struct VmChipWrapper<A: VmAdapterChip, C: VmCoreChip<A::Interface>> {
+    pub adapter: A,
+    pub core: C,
+    records: Vec<(A::ReadRecord, A::WriteRecord, C::Record)>,
+}
+
+impl<A, C> VmChipWrapper<A, C>
+where
+    A: VmAdapterChip,
+    C: VmCoreChip<A::Interface>,
+{
+    pub fn new(adapter: A, core: C) -> Self {
+        Self { adapter, core, records: Vec::new() }
+    }
+
+    pub fn air(&self) -> VmAirWrapper<A::Air, C::Air> {
+        VmAirWrapper::new(self.adapter.air().clone(), self.core.air().clone())
+    }
+
+    pub fn air_width(&self) -> usize {
+        self.adapter.air().width() + self.core.air().width()
+    }
+
+    /// The number of executed instructions that are not yet in a trace.
+    pub fn current_trace_height(&self) -> usize {
+        self.records.len()
+    }
+
+    /// Executes `instruction` from `from_state` and returns the execution state after it.
+    pub fn execute(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> ExecutionState<u32> {
+        let (reads, read_record) = self.adapter.preprocess(memory, instruction);
+        let (output, core_record) =
+            self.core.execute_instruction(instruction, from_state.pc, reads);
+        let (to_state, write_record) =
+            self.adapter.postprocess(memory, instruction, from_state, output, &read_record);
+        self.records.push((read_record, write_record, core_record));
+        to_state
+    }
+
+    /// Generates one row per executed instruction, padded to a power of two with zero rows, and
+    /// clears the records. Filling the memory aux columns requests range checks, so this must be
+    /// called before the range checker trace is generated.
+    pub fn generate_trace(&mut self, memory: &MemoryController) -> RowMajorMatrix<F> {
+        let adapter_width = self.adapter.air().width();
+        let width = self.air_width();
+        let height = next_power_of_two_or_zero(self.records.len());
+        let mut trace = RowMajorMatrix::new(vec![F::ZERO; height * width], width);
+        for (row, (read_record, write_record, core_record)) in
+            trace.rows_mut().zip(core::mem::take(&mut self.records))
+        {
+            let (adapter_row, core_row) = row.split_at_mut(adapter_width);
+            self.adapter.generate_trace_row(adapter_row, read_record, write_record, memory);
+            self.core.generate_trace_row(core_row, core_record);
+        }
+        trace
+    }
+}
+
+impl<A, C> InstructionExecutor for VmChipWrapper<A, C>
+where
+    A: VmAdapterChip,
+    C: VmCoreChip<A::Interface>,
+{
+    fn execute(
+        &mut self,
+        memory: &mut MemoryController,
+        instruction: &Instruction<F>,
+        from_state: ExecutionState<u32>,
+    ) -> Result<ExecutionState<u32>, ExecutionError> {
+        self.adapter.check(memory, instruction, from_state)?;
+        Ok(VmChipWrapper::execute(self, memory, instruction, from_state))
+    }
+}
+
+/// An interface with `NUM_READS` reads of `READ_SIZE` cells and `NUM_WRITES` writes of
+/// `WRITE_SIZE` cells.
+pub struct BasicAdapterInterface<
+    T,
+    PI,
+    const NUM_READS: usize,
+    const NUM_WRITES: usize,
+    const READ_SIZE: usize,
+    const WRITE_SIZE: usize,
+>(PhantomData<T>, PhantomData<PI>);
+
+impl<
+    T,
+    PI,
+    const NUM_READS: usize,
+    const NUM_WRITES: usize,
+    const READ_SIZE: usize,
+    const WRITE_SIZE: usize,
+> VmAdapterInterface<T>
+    for BasicAdapterInterface<T, PI, NUM_READS, NUM_WRITES, READ_SIZE, WRITE_SIZE>
+{
+    type Reads = [[T; READ_SIZE]; NUM_READS];
+    type Writes = [[T; WRITE_SIZE]; NUM_WRITES];
+    type ProcessedInstruction = PI;
+}
+
+#[derive(Clone, Debug)]
+pub struct MinimalInstruction<T> {
+    pub is_valid: T,
+    /// Absolute opcode number
+    pub opcode: T,
+}
+
+#[derive(Clone, Debug)]
+pub struct ImmInstruction<T> {
+    pub is_valid: T,
+    /// Absolute opcode number
+    pub opcode: T,
+    pub immediate: T,
+}
+
+/// An instruction whose immediate is sign-extended from its lower limbs by `imm_sign`.
+#[derive(Clone, Debug)]
+pub struct SignedImmInstruction<T> {
+    pub is_valid: T,
+    /// Absolute opcode number
+    pub opcode: T,
+    pub immediate: T,
+    /// Sign of the immediate (1 if negative, 0 if positive)
+    pub imm_sign: T,
+}
+
+/// A load or store that accesses the aligned memory block holding the effective address, where
+/// the shift amount is the offset of the address within the block.
+#[derive(Clone, Debug)]
+pub struct LoadStoreInstruction<T> {
+    pub is_valid: T,
+    /// Absolute opcode number
+    pub opcode: T,
+    pub is_load: T,
+    /// The shift amount of the read block for loads, and zero for stores
+    pub load_shift_amount: T,
+    /// The shift amount of the written block for stores, and zero for loads
+    pub store_shift_amount: T,
+}
+
+impl<T> From<ImmInstruction<T>> for MinimalInstruction<T> {
+    fn from(instruction: ImmInstruction<T>) -> Self {
+        MinimalInstruction { is_valid: instruction.is_valid, opcode: instruction.opcode }
+    }
+}
@@ -0,0 +1,319 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    adapters::{
+        RV32_MEMORY_AS, RV32_REGISTER_AS, Rv32BaseAluAdapterChip, Rv32BranchAdapterChip,
+        Rv32CondRdWriteAdapterChip, Rv32JalrAdapterChip, Rv32LoadStoreAdapterChip,
+        Rv32MultAdapterChip, Rv32RdWriteAdapterChip, compose, decompose,
+    },
+    base_alu::{Rv32BaseAluChip, Rv32BaseAluCoreChip},
+    bitwise_op_lookup::{BitwiseOperationLookupChip, SharedBitwiseOperationLookupChip},
+    branch_eq::{Rv32BranchEqualChip, Rv32BranchEqualCoreChip},
+    branch_lt::{Rv32BranchLessThanChip, Rv32BranchLessThanCoreChip},
+    core::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS, Rv32AuipcChip, Rv32AuipcCoreChip},
+    divrem::{Rv32DivRemChip, Rv32DivRemCoreChip},
+    execution::{ExecutionBridge, ExecutionError, ExecutionState, InstructionExecutor},
+    instructions::{
+        BaseAluOpcode, BranchEqualOpcode, BranchLessThanOpcode, DivRemOpcode, LessThanOpcode,
+        LocalOpcode, MulHOpcode, MulOpcode, Rv32AuipcOpcode, Rv32JalLuiOpcode, Rv32JalrOpcode,
+        Rv32LoadStoreOpcode, ShiftOpcode, VmOpcode,
+    },
+    integration_api::VmChipWrapper,
+    jal_lui::{Rv32JalLuiChip, Rv32JalLuiCoreChip},
+    jalr::{Rv32JalrChip, Rv32JalrCoreChip},
+    less_than::{Rv32LessThanChip, Rv32LessThanCoreChip},
+    loadstore::{Rv32LoadStoreChip, Rv32LoadStoreCoreChip},
+    memory::{MemoryConfig, MemoryController},
+    mul::{Rv32MultiplicationChip, Rv32MultiplicationCoreChip},
+    mulh::{Rv32MulHChip, Rv32MulHCoreChip},
+    openvm_stark_backend::{
+        debug::{check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::Interaction,
+    },
+    program::{Program, ProgramChip},
+    range_tuple::{RangeTupleCheckerChip, SharedRangeTupleCheckerChip},
+    shift::{Rv32ShiftChip, Rv32ShiftCoreChip},
+    testing::{
+        BITWISE_OP_LOOKUP_BUS, EXECUTION_BUS, MEMORY_BUS, PROGRAM_BUS, RANGE_CHECKER_BUS,
+        RANGE_TUPLE_CHECKER_BUS,
+    },
+    var_range::{SharedVariableRangeCheckerChip, VariableRangeCheckerChip},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/arch/segment.rs
+// for full implementation details.
+
+/// The chips of the RV32IM opcode classes, which keep the records of the instructions they
+/// execute until their traces are generated.
+pub struct Rv32Chips {
+    pub base_alu: Rv32BaseAluChip,
+    pub shift: Rv32ShiftChip,
+    pub less_than: Rv32LessThanChip,
+    pub load_store: Rv32LoadStoreChip,
+    pub branch_eq: Rv32BranchEqualChip,
+    pub branch_lt: Rv32BranchLessThanChip,
+    pub jal_lui: Rv32JalLuiChip,
+    pub jalr: Rv32JalrChip,
+    pub auipc: Rv32AuipcChip,
+    pub mul: Rv32MultiplicationChip,
+    pub mulh: Rv32MulHChip,
+    pub divrem: Rv32DivRemChip,
+}
+
+impl Rv32Chips {
+    pub fn new(
+        memory: &MemoryController,
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    ) -> Self {
+        let memory_bridge = memory.bridge;
+        let execution_bridge = ExecutionBridge::new(EXECUTION_BUS, PROGRAM_BUS);
+        let bitwise = || bitwise_lookup_chip.clone();
+        let alu_adapter =
+            || Rv32BaseAluAdapterChip::new(memory_bridge, execution_bridge, bitwise());
+        let mult_adapter = || Rv32MultAdapterChip::new(memory_bridge, execution_bridge);
+        let branch_adapter = || Rv32BranchAdapterChip::new(memory_bridge, execution_bridge);
+        Self {
+            base_alu: VmChipWrapper::new(alu_adapter(), Rv32BaseAluCoreChip::new(bitwise())),
+            shift: VmChipWrapper::new(
+                alu_adapter(),
+                Rv32ShiftCoreChip::new(bitwise(), memory.range_checker.clone()),
+            ),
+            less_than: VmChipWrapper::new(alu_adapter(), Rv32LessThanCoreChip::new(bitwise())),
+            load_store: VmChipWrapper::new(
+                Rv32LoadStoreAdapterChip::new(
+                    memory_bridge,
+                    execution_bridge,
+                    bitwise(),
+                    memory.config().pointer_max_bits,
+                ),
+                Rv32LoadStoreCoreChip::new(bitwise()),
+            ),
+            branch_eq: VmChipWrapper::new(branch_adapter(), Rv32BranchEqualCoreChip::new()),
+            branch_lt: VmChipWrapper::new(
+                branch_adapter(),
+                Rv32BranchLessThanCoreChip::new(bitwise()),
+            ),
+            jal_lui: VmChipWrapper::new(
+                Rv32CondRdWriteAdapterChip::new(memory_bridge, execution_bridge),
+                Rv32JalLuiCoreChip::new(bitwise()),
+            ),
+            jalr: VmChipWrapper::new(
+                Rv32JalrAdapterChip::new(memory_bridge, execution_bridge),
+                Rv32JalrCoreChip::new(bitwise()),
+            ),
+            auipc: VmChipWrapper::new(
+                Rv32RdWriteAdapterChip::new(memory_bridge, execution_bridge),
+                Rv32AuipcCoreChip::new(bitwise()),
+            ),
+            mul: VmChipWrapper::new(
+                mult_adapter(),
+                Rv32MultiplicationCoreChip::new(range_tuple_chip.clone()),
+            ),
+            mulh: VmChipWrapper::new(
+                mult_adapter(),
+                Rv32MulHCoreChip::new(bitwise(), range_tuple_chip.clone()),
+            ),
+            divrem: VmChipWrapper::new(
+                mult_adapter(),
+                Rv32DivRemCoreChip::new(bitwise(), range_tuple_chip),
+            ),
+        }
+    }
+
+    /// The executor of the opcode class of `opcode`, if it is an RV32IM opcode.
+    pub fn executor(&mut self, opcode: VmOpcode) -> Option<&mut dyn InstructionExecutor> {
+        fn is<Opcode: LocalOpcode>(opcode: VmOpcode) -> bool {
+            opcode.local_opcode::<Opcode>().is_some()
+        }
+        let executor: &mut dyn InstructionExecutor = match opcode {
+            _ if is::<BaseAluOpcode>(opcode) => &mut self.base_alu,
+            _ if is::<ShiftOpcode>(opcode) => &mut self.shift,
+            _ if is::<LessThanOpcode>(opcode) => &mut self.less_than,
+            _ if is::<Rv32LoadStoreOpcode>(opcode) => &mut self.load_store,
+            _ if is::<BranchEqualOpcode>(opcode) => &mut self.branch_eq,
+            _ if is::<BranchLessThanOpcode>(opcode) => &mut self.branch_lt,
+            _ if is::<Rv32JalLuiOpcode>(opcode) => &mut self.jal_lui,
+            _ if is::<Rv32JalrOpcode>(opcode) => &mut self.jalr,
+            _ if is::<Rv32AuipcOpcode>(opcode) => &mut self.auipc,
+            _ if is::<MulOpcode>(opcode) => &mut self.mul,
+            _ if is::<MulHOpcode>(opcode) => &mut self.mulh,
+            _ if is::<DivRemOpcode>(opcode) => &mut self.divrem,
+            _ => return None,
+        };
+        Some(executor)
+    }
+
+    /// Generates the trace of every chip, checks its constraints and returns the interactions.
+    pub fn check_constraints(&mut self, memory: &MemoryController) -> Vec<Interaction<F>> {
+        let mut interactions = Vec::new();
+        interactions
+            .extend(check_constraints(&self.base_alu.air(), &self.base_alu.generate_trace(memory)));
+        interactions
+            .extend(check_constraints(&self.shift.air(), &self.shift.generate_trace(memory)));
+        interactions.extend(check_constraints(
+            &self.less_than.air(),
+            &self.less_than.generate_trace(memory),
+        ));
+        interactions.extend(check_constraints(
+            &self.load_store.air(),
+            &self.load_store.generate_trace(memory),
+        ));
+        interactions.extend(check_constraints(
+            &self.branch_eq.air(),
+            &self.branch_eq.generate_trace(memory),
+        ));
+        interactions.extend(check_constraints(
+            &self.branch_lt.air(),
+            &self.branch_lt.generate_trace(memory),
+        ));
+        interactions
+            .extend(check_constraints(&self.jal_lui.air(), &self.jal_lui.generate_trace(memory)));
+        interactions.extend(check_constraints(&self.jalr.air(), &self.jalr.generate_trace(memory)));
+        interactions
+            .extend(check_constraints(&self.auipc.air(), &self.auipc.generate_trace(memory)));
+        interactions.extend(check_constraints(&self.mul.air(), &self.mul.generate_trace(memory)));
+        interactions.extend(check_constraints(&self.mulh.air(), &self.mulh.generate_trace(memory)));
+        interactions
+            .extend(check_constraints(&self.divrem.air(), &self.divrem.generate_trace(memory)));
+        interactions
+    }
+}
+
+/// A reference RV32IM interpreter: fetches the instructions of a program, dispatches them to
+/// the chips of their opcode classes and keeps the records those chips need for their traces.
+///
+/// The registers and the main memory live in the address spaces of a [MemoryController]. The
+/// program halts when the pc reaches the end of the program.
+pub struct Rv32Interpreter {
+    pub program: ProgramChip,
+    pub memory: MemoryController,
+    pub chips: Rv32Chips,
+    pub range_checker: SharedVariableRangeCheckerChip,
+    pub bitwise_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+    pub range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    initial_state: ExecutionState<u32>,
+    state: ExecutionState<u32>,
+}
+
+impl Rv32Interpreter {
+    /// Starts `program` at `pc_start` with zeroed registers and memory.
+    pub fn new(program: Program<F>, pc_start: u32, config: MemoryConfig) -> Self {
+        let range_checker = Arc::new(VariableRangeCheckerChip::new(RANGE_CHECKER_BUS));
+        let bitwise_chip = Arc::new(BitwiseOperationLookupChip::new(BITWISE_OP_LOOKUP_BUS));
+        let range_tuple_chip = Arc::new(RangeTupleCheckerChip::new(RANGE_TUPLE_CHECKER_BUS));
+        let memory = MemoryController::new(MEMORY_BUS, config, range_checker.clone());
+        let chips = Rv32Chips::new(&memory, bitwise_chip.clone(), range_tuple_chip.clone());
+        let state = ExecutionState::new(pc_start, memory.timestamp());
+        Self {
+            program: ProgramChip::new(PROGRAM_BUS, program),
+            memory,
+            chips,
+            range_checker,
+            bitwise_chip,
+            range_tuple_chip,
+            initial_state: state,
+            state,
+        }
+    }
+
+    /// The execution state of the next instruction.
+    pub fn state(&self) -> ExecutionState<u32> {
+        self.state
+    }
+
+    pub fn is_halted(&self) -> bool {
+        let program = self.program.program();
+        self.state.pc == program.pc(program.len())
+    }
+
+    /// Sets the initial value of register `reg`. Must be called before the first step.
+    pub fn set_register(&mut self, reg: usize, value: u32) {
+        let pointer = (reg * RV32_REGISTER_NUM_LIMBS) as u32;
+        self.memory.set_initial(RV32_REGISTER_AS, pointer, decompose(value));
+    }
+
+    /// Returns the current value of register `reg` without accessing it.
+    pub fn register(&self, reg: usize) -> u32 {
+        compose(&self.memory.unsafe_read(RV32_REGISTER_AS, (reg * RV32_REGISTER_NUM_LIMBS) as u32))
+    }
+
+    /// Sets the initial main memory to the words of `image` by address, e.g. the memory image of
+    /// an ELF. Must be called before the first step.
+    pub fn set_memory_image(&mut self, image: &BTreeMap<u32, u32>) {
+        for (&pointer, &word) in image {
+            self.memory.set_initial(RV32_MEMORY_AS, pointer, decompose(word));
+        }
+    }
+
+    /// Returns the current word of main memory at the aligned `pointer` without accessing it.
+    pub fn memory_word(&self, pointer: u32) -> u32 {
+        compose(&self.memory.unsafe_read(RV32_MEMORY_AS, pointer))
+    }
+
+    /// Executes the instruction at the current pc. On a fault, the execution state stays at the
+    /// faulting instruction, and the run can no longer be verified.
+    pub fn step(&mut self) -> Result<(), ExecutionError> {
+        let pc = self.state.pc;
+        let program = self.program.program();
+        let not_found =
+            ExecutionError::PcNotFound { pc, pc_base: program.pc_base, program_len: program.len() };
+        let instruction = self.program.get_instruction(pc).ok_or(not_found)?.clone();
+        let executor = self
+            .chips
+            .executor(instruction.opcode)
+            .ok_or(ExecutionError::DisabledOperation { pc, opcode: instruction.opcode })?;
+        let to_state = executor.execute(&mut self.memory, &instruction, self.state)?;
+        self.state = to_state;
+        Ok(())
+    }
+
+    /// Steps until the program halts and returns the number of executed instructions.
+    pub fn run(&mut self, max_steps: usize) -> Result<usize, ExecutionError> {
+        let mut steps = 0;
+        while !self.is_halted() {
+            if steps == max_steps {
+                return Err(ExecutionError::StepLimit { max_steps });
+            }
+            self.step()?;
+            steps += 1;
+        }
+        Ok(steps)
+    }
+
+    /// Checks the constraints of every chip, and that all buses are balanced with the execution
+    /// bus closed by the initial and the current execution states.
+    pub fn verify(mut self) {
+        let mut interactions = self.chips.check_constraints(&self.memory);
+        let program_trace = self.program.generate_trace();
+        interactions.extend(check_constraints(&self.program.air, &program_trace));
+        for (state, count) in [(self.initial_state, F::ONE), (self.state, -F::ONE)] {
+            interactions.push(Interaction {
+                message: vec![
+                    F::from_canonical_u32(state.pc),
+                    F::from_canonical_u32(state.timestamp),
+                ],
+                count,
+                bus_index: EXECUTION_BUS.inner.index,
+                count_weight: 1,
+            });
+        }
+        interactions.extend(check_constraints(
+            &self.memory.boundary_air,
+            &self.memory.generate_boundary_trace(),
+        ));
+        interactions
+            .extend(check_constraints(&self.bitwise_chip.air, &self.bitwise_chip.generate_trace()));
+        interactions.extend(check_constraints(
+            &self.range_tuple_chip.air,
+            &self.range_tuple_chip.generate_trace(),
+        ));
+        interactions.extend(check_constraints(
+            &self.range_checker.air,
+            &self.range_checker.generate_trace(),
+        ));
+        verify_interactions(interactions);
+    }
+}
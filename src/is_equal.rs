@@ -0,0 +1,60 @@
+use crate::{
+    is_zero::{IsZeroIo, IsZeroSubAir},
+    openvm_stark_backend::{air::AirBuilder, field::F},
+    sub_air::{SubAir, TraceSubRowGenerator},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/is_equal/mod.rs
+// for full implementation details.
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsEqualIo<T> {
+    pub x: T,
+    pub y: T,
+    /// The boolean output, constrained to equal (x == y) when `condition != 0`.
+    pub out: T,
+    /// Constraints are only imposed when `condition != 0`.
+    pub condition: T,
+}
+
+impl<T> IsEqualIo<T> {
+    pub fn new(x: T, y: T, out: T, condition: T) -> Self {
+        Self { x, y, out, condition }
+    }
+}
+
+/// An Air that constrains `out = (x == y)` by checking `x - y` with an [IsZeroSubAir]. The
+/// auxiliary column `inv` holds the inverse of `x - y` when it is non-zero.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IsEqSubAir;
+
+impl<AB: AirBuilder> SubAir<AB> for IsEqSubAir {
+    /// `(io, inv)`
+    type AirContext<'a>
+        = (IsEqualIo<AB::Expr>, AB::Var)
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(&'a self, builder: &'a mut AB, (io, inv): (IsEqualIo<AB::Expr>, AB::Var))
+    where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        let is_zero_io = IsZeroIo::new(io.x - io.y, io.out, io.condition);
+        IsZeroSubAir.eval(builder, (is_zero_io, inv));
+    }
+}
+
+impl TraceSubRowGenerator<F> for IsEqSubAir {
+    /// `(x, y)`
+    type TraceContext<'a> = (F, F);
+    /// `(inv, out)`
+    type ColsMut<'a> = (&'a mut F, &'a mut F);
+
+    fn generate_subrow<'a>(&'a self, (x, y): (F, F), cols: (&'a mut F, &'a mut F)) {
+        IsZeroSubAir.generate_subrow(x - y, cols);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    aligned_borrow,
+    openvm_stark_backend::{
+        air::AirBuilder,
+        field::{F, Field, FieldAlgebra},
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/is_equal_array/mod.rs
+// for full implementation details.
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsEqArrayIo<T, const NUM: usize> {
+    pub x: [T; NUM],
+    pub y: [T; NUM],
+    /// The boolean output, constrained to equal (x == y) when `condition != 0`.
+    pub out: T,
+    /// Constraints are only imposed when `condition != 0`.
+    pub condition: T,
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct IsEqArrayAuxCols<T, const NUM: usize> {
+        // `diff_inv_marker` is filled with 0 except at the lowest index i such that `x[i] != y[i]`,
+        // where it holds `inv(x[i] - y[i])`.
+        pub diff_inv_marker: [T; NUM],
+    }
+}
+
+/// An Air that constrains `out = (x == y)` for arrays `x` and `y`.
+///
+/// The constraints are `out * (x[i] - y[i]) = 0` for every `i`, which forces `out = 0` when the
+/// arrays differ, and `out + sum_i (x[i] - y[i]) * diff_inv_marker[i] = 1`, which forces `out = 1`
+/// when they are equal and can only be satisfied by a non-zero difference otherwise.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IsEqArraySubAir<const NUM: usize>;
+
+impl<AB: AirBuilder, const NUM: usize> SubAir<AB> for IsEqArraySubAir<NUM> {
+    /// `(io, diff_inv_marker)`
+    type AirContext<'a>
+        = (IsEqArrayIo<AB::Expr, NUM>, [AB::Var; NUM])
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(
+        &'a self,
+        builder: &'a mut AB,
+        (io, diff_inv_marker): (IsEqArrayIo<AB::Expr, NUM>, [AB::Var; NUM]),
+    ) where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        let mut sum = io.out.clone();
+        for ((x_i, y_i), inv_marker_i) in io.x.into_iter().zip(io.y).zip(diff_inv_marker) {
+            let diff = x_i - y_i;
+            sum += diff.clone() * inv_marker_i;
+            builder.when(io.condition.clone()).assert_zero(io.out.clone() * diff);
+        }
+        builder.when(io.condition).assert_one(sum);
+    }
+}
+
+impl<const NUM: usize> TraceSubRowGenerator<F> for IsEqArraySubAir<NUM> {
+    /// `(x, y)`
+    type TraceContext<'a> = (&'a [F; NUM], &'a [F; NUM]);
+    /// `(diff_inv_marker, out)`
+    type ColsMut<'a> = (&'a mut [F; NUM], &'a mut F);
+
+    fn generate_subrow<'a>(
+        &'a self,
+        (x, y): (&'a [F; NUM], &'a [F; NUM]),
+        (diff_inv_marker, out): (&'a mut [F; NUM], &'a mut F),
+    ) {
+        diff_inv_marker.fill(F::ZERO);
+        match x.iter().zip(y).position(|(x_i, y_i)| x_i != y_i) {
+            Some(i) => {
+                diff_inv_marker[i] = (x[i] - y[i]).inverse();
+                *out = F::ZERO;
+            }
+            None => *out = F::ONE,
+        }
+    }
+}
@@ -0,0 +1,207 @@
+use crate::{
+    aligned_borrow,
+    bus::VariableRangeCheckerBus,
+    openvm_stark_backend::{
+        air::AirBuilder,
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+    var_range::VariableRangeCheckerChip,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/is_less_than/mod.rs
+// for full implementation details.
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsLessThanIo<T> {
+    pub x: T,
+    pub y: T,
+    /// Will be constrained to be boolean. When `count != 0`, `out = 1` iff `x < y`.
+    pub out: T,
+    /// Range checks are done with multiplicity `count`.
+    /// If `count == 0` then no range checks are done, and the other constraints are not imposed
+    /// either since the limbs they rely on are unchecked.
+    /// `count` **must** be boolean.
+    pub count: T,
+}
+
+impl<T> IsLessThanIo<T> {
+    pub fn new(x: impl Into<T>, y: impl Into<T>, out: impl Into<T>, count: impl Into<T>) -> Self {
+        Self { x: x.into(), y: y.into(), out: out.into(), count: count.into() }
+    }
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct LessThanAuxCols<T, const AUX_LEN: usize> {
+        // lower_decomp consists of lower decomposed into limbs of size bus.range_max_bits
+        // note: the final limb might have less than bus.range_max_bits bits
+        pub lower_decomp: [T; AUX_LEN],
+    }
+}
+
+/// This is intended for use as a **SubAir**, not as a standalone Air.
+///
+/// This SubAir constrains that `x < y` when `out = 1` and `x >= y` when `out = 0`, assuming that
+/// `x, y < 2^max_bits`. **The caller is responsible for range checking `x` and `y`.**
+///
+/// The constraints work by computing `y - x - 1 + 2^max_bits`, which lies in `[0, 2^max_bits)` iff
+/// `x >= y` and in `[2^max_bits, 2^(max_bits + 1) - 1)` iff `x < y`. The lower `max_bits` bits are
+/// decomposed into limbs that are range checked through the [VariableRangeCheckerBus], and `out` is
+/// the bit above them.
+#[derive(Clone, Copy, Debug)]
+pub struct IsLtSubAir {
+    pub bus: VariableRangeCheckerBus,
+    /// The maximum number of bits for the numbers to compare.
+    /// Soundness requirement: `max_bits <= 29` so that `2^(max_bits + 1)` does not overflow the
+    /// BabyBear field.
+    pub max_bits: usize,
+    /// `decomp_limbs = max_bits.div_ceil(bus.range_max_bits)`.
+    pub decomp_limbs: usize,
+}
+
+impl IsLtSubAir {
+    pub fn new(bus: VariableRangeCheckerBus, max_bits: usize) -> Self {
+        assert!(max_bits <= 29, "max_bits {max_bits} is too large for the BabyBear field");
+        let decomp_limbs = max_bits.div_ceil(bus.range_max_bits);
+        Self { bus, max_bits, decomp_limbs }
+    }
+
+    pub fn range_max_bits(&self) -> usize {
+        self.bus.range_max_bits
+    }
+
+    pub fn when_transition(self) -> IsLtWhenTransitionAir {
+        IsLtWhenTransitionAir(self)
+    }
+
+    /// Constrains `out` and `lower_decomp` against `y - x` when `condition != 0`, without any range
+    /// checks. The caller must call [IsLtSubAir::eval_range_checks] for the constraints to be
+    /// sound.
+    pub fn eval_without_range_checks<AB: AirBuilder>(
+        &self,
+        builder: &mut AB,
+        y_minus_x: impl Into<AB::Expr>,
+        out: impl Into<AB::Expr>,
+        condition: impl Into<AB::Expr>,
+        lower_decomp: &[AB::Var],
+    ) {
+        assert_eq!(lower_decomp.len(), self.decomp_limbs);
+        let out = out.into();
+        builder.assert_bool(out.clone());
+        // The desired intermediate value, i.e. `2^max_bits + y - x - 1`.
+        let intermed_val =
+            y_minus_x.into() + AB::Expr::from_canonical_usize((1 << self.max_bits) - 1);
+        // Each limb of `lower_decomp` is range checked, so `lower < 2^max_bits`.
+        let lower = lower_decomp.iter().enumerate().fold(AB::Expr::ZERO, |acc, (i, &limb)| {
+            acc + limb * AB::Expr::from_canonical_usize(1 << (i * self.range_max_bits()))
+        });
+        // `lower + out * 2^max_bits` is the correct intermediate sum.
+        let check_val = lower + out * AB::Expr::from_canonical_usize(1 << self.max_bits);
+        builder.when(condition).assert_eq(intermed_val, check_val);
+    }
+
+    /// Range checks each limb of `lower_decomp` with multiplicity `count`. The last limb is only
+    /// checked against the bits of `max_bits` it covers.
+    pub fn eval_range_checks<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        lower_decomp: &[AB::Var],
+        count: impl Into<AB::Expr>,
+    ) {
+        let count = count.into();
+        let mut bits_remaining = self.max_bits;
+        for &limb in lower_decomp {
+            let range_bits = bits_remaining.min(self.range_max_bits());
+            self.bus.range_check(limb, range_bits).eval(builder, count.clone());
+            bits_remaining = bits_remaining.saturating_sub(self.range_max_bits());
+        }
+    }
+}
+
+impl<AB: InteractionBuilder> SubAir<AB> for IsLtSubAir {
+    /// `(io, lower_decomp)`
+    type AirContext<'a>
+        = (IsLessThanIo<AB::Expr>, &'a [AB::Var])
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(
+        &'a self,
+        builder: &'a mut AB,
+        (io, lower_decomp): (IsLessThanIo<AB::Expr>, &'a [AB::Var]),
+    ) where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        let count = io.count;
+        self.eval_without_range_checks(builder, io.y - io.x, io.out, count.clone(), lower_decomp);
+        self.eval_range_checks(builder, lower_decomp, count);
+    }
+}
+
+/// The same subair as [IsLtSubAir] except that the non-range check constraints are only imposed
+/// on transitions, i.e. not on the last row. The range checks are always imposed, so on the last
+/// row they are performed with the `count` given by the caller even though nothing else is.
+#[derive(Clone, Copy, Debug)]
+pub struct IsLtWhenTransitionAir(pub IsLtSubAir);
+
+impl<AB: InteractionBuilder> SubAir<AB> for IsLtWhenTransitionAir {
+    /// `(io, lower_decomp)`
+    type AirContext<'a>
+        = (IsLessThanIo<AB::Expr>, &'a [AB::Var])
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(
+        &'a self,
+        builder: &'a mut AB,
+        (io, lower_decomp): (IsLessThanIo<AB::Expr>, &'a [AB::Var]),
+    ) where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        let count = io.count;
+        self.0.eval_without_range_checks(
+            &mut builder.when_transition(),
+            io.y - io.x,
+            io.out,
+            count.clone(),
+            lower_decomp,
+        );
+        self.0.eval_range_checks(builder, lower_decomp, count);
+    }
+}
+
+impl TraceSubRowGenerator<F> for IsLtSubAir {
+    /// `(range_checker, x, y)`
+    type TraceContext<'a> = (&'a VariableRangeCheckerChip, u32, u32);
+    /// `(lower_decomp, out)`
+    type ColsMut<'a> = (&'a mut [F], &'a mut F);
+
+    /// Fills `lower_decomp` and `out` for `x < y`, recording the range checks of the limbs in the
+    /// range checker.
+    fn generate_subrow<'a>(
+        &'a self,
+        (range_checker, x, y): (&'a VariableRangeCheckerChip, u32, u32),
+        (lower_decomp, out): (&'a mut [F], &'a mut F),
+    ) {
+        assert!(x < (1 << self.max_bits), "x {x} out of range for {} bits", self.max_bits);
+        assert!(y < (1 << self.max_bits), "y {y} out of range for {} bits", self.max_bits);
+        assert_eq!(lower_decomp.len(), self.decomp_limbs);
+        *out = F::from_bool(x < y);
+
+        // The lower `max_bits` bits of `2^max_bits + y - x - 1`.
+        let check_less_than = (1 << self.max_bits) + y - x - 1;
+        let lower_u32 = check_less_than & ((1 << self.max_bits) - 1);
+        range_checker
+            .decompose(lower_u32, self.max_bits, lower_decomp)
+            .expect("lower_decomp has decomp_limbs limbs");
+    }
+}
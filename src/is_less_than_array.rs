@@ -0,0 +1,250 @@
+use crate::{
+    aligned_borrow,
+    bus::VariableRangeCheckerBus,
+    is_less_than::{IsLtSubAir, LessThanAuxCols},
+    openvm_stark_backend::{
+        air::AirBuilder,
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+    utils::not,
+    var_range::VariableRangeCheckerChip,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/is_less_than_array/mod.rs
+// for full implementation details.
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsLtArrayIo<T, const NUM: usize> {
+    pub x: [T; NUM],
+    pub y: [T; NUM],
+    /// The boolean output, constrained to equal (x < y) when `count != 0`. The less than
+    /// comparison is done lexicographically, with the first element being the most significant.
+    pub out: T,
+    /// Range checks are done with multiplicity `count`.
+    /// If `count == 0` then no range checks are done, and the other constraints are not imposed
+    /// either since the limbs they rely on are unchecked.
+    /// `count` **must** be boolean.
+    pub count: T,
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct IsLtArrayAuxCols<T, const NUM: usize, const AUX_LEN: usize> {
+        // `diff_marker` is filled with 0 except at the lowest index i such that `x[i] != y[i]`. If
+        // such an `i` exists then it is constrained that `diff_inv = inv(y[i] - x[i])`.
+        pub diff_marker: [T; NUM],
+        pub diff_inv: T,
+        pub lt_aux: LessThanAuxCols<T, AUX_LEN>,
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsLtArrayAuxColsRef<'a, T> {
+    pub diff_marker: &'a [T],
+    pub diff_inv: &'a T,
+    pub lt_decomp: &'a [T],
+}
+
+#[derive(Debug)]
+pub struct IsLtArrayAuxColsMut<'a, T> {
+    pub diff_marker: &'a mut [T],
+    pub diff_inv: &'a mut T,
+    pub lt_decomp: &'a mut [T],
+}
+
+impl<'a, T, const NUM: usize, const AUX_LEN: usize> From<&'a IsLtArrayAuxCols<T, NUM, AUX_LEN>>
+    for IsLtArrayAuxColsRef<'a, T>
+{
+    fn from(value: &'a IsLtArrayAuxCols<T, NUM, AUX_LEN>) -> Self {
+        Self {
+            diff_marker: &value.diff_marker,
+            diff_inv: &value.diff_inv,
+            lt_decomp: &value.lt_aux.lower_decomp,
+        }
+    }
+}
+
+impl<'a, T, const NUM: usize, const AUX_LEN: usize> From<&'a mut IsLtArrayAuxCols<T, NUM, AUX_LEN>>
+    for IsLtArrayAuxColsMut<'a, T>
+{
+    fn from(value: &'a mut IsLtArrayAuxCols<T, NUM, AUX_LEN>) -> Self {
+        Self {
+            diff_marker: &mut value.diff_marker,
+            diff_inv: &mut value.diff_inv,
+            lt_decomp: &mut value.lt_aux.lower_decomp,
+        }
+    }
+}
+
+/// This is intended for use as a **SubAir**, not as a standalone Air.
+///
+/// This SubAir constrains that `x < y` lexicographically when `out = 1` and `x >= y` when
+/// `out = 0`, assuming that all elements of `x` and `y` are less than `2^max_bits`. **The caller is
+/// responsible for range checking `x` and `y`.**
+///
+/// The most significant differing element is selected with the one-hot `diff_marker`, and its
+/// difference is compared with an [IsLtSubAir].
+#[derive(Clone, Copy, Debug)]
+pub struct IsLtArraySubAir<const NUM: usize> {
+    pub lt: IsLtSubAir,
+}
+
+impl<const NUM: usize> IsLtArraySubAir<NUM> {
+    pub fn new(bus: VariableRangeCheckerBus, max_bits: usize) -> Self {
+        Self { lt: IsLtSubAir::new(bus, max_bits) }
+    }
+
+    pub fn when_transition(self) -> IsLtArrayWhenTransitionAir<NUM> {
+        IsLtArrayWhenTransitionAir(self)
+    }
+
+    pub fn max_bits(&self) -> usize {
+        self.lt.max_bits
+    }
+
+    pub fn range_max_bits(&self) -> usize {
+        self.lt.range_max_bits()
+    }
+
+    /// Constrains `out` against `x` and `y` when `io.count != 0`, without any range checks. The
+    /// caller must call [IsLtArraySubAir::eval_range_checks] for the constraints to be sound.
+    pub fn eval_without_range_checks<AB: AirBuilder>(
+        &self,
+        builder: &mut AB,
+        io: IsLtArrayIo<AB::Expr, NUM>,
+        aux: IsLtArrayAuxColsRef<'_, AB::Var>,
+    ) {
+        assert_eq!(aux.diff_marker.len(), NUM);
+        let condition = io.count;
+        let mut prefix_sum = AB::Expr::ZERO;
+        let mut diff_val = AB::Expr::ZERO;
+        for (i, (x, y)) in io.x.into_iter().zip(io.y).enumerate() {
+            let diff = y - x;
+            let marker = aux.diff_marker[i];
+            builder.assert_bool(marker);
+            prefix_sum += marker.into();
+            // All elements before the marker are equal.
+            builder
+                .when(condition.clone())
+                .assert_zero(not::<AB::Expr>(prefix_sum.clone()) * diff.clone());
+            diff_val += diff * marker;
+        }
+        // At most one element is marked, and the marked difference is non-zero.
+        builder.assert_bool(prefix_sum.clone());
+        builder
+            .when(condition.clone())
+            .when(prefix_sum)
+            .assert_one(diff_val.clone() * *aux.diff_inv);
+        // When the arrays are equal `diff_val = 0`, which forces `out = 0`.
+        self.lt.eval_without_range_checks(builder, diff_val, io.out, condition, aux.lt_decomp);
+    }
+
+    pub fn eval_range_checks<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        lt_decomp: &[AB::Var],
+        count: impl Into<AB::Expr>,
+    ) {
+        self.lt.eval_range_checks(builder, lt_decomp, count);
+    }
+}
+
+impl<AB: InteractionBuilder, const NUM: usize> SubAir<AB> for IsLtArraySubAir<NUM> {
+    /// `(io, aux)`
+    type AirContext<'a>
+        = (IsLtArrayIo<AB::Expr, NUM>, IsLtArrayAuxColsRef<'a, AB::Var>)
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(
+        &'a self,
+        builder: &'a mut AB,
+        (io, aux): (IsLtArrayIo<AB::Expr, NUM>, IsLtArrayAuxColsRef<'a, AB::Var>),
+    ) where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        let count = io.count.clone();
+        let lt_decomp = aux.lt_decomp;
+        self.eval_without_range_checks(builder, io, aux);
+        self.eval_range_checks(builder, lt_decomp, count);
+    }
+}
+
+/// The same subair as [IsLtArraySubAir] except that the non-range check constraints are only
+/// imposed on transitions, i.e. not on the last row.
+///
+/// The range checks are still imposed on the last row with the `count` given by the caller. When
+/// `next` wraps around to the first row, the caller's `count` is usually derived from the first
+/// row, so the trace generator must still fill the last row's `lt_decomp` with in-range limbs and
+/// record their range checks.
+#[derive(Clone, Copy, Debug)]
+pub struct IsLtArrayWhenTransitionAir<const NUM: usize>(pub IsLtArraySubAir<NUM>);
+
+impl<AB: InteractionBuilder, const NUM: usize> SubAir<AB> for IsLtArrayWhenTransitionAir<NUM> {
+    /// `(io, aux)`
+    type AirContext<'a>
+        = (IsLtArrayIo<AB::Expr, NUM>, IsLtArrayAuxColsRef<'a, AB::Var>)
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(
+        &'a self,
+        builder: &'a mut AB,
+        (io, aux): (IsLtArrayIo<AB::Expr, NUM>, IsLtArrayAuxColsRef<'a, AB::Var>),
+    ) where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        let count = io.count.clone();
+        let lt_decomp = aux.lt_decomp;
+        self.0.eval_without_range_checks(&mut builder.when_transition(), io, aux);
+        self.0.eval_range_checks(builder, lt_decomp, count);
+    }
+}
+
+impl<const NUM: usize> TraceSubRowGenerator<F> for IsLtArraySubAir<NUM> {
+    /// `(range_checker, x, y)`
+    type TraceContext<'a> = (&'a VariableRangeCheckerChip, &'a [F], &'a [F]);
+    /// `(aux, out)`
+    type ColsMut<'a> = (IsLtArrayAuxColsMut<'a, F>, &'a mut F);
+
+    /// Fills the aux columns and `out` for `x < y`, recording the range checks of the limbs in the
+    /// range checker.
+    fn generate_subrow<'a>(
+        &'a self,
+        (range_checker, x, y): (&'a VariableRangeCheckerChip, &'a [F], &'a [F]),
+        (aux, out): (IsLtArrayAuxColsMut<'a, F>, &'a mut F),
+    ) {
+        assert_eq!(x.len(), NUM);
+        assert_eq!(y.len(), NUM);
+        let mut is_eq = true;
+        let mut diff_val = F::ZERO;
+        *aux.diff_inv = F::ZERO;
+        for ((x_i, y_i), diff_marker) in x.iter().zip(y).zip(aux.diff_marker.iter_mut()) {
+            if x_i != y_i && is_eq {
+                is_eq = false;
+                *diff_marker = F::ONE;
+                diff_val = *y_i - *x_i;
+                *aux.diff_inv = diff_val.inverse();
+            } else {
+                *diff_marker = F::ZERO;
+            }
+        }
+        // `diff_val` can be "negative" but `shifted_diff` is in `[0, 2^(max_bits + 1))`.
+        let shifted_diff =
+            (diff_val + F::from_canonical_u32((1 << self.max_bits()) - 1)).as_canonical_u32();
+        let lower_u32 = shifted_diff & ((1 << self.max_bits()) - 1);
+        *out = F::from_bool(shifted_diff != lower_u32);
+        range_checker
+            .decompose(lower_u32, self.max_bits(), aux.lt_decomp)
+            .expect("lt_decomp has decomp_limbs limbs");
+    }
+}
@@ -0,0 +1,65 @@
+use crate::{
+    openvm_stark_backend::{
+        air::AirBuilder,
+        field::{F, Field, FieldAlgebra},
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/is_zero/mod.rs
+// for full implementation details.
+
+#[derive(Clone, Copy, Debug)]
+pub struct IsZeroIo<T> {
+    pub x: T,
+    /// The boolean output, constrained to equal (x == 0) when `condition != 0`.
+    pub out: T,
+    /// Constraints are only imposed when `condition != 0`.
+    pub condition: T,
+}
+
+impl<T> IsZeroIo<T> {
+    pub fn new(x: T, out: T, condition: T) -> Self {
+        Self { x, out, condition }
+    }
+}
+
+/// An Air that constrains `out = (x == 0)`, using an auxiliary column `inv` that holds the
+/// inverse of `x` when it is non-zero.
+///
+/// The constraints `x * out = 0` and `out + x * inv = 1` force `out = 1` when `x = 0`, and
+/// `out = 0` when `x != 0`, so `out` is boolean without a separate constraint.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IsZeroSubAir;
+
+impl<AB: AirBuilder> SubAir<AB> for IsZeroSubAir {
+    /// `(io, inv)`
+    type AirContext<'a>
+        = (IsZeroIo<AB::Expr>, AB::Var)
+    where
+        AB::Expr: 'a,
+        AB::Var: 'a,
+        AB: 'a;
+
+    fn eval<'a>(&'a self, builder: &'a mut AB, (io, inv): (IsZeroIo<AB::Expr>, AB::Var))
+    where
+        AB::Var: 'a,
+        AB::Expr: 'a,
+    {
+        builder.when(io.condition.clone()).assert_zero(io.x.clone() * io.out.clone());
+        builder.when(io.condition).assert_one(io.out + io.x * inv);
+    }
+}
+
+impl TraceSubRowGenerator<F> for IsZeroSubAir {
+    /// `x`
+    type TraceContext<'a> = F;
+    /// `(inv, out)`
+    type ColsMut<'a> = (&'a mut F, &'a mut F);
+
+    fn generate_subrow<'a>(&'a self, x: F, (inv, out): (&'a mut F, &'a mut F)) {
+        *out = F::from_bool(x.is_zero());
+        *inv = x.try_inverse().unwrap_or(F::ZERO);
+    }
+}
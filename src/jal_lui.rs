@@ -0,0 +1,254 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32CondRdWriteAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{PC_BITS, RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    execution::DEFAULT_PC_STEP,
+    instructions::{
+        Instruction, LocalOpcode,
+        Rv32JalLuiOpcode::{self, JAL, LUI},
+    },
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, ImmInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/jal_lui/core.rs
+// for full implementation details.
+
+/// The number of bits of the signed JAL offset.
+pub const RV_J_TYPE_IMM_BITS: usize = 21;
+/// The number of bits of the LUI immediate, i.e. of the upper bits of `rd`.
+pub const RV_U_TYPE_IMM_BITS: usize = 20;
+
+pub type Rv32JalLuiChip = VmChipWrapper<Rv32CondRdWriteAdapterChip, Rv32JalLuiCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32JalLuiCoreCols<T> {
+        // The signed offset for JAL, the upper 20 bits of rd for LUI
+        pub imm: T,
+        pub rd_data: [T; RV32_REGISTER_NUM_LIMBS],
+        pub is_jal: T,
+        pub is_lui: T,
+    }
+}
+
+/// Constrains `rd = from_pc + 4, to_pc = from_pc + imm` for JAL and
+/// `rd = imm << 12, to_pc = from_pc + 4` for LUI.
+#[derive(Clone, Copy)]
+pub struct Rv32JalLuiCoreAir {
+    pub bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+}
+
+impl BaseAir<F> for Rv32JalLuiCoreAir {
+    fn width(&self) -> usize {
+        Rv32JalLuiCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32JalLuiCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; 0]; 0]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<ImmInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32JalLuiCoreCols<AB::Var> = local_core.borrow();
+        let Rv32JalLuiCoreCols { imm, rd_data: rd, is_jal, is_lui } = *cols;
+
+        builder.assert_bool(is_lui);
+        builder.assert_bool(is_jal);
+        let is_valid = is_lui + is_jal;
+        builder.assert_bool(is_valid.clone());
+
+        // The least significant limb of rd is zero for LUI, and the other limbs hold imm << 4.
+        builder.when(is_lui).assert_zero(rd[0]);
+        let intermed_val = rd.iter().skip(1).enumerate().fold(AB::Expr::ZERO, |acc, (i, &val)| {
+            acc + val * AB::F::from_canonical_u32(1 << (i * RV32_CELL_BITS))
+        });
+        builder.when(is_lui).assert_eq(
+            intermed_val.clone(),
+            imm * AB::F::from_canonical_u32(1 << (12 - RV32_CELL_BITS)),
+        );
+
+        let intermed_val = rd[0] + intermed_val * AB::F::from_canonical_u32(1 << RV32_CELL_BITS);
+        builder
+            .when(is_jal)
+            .assert_eq(intermed_val, from_pc + AB::F::from_canonical_u32(DEFAULT_PC_STEP));
+
+        // Range checking of rd_data entries to RV32_CELL_BITS bits
+        for i in 0..(RV32_REGISTER_NUM_LIMBS / 2) {
+            self.bus.send_range(rd[i * 2], rd[i * 2 + 1]).eval(builder, is_valid.clone());
+        }
+
+        // For JAL, rd = from_pc + 4 fits in PC_BITS bits: the most significant limb has its
+        // upper bits unset, so XOR-ing them in is the same as adding them.
+        let last_limb_bits = PC_BITS - RV32_CELL_BITS * (RV32_REGISTER_NUM_LIMBS - 1);
+        let additional_bits = (last_limb_bits..RV32_CELL_BITS).fold(0, |acc, x| acc + (1 << x));
+        let additional_bits = AB::F::from_canonical_u32(additional_bits);
+        self.bus
+            .send_xor::<AB::Expr>(
+                rd[RV32_REGISTER_NUM_LIMBS - 1],
+                additional_bits,
+                rd[RV32_REGISTER_NUM_LIMBS - 1] + additional_bits,
+            )
+            .eval(builder, is_jal);
+
+        let to_pc = from_pc + is_lui * AB::F::from_canonical_u32(DEFAULT_PC_STEP) + is_jal * imm;
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            is_lui * AB::F::from_canonical_usize(LUI.local_usize())
+                + is_jal * AB::F::from_canonical_usize(JAL.local_usize()),
+        );
+
+        AdapterAirContext {
+            to_pc: Some(to_pc),
+            reads: [].into(),
+            writes: [rd.map(Into::into)].into(),
+            instruction: ImmInstruction {
+                is_valid,
+                opcode: expected_opcode,
+                immediate: imm.into(),
+            }
+            .into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        Rv32JalLuiOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32JalLuiCoreRecord<F> {
+    pub imm: F,
+    pub rd_data: [F; RV32_REGISTER_NUM_LIMBS],
+    pub is_jal: bool,
+    pub is_lui: bool,
+}
+
+/// Executes JAL and LUI and fills the core columns of [Rv32JalLuiCoreAir].
+pub struct Rv32JalLuiCoreChip {
+    pub air: Rv32JalLuiCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32JalLuiCoreChip {
+    pub fn new(bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>) -> Self {
+        Self { air: Rv32JalLuiCoreAir { bus: bitwise_lookup_chip.bus() }, bitwise_lookup_chip }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32JalLuiCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32JalLuiCoreRecord<F>;
+    type Air = Rv32JalLuiCoreAir;
+
+    /// Executes JAL with the signed offset `c`, a field element that may encode a negative
+    /// offset, or LUI with the upper 20 bits `c`, and requests the range checks that
+    /// [Rv32JalLuiCoreAir] sends for it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        from_pc: u32,
+        _reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let local_opcode: Rv32JalLuiOpcode = instruction
+            .opcode
+            .local_opcode()
+            .unwrap_or_else(|| panic!("opcode {} is not a JAL or LUI opcode", instruction.opcode));
+        assert!(from_pc < (1 << PC_BITS), "pc {from_pc} out of range for {PC_BITS} bits");
+        let imm = instruction.c;
+        let signed_imm = match local_opcode {
+            JAL => {
+                let offset = match imm.as_canonical_u32() {
+                    imm if imm < (1 << (RV_J_TYPE_IMM_BITS - 1)) => imm as i32,
+                    _ => -((-imm).as_canonical_u32() as i32),
+                };
+                assert!(
+                    -(1 << (RV_J_TYPE_IMM_BITS - 1)) <= offset,
+                    "offset {offset} out of range for JAL"
+                );
+                offset
+            }
+            LUI => {
+                let imm = imm.as_canonical_u32();
+                assert!(imm < (1 << RV_U_TYPE_IMM_BITS), "imm {imm} out of range for LUI");
+                imm as i32
+            }
+        };
+        let (to_pc, rd_data) = run_jal_lui(local_opcode, from_pc, signed_imm);
+        assert!(to_pc < (1 << PC_BITS), "to_pc {to_pc} out of range for {PC_BITS} bits");
+
+        for i in 0..(RV32_REGISTER_NUM_LIMBS / 2) {
+            self.bitwise_lookup_chip.request_range(rd_data[i * 2], rd_data[i * 2 + 1]);
+        }
+        if local_opcode == JAL {
+            let last_limb_bits = PC_BITS - RV32_CELL_BITS * (RV32_REGISTER_NUM_LIMBS - 1);
+            let additional_bits = (last_limb_bits..RV32_CELL_BITS).fold(0, |acc, x| acc + (1 << x));
+            self.bitwise_lookup_chip
+                .request_xor(rd_data[RV32_REGISTER_NUM_LIMBS - 1], additional_bits);
+        }
+
+        let rd_data = rd_data.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext { to_pc: Some(to_pc), writes: [rd_data].into() };
+        let record = Rv32JalLuiCoreRecord {
+            imm,
+            rd_data,
+            is_jal: local_opcode == JAL,
+            is_lui: local_opcode == LUI,
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let core_cols: &mut Rv32JalLuiCoreCols<F> = row_slice.borrow_mut();
+        core_cols.imm = record.imm;
+        core_cols.rd_data = record.rd_data;
+        core_cols.is_jal = F::from_bool(record.is_jal);
+        core_cols.is_lui = F::from_bool(record.is_lui);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the next pc and the limbs of `rd`: `(pc + imm, pc + 4)` for JAL and
+/// `(pc + 4, imm << 12)` for LUI.
+pub fn run_jal_lui(
+    opcode: Rv32JalLuiOpcode,
+    pc: u32,
+    imm: i32,
+) -> (u32, [u32; RV32_REGISTER_NUM_LIMBS]) {
+    let (to_pc, rd) = match opcode {
+        JAL => (pc.wrapping_add_signed(imm), pc + DEFAULT_PC_STEP),
+        LUI => (pc + DEFAULT_PC_STEP, (imm as u32) << 12),
+    };
+    (to_pc, array::from_fn(|i| (rd >> (RV32_CELL_BITS * i)) & RV32_LIMB_MAX))
+}
@@ -0,0 +1,279 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::{Rv32JalrAdapterChip, compose},
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{PC_BITS, RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    execution::DEFAULT_PC_STEP,
+    instructions::{
+        Instruction, LocalOpcode,
+        Rv32JalrOpcode::{self, JALR},
+    },
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, SignedImmInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/jalr/core.rs
+// for full implementation details.
+
+pub type Rv32JalrChip = VmChipWrapper<Rv32JalrAdapterChip, Rv32JalrCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32JalrCoreCols<T> {
+        pub is_valid: T,
+        // The lower 16 bits of the immediate, sign-extended by imm_sign
+        pub imm: T,
+        pub imm_sign: T,
+        pub rs1_data: [T; RV32_REGISTER_NUM_LIMBS],
+        pub rd_data: [T; RV32_REGISTER_NUM_LIMBS],
+        // The least significant bit of rs1 + imm, which is cleared in the PC
+        pub to_pc_least_sig_bit: T,
+        // The limbs of the new PC, except that the least significant limb is halved since the PC
+        // is even
+        pub to_pc_limbs: [T; RV32_REGISTER_NUM_LIMBS],
+    }
+}
+
+/// Constrains `rd = from_pc + 4` and `to_pc = (rs1 + imm) & !1`, where `to_pc` must fit in
+/// [PC_BITS] bits.
+#[derive(Clone, Copy)]
+pub struct Rv32JalrCoreAir {
+    pub bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+}
+
+impl BaseAir<F> for Rv32JalrCoreAir {
+    fn width(&self) -> usize {
+        Rv32JalrCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32JalrCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<SignedImmInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32JalrCoreCols<AB::Var> = local_core.borrow();
+        let Rv32JalrCoreCols {
+            is_valid,
+            imm,
+            imm_sign,
+            rs1_data: rs1,
+            rd_data: rd,
+            to_pc_least_sig_bit,
+            to_pc_limbs,
+        } = *cols;
+
+        builder.assert_bool(is_valid);
+        builder.assert_bool(imm_sign);
+        builder.assert_bool(to_pc_least_sig_bit);
+
+        let compose_limbs = |limbs: [AB::Expr; RV32_REGISTER_NUM_LIMBS]| {
+            limbs.into_iter().enumerate().fold(AB::Expr::ZERO, |acc, (i, limb)| {
+                acc + limb * AB::F::from_canonical_u32(1 << (i * RV32_CELL_BITS))
+            })
+        };
+
+        // rd = from_pc + 4, which cannot overflow since from_pc is a valid PC
+        builder.when(is_valid).assert_eq(
+            compose_limbs(rd.map(Into::into)),
+            from_pc + AB::F::from_canonical_u32(DEFAULT_PC_STEP),
+        );
+
+        // Constrain to_pc_least_sig_bit + to_pc = rs1 + imm as an i32 addition with two limbs
+        // of 16 bits, where the upper limb of the immediate is its sign extension.
+        let to_pc_limbs: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|i| match i {
+            0 => to_pc_limbs[0] * AB::F::from_canonical_u32(2),
+            i => to_pc_limbs[i].into(),
+        });
+        let half = 2 * RV32_CELL_BITS;
+        let inv = AB::F::from_canonical_u32(1 << half).inverse();
+        let limb_pair = |limbs: &[AB::Expr]| {
+            limbs[0].clone() + limbs[1].clone() * AB::F::from_canonical_u32(1 << RV32_CELL_BITS)
+        };
+        let rs1 = rs1.map(Into::into);
+
+        let carry =
+            (limb_pair(&rs1[..2]) + imm - limb_pair(&to_pc_limbs[..2]) - to_pc_least_sig_bit) * inv;
+        builder.when(is_valid).assert_bool(carry.clone());
+
+        let imm_extend_limb = imm_sign * AB::F::from_canonical_u32((1 << half) - 1);
+        let carry =
+            (limb_pair(&rs1[2..]) + imm_extend_limb + carry - limb_pair(&to_pc_limbs[2..])) * inv;
+        builder.when(is_valid).assert_bool(carry);
+
+        // Range checking of rd_data and to_pc_limbs entries to RV32_CELL_BITS bits, except that
+        // the most significant limbs are range checked to [0, 2^(PC_BITS - 24)) by scaling them up
+        // to a full limb, as for AUIPC.
+        let msl_shift =
+            AB::F::from_canonical_u32(1 << (RV32_REGISTER_NUM_LIMBS * RV32_CELL_BITS - PC_BITS));
+        for limbs in [rd.map(Into::into), to_pc_limbs.clone()] {
+            self.bus
+                .send_range::<AB::Expr>(limbs[0].clone(), limbs[1].clone())
+                .eval(builder, is_valid);
+            self.bus
+                .send_range::<AB::Expr>(
+                    limbs[2].clone(),
+                    limbs[RV32_REGISTER_NUM_LIMBS - 1].clone() * msl_shift,
+                )
+                .eval(builder, is_valid);
+        }
+        // The doubled least significant limb of to_pc does not bound the halved one, since 1/2
+        // doubles to 1. Range checking both bounds the halved limb to RV32_CELL_BITS - 1 bits, so
+        // to_pc is even.
+        self.bus
+            .send_range::<AB::Expr>(cols.to_pc_limbs[0], to_pc_limbs[0].clone())
+            .eval(builder, is_valid);
+
+        let to_pc = compose_limbs(to_pc_limbs);
+        let expected_opcode = VmCoreAir::<AB, I>::opcode_to_global_expr(self, JALR);
+
+        AdapterAirContext {
+            to_pc: Some(to_pc),
+            reads: [rs1].into(),
+            writes: [rd.map(Into::into)].into(),
+            instruction: SignedImmInstruction {
+                is_valid: is_valid.into(),
+                opcode: expected_opcode,
+                immediate: imm.into(),
+                imm_sign: imm_sign.into(),
+            }
+            .into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        Rv32JalrOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32JalrCoreRecord<F> {
+    pub imm: F,
+    pub imm_sign: F,
+    pub rs1_data: [F; RV32_REGISTER_NUM_LIMBS],
+    pub rd_data: [F; RV32_REGISTER_NUM_LIMBS],
+    pub to_pc_least_sig_bit: F,
+    pub to_pc_limbs: [F; RV32_REGISTER_NUM_LIMBS],
+}
+
+/// Executes JALR and fills the core columns of [Rv32JalrCoreAir].
+pub struct Rv32JalrCoreChip {
+    pub air: Rv32JalrCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32JalrCoreChip {
+    pub fn new(bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>) -> Self {
+        Self { air: Rv32JalrCoreAir { bus: bitwise_lookup_chip.bus() }, bitwise_lookup_chip }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32JalrCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32JalrCoreRecord<F>;
+    type Air = Rv32JalrCoreAir;
+
+    /// Executes `rd = from_pc + 4, to_pc = (rs1 + imm) & !1` where the immediate is the 16 bits
+    /// `c` sign-extended by `g`, and requests the range checks that [Rv32JalrCoreAir] sends for
+    /// it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        assert_eq!(
+            instruction.opcode,
+            JALR.global_opcode(),
+            "opcode {} is not JALR",
+            instruction.opcode
+        );
+        assert!(from_pc < (1 << PC_BITS), "pc {from_pc} out of range for {PC_BITS} bits");
+        let imm = instruction.c.as_canonical_u32();
+        let imm_sign = instruction.g.as_canonical_u32();
+        assert!(imm < (1 << (2 * RV32_CELL_BITS)), "imm {imm} out of range for JALR");
+        assert!(imm_sign <= 1, "imm_sign {imm_sign} is not a bit");
+        let imm_extended = imm + imm_sign * 0xffff_0000;
+
+        let [rs1_data] = reads.into();
+        let rs1 = compose(&rs1_data);
+        let (to_pc, rd_data) = run_jalr(from_pc, rs1, imm_extended);
+        assert!(to_pc < (1 << PC_BITS), "to_pc {to_pc:#x} out of range for {PC_BITS} bits");
+
+        let to_pc_limbs: [u32; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|i| match i {
+            0 => (to_pc & RV32_LIMB_MAX) >> 1,
+            i => (to_pc >> (i * RV32_CELL_BITS)) & RV32_LIMB_MAX,
+        });
+        // The same order as in `Rv32JalrCoreAir::eval`.
+        let msl_shift = RV32_REGISTER_NUM_LIMBS * RV32_CELL_BITS - PC_BITS;
+        for limbs in
+            [rd_data, [to_pc_limbs[0] << 1, to_pc_limbs[1], to_pc_limbs[2], to_pc_limbs[3]]]
+        {
+            self.bitwise_lookup_chip.request_range(limbs[0], limbs[1]);
+            self.bitwise_lookup_chip.request_range(limbs[2], limbs[3] << msl_shift);
+        }
+        self.bitwise_lookup_chip.request_range(to_pc_limbs[0], to_pc_limbs[0] << 1);
+
+        let rd_data = rd_data.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext { to_pc: Some(to_pc), writes: [rd_data].into() };
+        let record = Rv32JalrCoreRecord {
+            imm: instruction.c,
+            imm_sign: instruction.g,
+            rs1_data,
+            rd_data,
+            to_pc_least_sig_bit: F::from_bool(rs1.wrapping_add(imm_extended) & 1 == 1),
+            to_pc_limbs: to_pc_limbs.map(F::from_canonical_u32),
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let core_cols: &mut Rv32JalrCoreCols<F> = row_slice.borrow_mut();
+        core_cols.is_valid = F::ONE;
+        core_cols.imm = record.imm;
+        core_cols.imm_sign = record.imm_sign;
+        core_cols.rs1_data = record.rs1_data;
+        core_cols.rd_data = record.rd_data;
+        core_cols.to_pc_least_sig_bit = record.to_pc_least_sig_bit;
+        core_cols.to_pc_limbs = record.to_pc_limbs;
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the next pc `(rs1 + imm) & !1` and the limbs of `rd = pc + 4`, where `imm` is the
+/// sign-extended immediate.
+pub fn run_jalr(pc: u32, rs1: u32, imm: u32) -> (u32, [u32; RV32_REGISTER_NUM_LIMBS]) {
+    let to_pc = rs1.wrapping_add(imm) & !1;
+    let rd = pc + DEFAULT_PC_STEP;
+    (to_pc, array::from_fn(|i| (rd >> (RV32_CELL_BITS * i)) & RV32_LIMB_MAX))
+}
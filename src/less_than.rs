@@ -0,0 +1,301 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32BaseAluAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS},
+    instructions::{Instruction, LessThanOpcode, LocalOpcode},
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, MinimalInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    utils::not,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/less_than/core.rs
+// for full implementation details.
+
+pub type Rv32LessThanChip = VmChipWrapper<Rv32BaseAluAdapterChip, Rv32LessThanCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32LessThanCoreCols<T> {
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+        pub c: [T; RV32_REGISTER_NUM_LIMBS],
+        pub cmp_result: T,
+
+        pub opcode_slt_flag: T,
+        pub opcode_sltu_flag: T,
+
+        // Most significant limb of b and c respectively as a field element, will be range
+        // checked to be within [-128, 127) if signed, [0, 256) if unsigned.
+        pub b_msb_f: T,
+        pub c_msb_f: T,
+
+        // 1 at the most significant index i such that b[i] != c[i], otherwise 0. If such an i
+        // exists, diff_val = c[i] - b[i] if c[i] > b[i] or b[i] - c[i] else.
+        pub diff_marker: [T; RV32_REGISTER_NUM_LIMBS],
+        pub diff_val: T,
+        // The inverse of diff_val if b != c, which proves that diff_val is non-zero.
+        pub diff_inv: T,
+    }
+}
+
+/// Constrains `cmp_result = b < c`, as signed integers for SLT and as unsigned integers for
+/// SLTU.
+#[derive(Clone, Copy)]
+pub struct Rv32LessThanCoreAir {
+    pub bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+}
+
+impl BaseAir<F> for Rv32LessThanCoreAir {
+    fn width(&self) -> usize {
+        Rv32LessThanCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32LessThanCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<MinimalInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32LessThanCoreCols<AB::Var> = local_core.borrow();
+        let flags = [cols.opcode_slt_flag, cols.opcode_sltu_flag];
+
+        let is_valid = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| {
+            builder.assert_bool(flag);
+            acc + flag
+        });
+        builder.assert_bool(is_valid.clone());
+        builder.assert_bool(cols.cmp_result);
+
+        let b = &cols.b;
+        let c = &cols.c;
+        let marker = &cols.diff_marker;
+        let mut prefix_sum = AB::Expr::ZERO;
+
+        let b_diff = b[RV32_REGISTER_NUM_LIMBS - 1] - cols.b_msb_f;
+        let c_diff = c[RV32_REGISTER_NUM_LIMBS - 1] - cols.c_msb_f;
+        builder.assert_zero(
+            b_diff.clone() * (AB::Expr::from_canonical_u32(1 << RV32_CELL_BITS) - b_diff),
+        );
+        builder.assert_zero(
+            c_diff.clone() * (AB::Expr::from_canonical_u32(1 << RV32_CELL_BITS) - c_diff),
+        );
+
+        for i in (0..RV32_REGISTER_NUM_LIMBS).rev() {
+            let diff = (if i == RV32_REGISTER_NUM_LIMBS - 1 {
+                cols.c_msb_f - cols.b_msb_f
+            } else {
+                c[i] - b[i]
+            }) * (AB::Expr::from_canonical_u8(2) * cols.cmp_result - AB::Expr::ONE);
+            prefix_sum += marker[i].into();
+            builder.assert_bool(marker[i]);
+            builder.assert_zero(not::<AB::Expr>(prefix_sum.clone()) * diff.clone());
+            builder.when(marker[i]).assert_eq(cols.diff_val, diff);
+        }
+        // - If b != c, then prefix_sum = 1 so marker[i] must be 1 iff i is the first index where
+        //   diff != 0. Constrains that diff == diff_val where diff_val is non-zero.
+        // - If b == c, then prefix_sum = 0 and cmp_result = 0. Here, prefix_sum cannot be 1
+        //   because all diff are zero, making diff == diff_val fail.
+
+        builder.assert_bool(prefix_sum.clone());
+        builder.when(not::<AB::Expr>(prefix_sum.clone())).assert_zero(cols.cmp_result);
+
+        // Check if b_msb_f and c_msb_f are in [-128, 127) if signed, [0, 256) if unsigned.
+        self.bus
+            .send_range::<AB::Expr>(
+                cols.b_msb_f
+                    + AB::Expr::from_canonical_u32(1 << (RV32_CELL_BITS - 1))
+                        * cols.opcode_slt_flag,
+                cols.c_msb_f
+                    + AB::Expr::from_canonical_u32(1 << (RV32_CELL_BITS - 1))
+                        * cols.opcode_slt_flag,
+            )
+            .eval(builder, is_valid.clone());
+
+        // diff_val is non-zero by its inverse, and the range check ensures it is positive, i.e.
+        // that cmp_result has the right sign.
+        builder.when(prefix_sum.clone()).assert_one(cols.diff_val * cols.diff_inv);
+        self.bus.send_range::<AB::Expr>(cols.diff_val, AB::F::ZERO).eval(builder, prefix_sum);
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            flags.iter().zip(LessThanOpcode::ALL).fold(AB::Expr::ZERO, |acc, (&flag, opcode)| {
+                acc + flag * AB::Expr::from_canonical_usize(opcode.local_usize())
+            }),
+        );
+        let mut a: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+        a[0] = cols.cmp_result.into();
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: [cols.b.map(Into::into), cols.c.map(Into::into)].into(),
+            writes: [a].into(),
+            instruction: MinimalInstruction { is_valid, opcode: expected_opcode }.into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        LessThanOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32LessThanCoreRecord<F> {
+    pub opcode: LessThanOpcode,
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub c: [F; RV32_REGISTER_NUM_LIMBS],
+    pub cmp_result: F,
+    pub b_msb_f: F,
+    pub c_msb_f: F,
+    pub diff_val: F,
+    /// [RV32_REGISTER_NUM_LIMBS] if `b == c`.
+    pub diff_idx: usize,
+}
+
+/// Executes SLT and SLTU and fills the core columns of [Rv32LessThanCoreAir].
+pub struct Rv32LessThanCoreChip {
+    pub air: Rv32LessThanCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32LessThanCoreChip {
+    pub fn new(bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>) -> Self {
+        Self { air: Rv32LessThanCoreAir { bus: bitwise_lookup_chip.bus() }, bitwise_lookup_chip }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32LessThanCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32LessThanCoreRecord<F>;
+    type Air = Rv32LessThanCoreAir;
+
+    /// Executes `a = b < c` and requests the range checks that [Rv32LessThanCoreAir] sends for
+    /// it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let less_than_opcode: LessThanOpcode = instruction
+            .opcode
+            .local_opcode()
+            .unwrap_or_else(|| panic!("opcode {} is not a less than opcode", instruction.opcode));
+        let [b, c] = reads.into();
+        let b_limbs = b.map(|x| x.as_canonical_u32());
+        let c_limbs = c.map(|x| x.as_canonical_u32());
+        let signed = less_than_opcode == LessThanOpcode::SLT;
+        let (cmp_result, diff_idx, b_sign, c_sign) = run_less_than(signed, &b_limbs, &c_limbs);
+
+        let (b_msb_f, b_msb_range) =
+            msb_to_field(b_limbs[RV32_REGISTER_NUM_LIMBS - 1], signed, b_sign);
+        let (c_msb_f, c_msb_range) =
+            msb_to_field(c_limbs[RV32_REGISTER_NUM_LIMBS - 1], signed, c_sign);
+        let diff_val = if diff_idx == RV32_REGISTER_NUM_LIMBS {
+            0
+        } else if diff_idx == RV32_REGISTER_NUM_LIMBS - 1 {
+            if cmp_result { c_msb_f - b_msb_f } else { b_msb_f - c_msb_f }.as_canonical_u32()
+        } else if cmp_result {
+            c_limbs[diff_idx] - b_limbs[diff_idx]
+        } else {
+            b_limbs[diff_idx] - c_limbs[diff_idx]
+        };
+
+        self.bitwise_lookup_chip.request_range(b_msb_range, c_msb_range);
+        if diff_idx != RV32_REGISTER_NUM_LIMBS {
+            self.bitwise_lookup_chip.request_range(diff_val, 0);
+        }
+
+        let mut writes = [F::ZERO; RV32_REGISTER_NUM_LIMBS];
+        writes[0] = F::from_bool(cmp_result);
+
+        let output = AdapterRuntimeContext::without_pc([writes]);
+        let record = Rv32LessThanCoreRecord {
+            opcode: less_than_opcode,
+            b,
+            c,
+            cmp_result: F::from_bool(cmp_result),
+            b_msb_f,
+            c_msb_f,
+            diff_val: F::from_canonical_u32(diff_val),
+            diff_idx,
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32LessThanCoreCols<F> = row_slice.borrow_mut();
+        row_slice.b = record.b;
+        row_slice.c = record.c;
+        row_slice.cmp_result = record.cmp_result;
+        row_slice.b_msb_f = record.b_msb_f;
+        row_slice.c_msb_f = record.c_msb_f;
+        row_slice.diff_val = record.diff_val;
+        row_slice.diff_inv = match record.diff_idx {
+            RV32_REGISTER_NUM_LIMBS => F::ZERO,
+            _ => record.diff_val.inverse(),
+        };
+        row_slice.opcode_slt_flag = F::from_bool(record.opcode == LessThanOpcode::SLT);
+        row_slice.opcode_sltu_flag = F::from_bool(record.opcode == LessThanOpcode::SLTU);
+        row_slice.diff_marker = array::from_fn(|i| F::from_bool(i == record.diff_idx));
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the most significant limb `msb` as a field element, negative if `sign` is set, and
+/// the value that is range checked for it: the field element shifted into `[0, 256)`.
+pub fn msb_to_field(msb: u32, signed: bool, sign: bool) -> (F, u32) {
+    if sign {
+        (-F::from_canonical_u32((1 << RV32_CELL_BITS) - msb), msb - (1 << (RV32_CELL_BITS - 1)))
+    } else {
+        (F::from_canonical_u32(msb), msb + (u32::from(signed) << (RV32_CELL_BITS - 1)))
+    }
+}
+
+/// Compares the little-endian limbs `x` and `y`, as two's complement integers if `signed`.
+///
+/// Returns `x < y`, the index of the most significant limb where they differ
+/// ([RV32_REGISTER_NUM_LIMBS] if they are equal), and the signs of `x` and `y`.
+pub fn run_less_than(
+    signed: bool,
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> (bool, usize, bool, bool) {
+    let x_sign = (x[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1) == 1) && signed;
+    let y_sign = (y[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1) == 1) && signed;
+    for i in (0..RV32_REGISTER_NUM_LIMBS).rev() {
+        if x[i] != y[i] {
+            return ((x[i] < y[i]) ^ x_sign ^ y_sign, i, x_sign, y_sign);
+        }
+    }
+    (false, RV32_REGISTER_NUM_LIMBS, x_sign, y_sign)
+}
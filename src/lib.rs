@@ -1,3 +1,38 @@
+pub mod adapters;
+pub mod aligned_borrow;
+pub mod asm;
+pub mod base_alu;
+pub mod bitwise_op_lookup;
+pub mod branch_eq;
+pub mod branch_lt;
 pub mod bus;
 pub mod core;
+pub mod decoder;
+pub mod divrem;
+pub mod elf;
+pub mod encoder;
+pub mod execution;
+pub mod instructions;
+pub mod integration_api;
+pub mod interpreter;
+pub mod is_equal;
+pub mod is_equal_array;
+pub mod is_less_than;
+pub mod is_less_than_array;
+pub mod is_zero;
+pub mod jal_lui;
+pub mod jalr;
+pub mod less_than;
+pub mod loadstore;
+pub mod memory;
+pub mod mul;
+pub mod mulh;
 pub mod openvm_stark_backend;
+pub mod program;
+pub mod range_tuple;
+pub mod shift;
+pub mod sub_air;
+pub mod testing;
+pub mod utils;
+pub mod var_range;
+pub mod volatile;
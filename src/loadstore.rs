@@ -0,0 +1,360 @@
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::{
+    adapters::Rv32LoadStoreAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    encoder::Encoder,
+    instructions::{
+        Instruction, LocalOpcode,
+        Rv32LoadStoreOpcode::{self, *},
+    },
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, LoadStoreInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/loadstore/core.rs
+// for full implementation details.
+
+pub type Rv32LoadStoreChip = VmChipWrapper<Rv32LoadStoreAdapterChip, Rv32LoadStoreCoreChip>;
+
+/// The `(opcode, shift amount)` pairs that the core distinguishes, where the shift amount is the
+/// offset of the effective address within its aligned memory block. Only the shift amounts that
+/// keep the access aligned to its width are listed.
+pub const LOADSTORE_CASES: [(Rv32LoadStoreOpcode, u32); 20] = [
+    (LOADW, 0),
+    (LOADHU, 0),
+    (LOADHU, 2),
+    (LOADBU, 0),
+    (LOADBU, 1),
+    (LOADBU, 2),
+    (LOADBU, 3),
+    (STOREW, 0),
+    (STOREH, 0),
+    (STOREH, 2),
+    (STOREB, 0),
+    (STOREB, 1),
+    (STOREB, 2),
+    (STOREB, 3),
+    (LOADB, 0),
+    (LOADB, 1),
+    (LOADB, 2),
+    (LOADB, 3),
+    (LOADH, 0),
+    (LOADH, 2),
+];
+
+/// The number of columns encoding the [LOADSTORE_CASES] with degree two flags.
+pub const LOADSTORE_FLAG_WIDTH: usize = 5;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32LoadStoreCoreCols<T> {
+        // The encoded index into LOADSTORE_CASES, all zero on padding rows
+        pub flags: [T; LOADSTORE_FLAG_WIDTH],
+        pub is_valid: T,
+        pub is_load: T,
+
+        pub read_data: [T; RV32_REGISTER_NUM_LIMBS],
+        pub prev_data: [T; RV32_REGISTER_NUM_LIMBS],
+        pub write_data: [T; RV32_REGISTER_NUM_LIMBS],
+        // The sign bit of the loaded value for LOADB and LOADH, zero otherwise
+        pub data_most_sig_bit: T,
+    }
+}
+
+/// Constrains the data written by a load or store given the aligned block read and the previous
+/// data of the written block:
+/// - loads write the bytes of the read block at the shift amount into `rd`, zero extended for
+///   LOADBU/LOADHU and sign extended for LOADB/LOADH,
+/// - stores write the lowest bytes of `rs2` into the previous block at the shift amount.
+///
+/// The shift amount is part of the encoded case, so that an access that is not aligned to its
+/// width has no case at all.
+#[derive(Clone)]
+pub struct Rv32LoadStoreCoreAir {
+    pub bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+    pub encoder: Encoder,
+}
+
+impl BaseAir<F> for Rv32LoadStoreCoreAir {
+    fn width(&self) -> usize {
+        Rv32LoadStoreCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32LoadStoreCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<([AB::Var; RV32_REGISTER_NUM_LIMBS], [AB::Expr; RV32_REGISTER_NUM_LIMBS])>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<LoadStoreInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32LoadStoreCoreCols<AB::Var> = local_core.borrow();
+        let Rv32LoadStoreCoreCols {
+            flags,
+            is_valid,
+            is_load,
+            read_data,
+            prev_data,
+            write_data,
+            data_most_sig_bit,
+        } = *cols;
+
+        self.encoder.eval(builder, &flags);
+        let case_flags = self.encoder.flags::<AB>(&flags);
+        let cases_where = |pred: fn(Rv32LoadStoreOpcode) -> bool| {
+            LOADSTORE_CASES
+                .iter()
+                .zip(&case_flags)
+                .filter(|((opcode, _), _)| pred(*opcode))
+                .fold(AB::Expr::ZERO, |acc, (_, flag)| acc + flag.clone())
+        };
+
+        builder.assert_eq(is_valid, self.encoder.is_valid::<AB>(&flags));
+        builder.assert_eq(is_load, cases_where(is_load_opcode));
+
+        // The sign bit is zero unless sign extending, and then the sign byte less the sign bit
+        // times 2^7 must fit in seven bits, which is range checked by doubling it.
+        let is_signed_load = cases_where(|opcode| matches!(opcode, LOADB | LOADH));
+        builder.assert_bool(data_most_sig_bit);
+        builder.when(AB::Expr::ONE - is_signed_load.clone()).assert_zero(data_most_sig_bit);
+        let sign_byte = LOADSTORE_CASES.iter().zip(&case_flags).fold(
+            AB::Expr::ZERO,
+            |acc, (&(opcode, shift), flag)| match opcode {
+                LOADB | LOADH => {
+                    let idx = shift as usize + access_width(opcode) - 1;
+                    acc + flag.clone() * read_data[idx]
+                }
+                _ => acc,
+            },
+        );
+        self.bus
+            .send_range::<AB::Expr>(
+                (sign_byte - data_most_sig_bit * AB::F::from_canonical_u32(1 << 7))
+                    * AB::F::from_canonical_u32(2),
+                AB::Expr::ZERO,
+            )
+            .eval(builder, is_signed_load);
+
+        let sign_ext = data_most_sig_bit * AB::F::from_canonical_u32(RV32_LIMB_MAX);
+        let expected_write_data = LOADSTORE_CASES.iter().zip(&case_flags).fold(
+            [AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO, AB::Expr::ZERO],
+            |mut acc, (&(opcode, shift), flag)| {
+                let data = shift_data(
+                    opcode,
+                    shift as usize,
+                    &read_data.map(Into::into),
+                    &prev_data.map(Into::into),
+                    AB::Expr::ZERO,
+                    sign_ext.clone(),
+                );
+                for (acc, limb) in acc.iter_mut().zip(data) {
+                    *acc += flag.clone() * limb;
+                }
+                acc
+            },
+        );
+        for (write, expected) in write_data.into_iter().zip(expected_write_data) {
+            builder.assert_eq(write, expected);
+        }
+
+        let shift_amount_where = |pred: fn(Rv32LoadStoreOpcode) -> bool| {
+            let vals: Vec<_> = LOADSTORE_CASES
+                .iter()
+                .enumerate()
+                .filter(|(_, (opcode, _))| pred(*opcode))
+                .map(|(i, &(_, shift))| (i, shift as usize))
+                .collect();
+            self.encoder.flag_with_val::<AB>(&flags, &vals)
+        };
+        let local_opcode = LOADSTORE_CASES.iter().zip(&case_flags).fold(
+            AB::Expr::ZERO,
+            |acc, ((opcode, _), flag)| {
+                acc + flag.clone() * AB::F::from_canonical_usize(opcode.local_usize())
+            },
+        );
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(self, local_opcode);
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: (prev_data, read_data.map(Into::into)).into(),
+            writes: [write_data.map(Into::into)].into(),
+            instruction: LoadStoreInstruction {
+                is_valid: is_valid.into(),
+                opcode: expected_opcode,
+                is_load: is_load.into(),
+                load_shift_amount: shift_amount_where(is_load_opcode),
+                store_shift_amount: shift_amount_where(|opcode| !is_load_opcode(opcode)),
+            }
+            .into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        Rv32LoadStoreOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32LoadStoreCoreRecord<F> {
+    /// The index into [LOADSTORE_CASES]
+    pub case: usize,
+    pub read_data: [F; RV32_REGISTER_NUM_LIMBS],
+    pub prev_data: [F; RV32_REGISTER_NUM_LIMBS],
+    pub write_data: [F; RV32_REGISTER_NUM_LIMBS],
+    pub data_most_sig_bit: F,
+}
+
+/// Executes loads and stores on the aligned block read by the adapter and fills the core columns
+/// of [Rv32LoadStoreCoreAir].
+pub struct Rv32LoadStoreCoreChip {
+    pub air: Rv32LoadStoreCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+}
+
+impl Rv32LoadStoreCoreChip {
+    pub fn new(bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>) -> Self {
+        let encoder = Encoder::new(LOADSTORE_CASES.len(), 2, true);
+        assert_eq!(encoder.width(), LOADSTORE_FLAG_WIDTH);
+        Self {
+            air: Rv32LoadStoreCoreAir { bus: bitwise_lookup_chip.bus(), encoder },
+            bitwise_lookup_chip,
+        }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32LoadStoreCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<([[F; RV32_REGISTER_NUM_LIMBS]; 2], F)>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32LoadStoreCoreRecord<F>;
+    type Air = Rv32LoadStoreCoreAir;
+
+    /// Computes the data written by a load or store at the given shift amount, and requests the
+    /// range check of the sign byte that [Rv32LoadStoreCoreAir] sends for LOADB and LOADH.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let local_opcode: Rv32LoadStoreOpcode =
+            instruction.opcode.local_opcode().unwrap_or_else(|| {
+                panic!("opcode {} is not a load or store opcode", instruction.opcode)
+            });
+        let ([prev_data, read_data], shift_amount) = reads.into();
+        let shift_amount = shift_amount.as_canonical_u32();
+        let case = LOADSTORE_CASES
+            .iter()
+            .position(|&case| case == (local_opcode, shift_amount))
+            .unwrap_or_else(|| panic!("unaligned {local_opcode:?} with shift {shift_amount}"));
+
+        let read = read_data.map(|x| x.as_canonical_u32());
+        let prev = prev_data.map(|x| x.as_canonical_u32());
+        let (write, msb) = run_write_data(local_opcode, shift_amount, &read, &prev);
+        if matches!(local_opcode, LOADB | LOADH) {
+            let sign_byte = read[shift_amount as usize + access_width(local_opcode) - 1];
+            self.bitwise_lookup_chip.request_range((sign_byte - (msb << 7)) * 2, 0);
+        }
+
+        let write_data = write.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext::without_pc([write_data]);
+        let record = Rv32LoadStoreCoreRecord {
+            case,
+            read_data,
+            prev_data,
+            write_data,
+            data_most_sig_bit: F::from_canonical_u32(msb),
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let core_cols: &mut Rv32LoadStoreCoreCols<F> = row_slice.borrow_mut();
+        self.air.encoder.generate_subrow(Some(record.case), &mut core_cols.flags);
+        core_cols.is_valid = F::ONE;
+        core_cols.is_load = F::from_bool(is_load_opcode(LOADSTORE_CASES[record.case].0));
+        core_cols.read_data = record.read_data;
+        core_cols.prev_data = record.prev_data;
+        core_cols.write_data = record.write_data;
+        core_cols.data_most_sig_bit = record.data_most_sig_bit;
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Whether the opcode writes `rd` from memory rather than memory from `rs2`.
+pub fn is_load_opcode(opcode: Rv32LoadStoreOpcode) -> bool {
+    matches!(opcode, LOADW | LOADHU | LOADBU | LOADB | LOADH)
+}
+
+/// The number of bytes accessed by the opcode, to which the effective address must be aligned.
+pub fn access_width(opcode: Rv32LoadStoreOpcode) -> usize {
+    match opcode {
+        LOADW | STOREW => 4,
+        LOADHU | STOREH | LOADH => 2,
+        LOADBU | STOREB | LOADB => 1,
+    }
+}
+
+/// Returns the limbs written by the opcode at the given shift amount, given the aligned `read`
+/// block and the `prev` data of the written block, and the sign bit of the loaded value for
+/// LOADB and LOADH.
+pub fn run_write_data(
+    opcode: Rv32LoadStoreOpcode,
+    shift: u32,
+    read: &[u32; RV32_REGISTER_NUM_LIMBS],
+    prev: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> ([u32; RV32_REGISTER_NUM_LIMBS], u32) {
+    assert_eq!(shift as usize % access_width(opcode), 0, "unaligned {opcode:?} with shift {shift}");
+    let msb = match opcode {
+        LOADB | LOADH => read[shift as usize + access_width(opcode) - 1] >> (RV32_CELL_BITS - 1),
+        _ => 0,
+    };
+    (shift_data(opcode, shift as usize, read, prev, 0, msb * RV32_LIMB_MAX), msb)
+}
+
+/// The limbs written by the opcode at the given shift amount, where the limbs beyond a load are
+/// `zero` or, for LOADB and LOADH, `sign_ext`.
+fn shift_data<T: Clone>(
+    opcode: Rv32LoadStoreOpcode,
+    shift: usize,
+    read: &[T; RV32_REGISTER_NUM_LIMBS],
+    prev: &[T; RV32_REGISTER_NUM_LIMBS],
+    zero: T,
+    sign_ext: T,
+) -> [T; RV32_REGISTER_NUM_LIMBS] {
+    let n = access_width(opcode);
+    core::array::from_fn(|i| match opcode {
+        LOADW | STOREW => read[i].clone(),
+        LOADHU | LOADBU if i < n => read[shift + i].clone(),
+        LOADHU | LOADBU => zero.clone(),
+        LOADH | LOADB if i < n => read[shift + i].clone(),
+        LOADH | LOADB => sign_ext.clone(),
+        STOREH | STOREB if (shift..shift + n).contains(&i) => read[i - shift].clone(),
+        STOREH | STOREB => prev[i].clone(),
+    })
+}
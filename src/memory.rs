@@ -0,0 +1,455 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    aligned_borrow,
+    bus::{MemoryBus, VariableRangeCheckerBus},
+    execution::{ExecutionError, INITIAL_TIMESTAMP},
+    is_less_than::{IsLessThanIo, IsLtSubAir, LessThanAuxCols},
+    openvm_stark_backend::{
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::RowMajorMatrix,
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+    var_range::SharedVariableRangeCheckerChip,
+    volatile::{VolatileBoundaryAir, VolatileBoundaryRecord},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/system/memory/offline_checker/bridge.rs
+// for full implementation details.
+
+/// The number of limbs of the timestamp comparison in [MemoryBaseAuxCols].
+pub const AUX_LEN: usize = 2;
+/// The number of cells of a memory block. Every access reads or writes one aligned block.
+pub const MEMORY_BLOCK_SIZE: usize = 4;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MemoryAddress<S, T> {
+    pub address_space: S,
+    pub pointer: T,
+}
+
+impl<S, T> MemoryAddress<S, T> {
+    pub fn new(address_space: S, pointer: T) -> Self {
+        Self { address_space, pointer }
+    }
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct MemoryBaseAuxCols<T> {
+        /// The previous timestamp that this memory address was accessed at.
+        pub prev_timestamp: T,
+        /// The auxiliary columns to perform the less than check.
+        pub timestamp_lt_aux: LessThanAuxCols<T, AUX_LEN>,
+    }
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct MemoryReadAuxCols<T> {
+        pub base: MemoryBaseAuxCols<T>,
+    }
+}
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct MemoryWriteAuxCols<T, const N: usize> {
+        pub base: MemoryBaseAuxCols<T>,
+        pub prev_data: [T; N],
+    }
+}
+
+/// The [MemoryBridge] is used within AIR evaluation functions to constrain logical memory
+/// operations (read/write). It adds all necessary constraints and interactions.
+#[derive(Clone, Copy, Debug)]
+pub struct MemoryBridge {
+    pub memory_bus: MemoryBus,
+    pub timestamp_lt_air: IsLtSubAir,
+}
+
+impl MemoryBridge {
+    /// Timestamps are compared with `timestamp_max_bits`, which must decompose into [AUX_LEN]
+    /// limbs of the range checker on `range_bus`.
+    pub fn new(
+        memory_bus: MemoryBus,
+        timestamp_max_bits: usize,
+        range_bus: VariableRangeCheckerBus,
+    ) -> Self {
+        let timestamp_lt_air = IsLtSubAir::new(range_bus, timestamp_max_bits);
+        assert_eq!(
+            timestamp_lt_air.decomp_limbs, AUX_LEN,
+            "timestamps of {timestamp_max_bits} bits must decompose into {AUX_LEN} limbs"
+        );
+        Self { memory_bus, timestamp_lt_air }
+    }
+
+    /// Prepare a logical memory read operation.
+    #[must_use]
+    pub fn read<'a, T, V, const N: usize>(
+        &self,
+        address: MemoryAddress<impl Into<T>, impl Into<T>>,
+        data: [impl Into<T>; N],
+        timestamp: impl Into<T>,
+        aux: &'a MemoryReadAuxCols<V>,
+    ) -> MemoryReadOperation<'a, T, V, N> {
+        MemoryReadOperation {
+            bridge: *self,
+            address: MemoryAddress::new(address.address_space.into(), address.pointer.into()),
+            data: data.map(Into::into),
+            timestamp: timestamp.into(),
+            aux,
+        }
+    }
+
+    /// Prepare a logical memory write operation.
+    #[must_use]
+    pub fn write<'a, T, V, const N: usize>(
+        &self,
+        address: MemoryAddress<impl Into<T>, impl Into<T>>,
+        data: [impl Into<T>; N],
+        timestamp: impl Into<T>,
+        aux: &'a MemoryWriteAuxCols<V, N>,
+    ) -> MemoryWriteOperation<'a, T, V, N> {
+        MemoryWriteOperation {
+            bridge: *self,
+            address: MemoryAddress::new(address.address_space.into(), address.pointer.into()),
+            data: data.map(Into::into),
+            timestamp: timestamp.into(),
+            aux,
+        }
+    }
+
+    /// Constrains `prev_timestamp < timestamp` when `enabled` is one.
+    fn eval_timestamp_lt<AB: InteractionBuilder>(
+        &self,
+        builder: &mut AB,
+        base: &MemoryBaseAuxCols<AB::Var>,
+        timestamp: AB::Expr,
+        enabled: AB::Expr,
+    ) {
+        let io = IsLessThanIo::new(base.prev_timestamp, timestamp, AB::Expr::ONE, enabled);
+        self.timestamp_lt_air.eval(builder, (io, &base.timestamp_lt_aux.lower_decomp));
+    }
+}
+
+/// Constraints and interactions for a logical memory read of `(address, data)` at time
+/// `timestamp`. This reads `(address, data, timestamp_prev)` from the memory bus and writes
+/// `(address, data, timestamp)` to the memory bus. It includes a constraint that
+/// `timestamp_prev < timestamp`.
+///
+/// The generic `T` type is intended to be `AB::Expr` where `AB` is the [AirBuilder]. The
+/// auxiliary columns are not expected to be expressions, so the generic `V` type is intended to
+/// be `AB::Var`.
+///
+/// [AirBuilder]: crate::openvm_stark_backend::air::AirBuilder
+pub struct MemoryReadOperation<'a, T, V, const N: usize> {
+    bridge: MemoryBridge,
+    address: MemoryAddress<T, T>,
+    data: [T; N],
+    timestamp: T,
+    aux: &'a MemoryReadAuxCols<V>,
+}
+
+impl<T: FieldAlgebra, V: Copy + Into<T>, const N: usize> MemoryReadOperation<'_, T, V, N> {
+    /// Evaluate constraints and send/receive interactions.
+    ///
+    /// Caller must constrain that `enabled` is boolean.
+    pub fn eval<AB>(self, builder: &mut AB, enabled: impl Into<AB::Expr>)
+    where
+        AB: InteractionBuilder<Var = V, Expr = T>,
+    {
+        let enabled = enabled.into();
+        let base = &self.aux.base;
+        self.bridge.eval_timestamp_lt(builder, base, self.timestamp.clone(), enabled.clone());
+
+        let memory_bus = self.bridge.memory_bus;
+        memory_bus
+            .receive::<T>(self.address.clone(), self.data.to_vec(), base.prev_timestamp)
+            .eval(builder, enabled.clone());
+        memory_bus
+            .send::<T>(self.address, self.data.to_vec(), self.timestamp)
+            .eval(builder, enabled);
+    }
+}
+
+/// Constraints and interactions for a logical memory write of `(address, data)` at time
+/// `timestamp`. This reads `(address, data_prev, timestamp_prev)` from the memory bus and writes
+/// `(address, data, timestamp)` to the memory bus. It includes a constraint that
+/// `timestamp_prev < timestamp`.
+pub struct MemoryWriteOperation<'a, T, V, const N: usize> {
+    bridge: MemoryBridge,
+    address: MemoryAddress<T, T>,
+    data: [T; N],
+    timestamp: T,
+    aux: &'a MemoryWriteAuxCols<V, N>,
+}
+
+impl<T: FieldAlgebra, V: Copy + Into<T>, const N: usize> MemoryWriteOperation<'_, T, V, N> {
+    /// Evaluate constraints and send/receive interactions.
+    ///
+    /// Caller must constrain that `enabled` is boolean.
+    pub fn eval<AB>(self, builder: &mut AB, enabled: impl Into<AB::Expr>)
+    where
+        AB: InteractionBuilder<Var = V, Expr = T>,
+    {
+        let enabled = enabled.into();
+        let base = &self.aux.base;
+        self.bridge.eval_timestamp_lt(builder, base, self.timestamp.clone(), enabled.clone());
+
+        let memory_bus = self.bridge.memory_bus;
+        memory_bus
+            .receive::<T>(self.address.clone(), self.aux.prev_data.to_vec(), base.prev_timestamp)
+            .eval(builder, enabled.clone());
+        memory_bus
+            .send::<T>(self.address, self.data.to_vec(), self.timestamp)
+            .eval(builder, enabled);
+    }
+}
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/system/memory/controller/mod.rs
+// for full implementation details.
+
+/// The bit sizes of the memory addresses and timestamps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryConfig {
+    /// Must be at most `range_max_bits` of the range checker.
+    pub address_space_max_bits: usize,
+    /// Must decompose into [AUX_LEN] limbs of `range_max_bits`.
+    pub pointer_max_bits: usize,
+    /// Must decompose into [AUX_LEN] limbs of `range_max_bits`.
+    pub timestamp_max_bits: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryReadRecord {
+    pub address: MemoryAddress<u32, u32>,
+    pub data: [F; MEMORY_BLOCK_SIZE],
+    pub prev_timestamp: u32,
+    pub timestamp: u32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MemoryWriteRecord {
+    pub address: MemoryAddress<u32, u32>,
+    pub data: [F; MEMORY_BLOCK_SIZE],
+    pub prev_data: [F; MEMORY_BLOCK_SIZE],
+    pub prev_timestamp: u32,
+    pub timestamp: u32,
+}
+
+/// The state of a memory block: its data before the first and after the last access, and the
+/// timestamp of the last access.
+#[derive(Clone, Copy, Debug)]
+struct MemoryBlock {
+    initial_data: [F; MEMORY_BLOCK_SIZE],
+    data: [F; MEMORY_BLOCK_SIZE],
+    timestamp: u32,
+}
+
+/// Host-side memory: performs the accesses of the executed instructions, records what the
+/// [MemoryBridge] constrains about them and generates the trace of the [VolatileBoundaryAir]
+/// that balances the memory bus.
+///
+/// Memory is accessed in aligned blocks of [MEMORY_BLOCK_SIZE] cells. Every access advances the
+/// timestamp by one.
+pub struct MemoryController {
+    pub bridge: MemoryBridge,
+    pub boundary_air: VolatileBoundaryAir,
+    pub range_checker: SharedVariableRangeCheckerChip,
+    config: MemoryConfig,
+    timestamp: u32,
+    blocks: BTreeMap<MemoryAddress<u32, u32>, MemoryBlock>,
+}
+
+impl MemoryController {
+    pub fn new(
+        memory_bus: MemoryBus,
+        config: MemoryConfig,
+        range_checker: SharedVariableRangeCheckerChip,
+    ) -> Self {
+        let range_bus = range_checker.bus();
+        Self {
+            bridge: MemoryBridge::new(memory_bus, config.timestamp_max_bits, range_bus),
+            boundary_air: VolatileBoundaryAir::new(
+                memory_bus,
+                range_bus,
+                config.address_space_max_bits,
+                config.pointer_max_bits,
+            ),
+            range_checker,
+            config,
+            timestamp: INITIAL_TIMESTAMP,
+            blocks: BTreeMap::new(),
+        }
+    }
+
+    pub fn config(&self) -> MemoryConfig {
+        self.config
+    }
+
+    /// The timestamp of the next access.
+    pub fn timestamp(&self) -> u32 {
+        self.timestamp
+    }
+
+    /// Returns the fault of the instruction at `pc` if one of its next `accesses` accesses would
+    /// run out of timestamps.
+    pub fn check_timestamps(&self, pc: u32, accesses: u32) -> Result<(), ExecutionError> {
+        let end = u64::from(self.timestamp) + u64::from(accesses);
+        match end <= 1 << self.config.timestamp_max_bits {
+            true => Ok(()),
+            false => Err(ExecutionError::TimestampOverflow { pc, timestamp: self.timestamp }),
+        }
+    }
+
+    /// Returns the fault of the instruction at `pc` if it accesses the block at `pointer`, which
+    /// is beyond the memory or not aligned to a block.
+    pub fn check_pointer(&self, pc: u32, pointer: u32) -> Result<(), ExecutionError> {
+        let pointer_max_bits = self.config.pointer_max_bits;
+        if pointer_max_bits < 32 && pointer >= (1 << pointer_max_bits) {
+            return Err(ExecutionError::AddressOutOfBounds { pc, address: pointer });
+        }
+        match pointer.is_multiple_of(MEMORY_BLOCK_SIZE as u32) {
+            true => Ok(()),
+            false => Err(ExecutionError::MisalignedAccess {
+                pc,
+                address: pointer,
+                width: MEMORY_BLOCK_SIZE,
+            }),
+        }
+    }
+
+    /// Increments the timestamp by `delta` without accessing memory.
+    pub fn increment_timestamp_by(&mut self, delta: u32) {
+        self.timestamp += delta;
+    }
+
+    /// Sets the initial data of the block at `(address_space, pointer)`. Blocks that are never
+    /// set start out as zeros.
+    ///
+    /// Panics if the block has already been accessed.
+    pub fn set_initial(&mut self, address_space: u32, pointer: u32, data: [F; MEMORY_BLOCK_SIZE]) {
+        let address = self.block_address(address_space, pointer);
+        assert!(
+            self.blocks.get(&address).is_none_or(|prev| prev.timestamp == 0),
+            "block at {address_space}:{pointer} is already accessed"
+        );
+        self.blocks.insert(address, MemoryBlock { initial_data: data, data, timestamp: 0 });
+    }
+
+    /// Returns the current data of the block at `(address_space, pointer)` without accessing it.
+    pub fn unsafe_read(&self, address_space: u32, pointer: u32) -> [F; MEMORY_BLOCK_SIZE] {
+        let address = self.block_address(address_space, pointer);
+        self.blocks.get(&address).map_or([F::ZERO; MEMORY_BLOCK_SIZE], |block| block.data)
+    }
+
+    pub fn read(&mut self, address_space: u32, pointer: u32) -> MemoryReadRecord {
+        let address = self.block_address(address_space, pointer);
+        let timestamp = self.next_timestamp();
+        let block = self.blocks.entry(address).or_insert_with(MemoryBlock::zero);
+        let prev_timestamp = core::mem::replace(&mut block.timestamp, timestamp);
+        MemoryReadRecord { address, data: block.data, prev_timestamp, timestamp }
+    }
+
+    pub fn write(
+        &mut self,
+        address_space: u32,
+        pointer: u32,
+        data: [F; MEMORY_BLOCK_SIZE],
+    ) -> MemoryWriteRecord {
+        let address = self.block_address(address_space, pointer);
+        let timestamp = self.next_timestamp();
+        let block = self.blocks.entry(address).or_insert_with(MemoryBlock::zero);
+        let prev_timestamp = core::mem::replace(&mut block.timestamp, timestamp);
+        let prev_data = core::mem::replace(&mut block.data, data);
+        MemoryWriteRecord { address, data, prev_data, prev_timestamp, timestamp }
+    }
+
+    /// Fills the aux columns of a read, recording the range checks of the timestamp comparison.
+    pub fn fill_read_aux(&self, record: &MemoryReadRecord, aux: &mut MemoryReadAuxCols<F>) {
+        self.fill_base_aux(record.prev_timestamp, record.timestamp, &mut aux.base);
+    }
+
+    /// Fills the aux columns of a write, recording the range checks of the timestamp comparison.
+    pub fn fill_write_aux(
+        &self,
+        record: &MemoryWriteRecord,
+        aux: &mut MemoryWriteAuxCols<F, MEMORY_BLOCK_SIZE>,
+    ) {
+        self.fill_base_aux(record.prev_timestamp, record.timestamp, &mut aux.base);
+        aux.prev_data = record.prev_data;
+    }
+
+    /// Fills the base aux columns of a write whose previous data is held in other columns,
+    /// recording the range checks of the timestamp comparison.
+    pub fn fill_write_base_aux(&self, record: &MemoryWriteRecord, aux: &mut MemoryBaseAuxCols<F>) {
+        self.fill_base_aux(record.prev_timestamp, record.timestamp, aux);
+    }
+
+    fn fill_base_aux(&self, prev_timestamp: u32, timestamp: u32, aux: &mut MemoryBaseAuxCols<F>) {
+        aux.prev_timestamp = F::from_canonical_u32(prev_timestamp);
+        let mut out = F::ZERO;
+        self.bridge.timestamp_lt_air.generate_subrow(
+            (&self.range_checker, prev_timestamp, timestamp),
+            (&mut aux.timestamp_lt_aux.lower_decomp, &mut out),
+        );
+        debug_assert_eq!(out, F::ONE);
+    }
+
+    /// Generates the trace of the [VolatileBoundaryAir] with one row per accessed or initialized
+    /// block, sorted by address.
+    pub fn generate_boundary_trace(&self) -> RowMajorMatrix<F> {
+        let records: Vec<_> = self
+            .blocks
+            .iter()
+            .map(|(&address, block)| VolatileBoundaryRecord {
+                address,
+                initial_data: block.initial_data,
+                final_data: block.data,
+                final_timestamp: block.timestamp,
+            })
+            .collect();
+        self.boundary_air.generate_trace(&self.range_checker, &records)
+    }
+
+    fn next_timestamp(&mut self) -> u32 {
+        let timestamp = self.timestamp;
+        assert!(
+            timestamp < (1 << self.config.timestamp_max_bits),
+            "timestamp {timestamp} out of range for {} bits",
+            self.config.timestamp_max_bits
+        );
+        self.timestamp += 1;
+        timestamp
+    }
+
+    fn block_address(&self, address_space: u32, pointer: u32) -> MemoryAddress<u32, u32> {
+        let MemoryConfig { address_space_max_bits, pointer_max_bits, .. } = self.config;
+        assert!(
+            address_space < (1 << address_space_max_bits),
+            "address space {address_space} out of range for {address_space_max_bits} bits"
+        );
+        assert!(
+            pointer < (1 << pointer_max_bits),
+            "pointer {pointer} out of range for {pointer_max_bits} bits"
+        );
+        assert!(
+            pointer.is_multiple_of(MEMORY_BLOCK_SIZE as u32),
+            "unaligned memory access at {address_space}:{pointer}"
+        );
+        MemoryAddress::new(address_space, pointer)
+    }
+}
+
+impl MemoryBlock {
+    fn zero() -> Self {
+        Self {
+            initial_data: [F::ZERO; MEMORY_BLOCK_SIZE],
+            data: [F::ZERO; MEMORY_BLOCK_SIZE],
+            timestamp: 0,
+        }
+    }
+}
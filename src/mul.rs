@@ -0,0 +1,205 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32MultAdapterChip,
+    aligned_borrow,
+    bus::RangeTupleCheckerBus,
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    instructions::{
+        Instruction, LocalOpcode,
+        MulOpcode::{self, MUL},
+    },
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, MinimalInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::BaseAir,
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    range_tuple::SharedRangeTupleCheckerChip,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/mul/core.rs
+// for full implementation details.
+
+pub type Rv32MultiplicationChip = VmChipWrapper<Rv32MultAdapterChip, Rv32MultiplicationCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32MultiplicationCoreCols<T> {
+        pub a: [T; RV32_REGISTER_NUM_LIMBS],
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+        pub c: [T; RV32_REGISTER_NUM_LIMBS],
+        pub is_valid: T,
+    }
+}
+
+/// Constrains `a = b * c` modulo `2^32` by schoolbook multiplication on the limbs, where each
+/// pair `(a[i], carry[i])` is range checked to `[0, 2^8) x [0, sizes[1])` through the tuple
+/// range checker.
+#[derive(Clone, Copy, Debug)]
+pub struct Rv32MultiplicationCoreAir {
+    pub bus: RangeTupleCheckerBus<2>,
+}
+
+impl BaseAir<F> for Rv32MultiplicationCoreAir {
+    fn width(&self) -> usize {
+        Rv32MultiplicationCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32MultiplicationCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<MinimalInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32MultiplicationCoreCols<AB::Var> = local_core.borrow();
+        builder.assert_bool(cols.is_valid);
+
+        let a = &cols.a;
+        let b = &cols.b;
+        let c = &cols.c;
+
+        // Define carry[i] = (sum_{k=0}^{i} b[k] * c[i - k] + carry[i - 1] - a[i]) / 2^LIMB_BITS.
+        // If 0 <= a[i] < 2^LIMB_BITS and carry[i] is small enough, it can be proven that
+        // a[i] = (sum_{k=0}^{i} b[k] * c[i - k] + carry[i - 1]) % 2^LIMB_BITS as necessary.
+        let carry_divide = AB::F::from_canonical_u32(1 << RV32_CELL_BITS).inverse();
+        let mut carry: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            let expected_limb = if i == 0 { AB::Expr::ZERO } else { carry[i - 1].clone() }
+                + (0..=i).fold(AB::Expr::ZERO, |acc, k| acc + (b[k] * c[i - k]));
+            carry[i] = (expected_limb - a[i]) * carry_divide;
+        }
+
+        for (a, carry) in a.iter().zip(carry) {
+            self.bus.send::<AB::Expr, AB::Expr>([(*a).into(), carry]).eval(builder, cols.is_valid);
+        }
+
+        let expected_opcode = VmCoreAir::<AB, I>::opcode_to_global_expr(self, MUL);
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: [cols.b.map(Into::into), cols.c.map(Into::into)].into(),
+            writes: [cols.a.map(Into::into)].into(),
+            instruction: MinimalInstruction {
+                is_valid: cols.is_valid.into(),
+                opcode: expected_opcode,
+            }
+            .into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        MulOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32MultiplicationCoreRecord<F> {
+    pub a: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub c: [F; RV32_REGISTER_NUM_LIMBS],
+}
+
+/// Executes MUL and fills the core columns of [Rv32MultiplicationCoreAir].
+pub struct Rv32MultiplicationCoreChip {
+    pub air: Rv32MultiplicationCoreAir,
+    pub range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+}
+
+impl Rv32MultiplicationCoreChip {
+    pub fn new(range_tuple_chip: SharedRangeTupleCheckerChip<2>) -> Self {
+        let [limb_size, carry_size] = *range_tuple_chip.sizes();
+        assert_eq!(limb_size, 1 << RV32_CELL_BITS, "the first range must be a limb");
+        assert!(
+            carry_size as usize >= RV32_REGISTER_NUM_LIMBS << RV32_CELL_BITS,
+            "the second range {carry_size} is too small for the carries"
+        );
+        Self { air: Rv32MultiplicationCoreAir { bus: range_tuple_chip.bus() }, range_tuple_chip }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32MultiplicationCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32MultiplicationCoreRecord<F>;
+    type Air = Rv32MultiplicationCoreAir;
+
+    /// Executes `a = b * c` and requests the range checks that [Rv32MultiplicationCoreAir] sends
+    /// for it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        assert_eq!(
+            instruction.opcode,
+            MUL.global_opcode(),
+            "opcode {} is not MUL",
+            instruction.opcode
+        );
+        let [b, c] = reads.into();
+        let (a, carry) =
+            run_mul(&b.map(|x| x.as_canonical_u32()), &c.map(|y| y.as_canonical_u32()));
+
+        for (a, carry) in a.into_iter().zip(carry) {
+            self.range_tuple_chip.add_count(&[a, carry]);
+        }
+
+        let a = a.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext::without_pc([a]);
+        (output, Rv32MultiplicationCoreRecord { a, b, c })
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32MultiplicationCoreCols<F> = row_slice.borrow_mut();
+        row_slice.a = record.a;
+        row_slice.b = record.b;
+        row_slice.c = record.c;
+        row_slice.is_valid = F::ONE;
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the limbs of `x * y` modulo `2^32` and the carry out of every limb.
+pub fn run_mul(
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> ([u32; RV32_REGISTER_NUM_LIMBS], [u32; RV32_REGISTER_NUM_LIMBS]) {
+    let mut result = [0; RV32_REGISTER_NUM_LIMBS];
+    let mut carry = [0; RV32_REGISTER_NUM_LIMBS];
+    for i in 0..RV32_REGISTER_NUM_LIMBS {
+        if i > 0 {
+            result[i] = carry[i - 1];
+        }
+        for j in 0..=i {
+            result[i] += x[j] * y[i - j];
+        }
+        carry[i] = result[i] >> RV32_CELL_BITS;
+        result[i] &= RV32_LIMB_MAX;
+    }
+    (result, carry)
+}
@@ -0,0 +1,342 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32MultAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::{BitwiseOperationLookupBus, RangeTupleCheckerBus},
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    instructions::{
+        Instruction, LocalOpcode,
+        MulHOpcode::{self, MULH, MULHSU, MULHU},
+    },
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, MinimalInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    range_tuple::SharedRangeTupleCheckerChip,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/mulh/core.rs
+// for full implementation details.
+
+pub type Rv32MulHChip = VmChipWrapper<Rv32MultAdapterChip, Rv32MulHCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32MulHCoreCols<T> {
+        pub a: [T; RV32_REGISTER_NUM_LIMBS],
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+        pub c: [T; RV32_REGISTER_NUM_LIMBS],
+
+        // The lower half of the 64-bit product, needed for its carries into the upper half.
+        pub a_mul: [T; RV32_REGISTER_NUM_LIMBS],
+        // The limb that sign extends b and c respectively, 0 or 255.
+        pub b_ext: T,
+        pub c_ext: T,
+
+        pub opcode_mulh_flag: T,
+        pub opcode_mulhsu_flag: T,
+        pub opcode_mulhu_flag: T,
+    }
+}
+
+/// Constrains `a` to be the upper 32 bits of the 64-bit product of `b` and `c`, which are
+/// sign extended to 64 bits as the opcode says. The pairs `(limb, carry)` of both halves of the
+/// product are range checked to `[0, 2^8) x [0, sizes[1])` through the tuple range checker.
+#[derive(Clone, Copy)]
+pub struct Rv32MulHCoreAir {
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+    pub range_tuple_bus: RangeTupleCheckerBus<2>,
+}
+
+impl BaseAir<F> for Rv32MulHCoreAir {
+    fn width(&self) -> usize {
+        Rv32MulHCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32MulHCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<MinimalInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32MulHCoreCols<AB::Var> = local_core.borrow();
+        let flags = [cols.opcode_mulh_flag, cols.opcode_mulhsu_flag, cols.opcode_mulhu_flag];
+
+        let is_valid = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| {
+            builder.assert_bool(flag);
+            acc + flag
+        });
+        builder.assert_bool(is_valid.clone());
+
+        let a = &cols.a;
+        let b = &cols.b;
+        let c = &cols.c;
+        let a_mul = &cols.a_mul;
+        let b_ext = cols.b_ext;
+        let c_ext = cols.c_ext;
+
+        // The lower half is constrained as for MUL, with
+        // carry_mul[i] = (sum_{k=0}^{i} b[k] * c[i - k] + carry_mul[i - 1] - a_mul[i]) / 2^8.
+        let carry_divide = AB::F::from_canonical_u32(1 << RV32_CELL_BITS).inverse();
+        let mut carry_mul: [AB::Expr; RV32_REGISTER_NUM_LIMBS] = array::from_fn(|_| AB::Expr::ZERO);
+
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            let expected_limb = if i == 0 { AB::Expr::ZERO } else { carry_mul[i - 1].clone() }
+                + (0..=i).fold(AB::Expr::ZERO, |acc, k| acc + (b[k] * c[i - k]));
+            carry_mul[i] = (expected_limb - a_mul[i]) * carry_divide;
+        }
+
+        for (a_mul, carry_mul) in a_mul.iter().zip(carry_mul.iter()) {
+            self.range_tuple_bus
+                .send::<AB::Expr, AB::Expr>([(*a_mul).into(), carry_mul.clone()])
+                .eval(builder, is_valid.clone());
+        }
+
+        // The upper half continues from the last carry of the lower half, where the limbs of b
+        // and c above the 32 bits are b_ext and c_ext:
+        // carry[j] = (sum_{k=j+1}^{3} b[k] * c[4 + j - k]
+        //     + sum_{k=0}^{j} (b[k] * c_ext + c[k] * b_ext) + carry[j - 1] - a[j]) / 2^8.
+        let mut carry_mulh: [AB::Expr; RV32_REGISTER_NUM_LIMBS] =
+            array::from_fn(|_| AB::Expr::ZERO);
+
+        for j in 0..RV32_REGISTER_NUM_LIMBS {
+            let expected_limb = if j == 0 {
+                carry_mul[RV32_REGISTER_NUM_LIMBS - 1].clone()
+            } else {
+                carry_mulh[j - 1].clone()
+            } + ((j + 1)..RV32_REGISTER_NUM_LIMBS)
+                .fold(AB::Expr::ZERO, |acc, k| acc + (b[k] * c[RV32_REGISTER_NUM_LIMBS + j - k]))
+                + (0..=j).fold(AB::Expr::ZERO, |acc, k| acc + (b[k] * c_ext) + (c[k] * b_ext));
+            carry_mulh[j] = (expected_limb - a[j]) * carry_divide;
+        }
+
+        for (a, carry_mulh) in a.iter().zip(carry_mulh) {
+            self.range_tuple_bus
+                .send::<AB::Expr, AB::Expr>([(*a).into(), carry_mulh])
+                .eval(builder, is_valid.clone());
+        }
+
+        // b_ext and c_ext are 0 or 255, where MULHU treats both and MULHSU treats c as unsigned.
+        let sign_mask = AB::F::from_canonical_u32(1 << (RV32_CELL_BITS - 1));
+        let ext_inv = AB::F::from_canonical_u32(RV32_LIMB_MAX).inverse();
+        let b_sign = b_ext * ext_inv;
+        let c_sign = c_ext * ext_inv;
+
+        builder.assert_bool(b_sign.clone());
+        builder.assert_bool(c_sign.clone());
+        builder.when(cols.opcode_mulhu_flag).assert_zero(b_sign.clone());
+        builder.when(cols.opcode_mulhu_flag + cols.opcode_mulhsu_flag).assert_zero(c_sign.clone());
+
+        // The sign of b, and of c for MULH, is the most significant bit of its top limb: the top
+        // limb less the sign bit is in [0, 128) exactly when doubling it keeps it in [0, 256).
+        self.bitwise_lookup_bus
+            .send_range::<AB::Expr>(
+                (b[RV32_REGISTER_NUM_LIMBS - 1] - b_sign * sign_mask)
+                    * AB::F::from_canonical_u32(2),
+                (c[RV32_REGISTER_NUM_LIMBS - 1] - c_sign * sign_mask)
+                    * (cols.opcode_mulh_flag + AB::Expr::ONE),
+            )
+            .eval(builder, cols.opcode_mulh_flag + cols.opcode_mulhsu_flag);
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            flags.iter().zip(MulHOpcode::ALL).fold(AB::Expr::ZERO, |acc, (&flag, opcode)| {
+                acc + flag * AB::Expr::from_canonical_usize(opcode.local_usize())
+            }),
+        );
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: [cols.b.map(Into::into), cols.c.map(Into::into)].into(),
+            writes: [cols.a.map(Into::into)].into(),
+            instruction: MinimalInstruction { is_valid, opcode: expected_opcode }.into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        MulHOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32MulHCoreRecord<F> {
+    pub opcode: MulHOpcode,
+    pub a: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub c: [F; RV32_REGISTER_NUM_LIMBS],
+    pub a_mul: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b_ext: F,
+    pub c_ext: F,
+}
+
+/// Executes MULH, MULHSU and MULHU and fills the core columns of [Rv32MulHCoreAir].
+pub struct Rv32MulHCoreChip {
+    pub air: Rv32MulHCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+    pub range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+}
+
+impl Rv32MulHCoreChip {
+    pub fn new(
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    ) -> Self {
+        // The sign extension doubles the number of products that add up in a limb of the upper
+        // half, compared to the lower half.
+        let [limb_size, carry_size] = *range_tuple_chip.sizes();
+        assert_eq!(limb_size, 1 << RV32_CELL_BITS, "the first range must be a limb");
+        assert!(
+            carry_size as usize >= (2 * RV32_REGISTER_NUM_LIMBS) << RV32_CELL_BITS,
+            "the second range {carry_size} is too small for the carries"
+        );
+        Self {
+            air: Rv32MulHCoreAir {
+                bitwise_lookup_bus: bitwise_lookup_chip.bus(),
+                range_tuple_bus: range_tuple_chip.bus(),
+            },
+            bitwise_lookup_chip,
+            range_tuple_chip,
+        }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32MulHCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32MulHCoreRecord<F>;
+    type Air = Rv32MulHCoreAir;
+
+    /// Executes the high multiplication `a = (b * c) >> 32` and requests the range checks that
+    /// [Rv32MulHCoreAir] sends for it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let mulh_opcode: MulHOpcode = instruction
+            .opcode
+            .local_opcode()
+            .unwrap_or_else(|| panic!("opcode {} is not a MULH opcode", instruction.opcode));
+        let [b, c] = reads.into();
+        let b_limbs = b.map(|x| x.as_canonical_u32());
+        let c_limbs = c.map(|y| y.as_canonical_u32());
+        let (a, a_mul, carry, b_ext, c_ext) = run_mulh(mulh_opcode, &b_limbs, &c_limbs);
+
+        for (a, carry) in a_mul.iter().chain(&a).zip(carry) {
+            self.range_tuple_chip.add_count(&[*a, carry]);
+        }
+
+        if mulh_opcode != MULHU {
+            let b_sign_mask = if b_ext == 0 { 0 } else { 1 << (RV32_CELL_BITS - 1) };
+            let c_sign_mask = if c_ext == 0 { 0 } else { 1 << (RV32_CELL_BITS - 1) };
+            self.bitwise_lookup_chip.request_range(
+                (b_limbs[RV32_REGISTER_NUM_LIMBS - 1] - b_sign_mask) << 1,
+                (c_limbs[RV32_REGISTER_NUM_LIMBS - 1] - c_sign_mask)
+                    << u32::from(mulh_opcode == MULH),
+            );
+        }
+
+        let a = a.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext::without_pc([a]);
+        let record = Rv32MulHCoreRecord {
+            opcode: mulh_opcode,
+            a,
+            b,
+            c,
+            a_mul: a_mul.map(F::from_canonical_u32),
+            b_ext: F::from_canonical_u32(b_ext),
+            c_ext: F::from_canonical_u32(c_ext),
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32MulHCoreCols<F> = row_slice.borrow_mut();
+        row_slice.a = record.a;
+        row_slice.b = record.b;
+        row_slice.c = record.c;
+        row_slice.a_mul = record.a_mul;
+        row_slice.b_ext = record.b_ext;
+        row_slice.c_ext = record.c_ext;
+        row_slice.opcode_mulh_flag = F::from_bool(record.opcode == MULH);
+        row_slice.opcode_mulhsu_flag = F::from_bool(record.opcode == MULHSU);
+        row_slice.opcode_mulhu_flag = F::from_bool(record.opcode == MULHU);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the limbs of the upper and of the lower half of the 64-bit product of `x` and `y`,
+/// the carries out of the limbs of the lower and then of the upper half, and the limbs that
+/// sign extend `x` and `y` for `opcode`.
+pub fn run_mulh(
+    opcode: MulHOpcode,
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> (
+    [u32; RV32_REGISTER_NUM_LIMBS],
+    [u32; RV32_REGISTER_NUM_LIMBS],
+    [u32; 2 * RV32_REGISTER_NUM_LIMBS],
+    u32,
+    u32,
+) {
+    let mut mul = [0; RV32_REGISTER_NUM_LIMBS];
+    let mut carry = [0; 2 * RV32_REGISTER_NUM_LIMBS];
+    for i in 0..RV32_REGISTER_NUM_LIMBS {
+        if i > 0 {
+            mul[i] = carry[i - 1];
+        }
+        for j in 0..=i {
+            mul[i] += x[j] * y[i - j];
+        }
+        carry[i] = mul[i] >> RV32_CELL_BITS;
+        mul[i] &= RV32_LIMB_MAX;
+    }
+
+    let x_ext = (x[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1))
+        * if opcode == MULHU { 0 } else { RV32_LIMB_MAX };
+    let y_ext = (y[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1))
+        * if opcode == MULH { RV32_LIMB_MAX } else { 0 };
+
+    let mut mulh = [0; RV32_REGISTER_NUM_LIMBS];
+    let mut x_prefix = 0;
+    let mut y_prefix = 0;
+    for i in 0..RV32_REGISTER_NUM_LIMBS {
+        x_prefix += x[i];
+        y_prefix += y[i];
+        mulh[i] = carry[RV32_REGISTER_NUM_LIMBS + i - 1] + x_prefix * y_ext + y_prefix * x_ext;
+        for j in (i + 1)..RV32_REGISTER_NUM_LIMBS {
+            mulh[i] += x[j] * y[RV32_REGISTER_NUM_LIMBS + i - j];
+        }
+        carry[RV32_REGISTER_NUM_LIMBS + i] = mulh[i] >> RV32_CELL_BITS;
+        mulh[i] &= RV32_LIMB_MAX;
+    }
+
+    (mulh, mul, carry, x_ext, y_ext)
+}
@@ -1,11 +1,27 @@
-use super::field::{Field, FieldAlgebra};
+use super::{
+    field::{Field, FieldAlgebra},
+    matrix::{Matrix, RowMajorMatrix},
+};
 use core::ops::{Add, Mul, Sub};
 
 // Please refer to
 // https://github.com/Plonky3/Plonky3/blob/b2f9bf3fbba465f1a04f595ae369889ffd4b66ca/air/src/air.rs#L29
 // for full implementation details.
 
-pub trait AirBuilder {
+pub trait BaseAir<F> {
+    /// The number of columns (a.k.a. registers) in this AIR.
+    fn width(&self) -> usize;
+
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<F>> {
+        None
+    }
+}
+
+pub trait Air<AB: AirBuilder>: BaseAir<AB::F> {
+    fn eval(&self, builder: &mut AB);
+}
+
+pub trait AirBuilder: Sized {
     type F: Field;
 
     type Expr: FieldAlgebra
@@ -31,8 +47,46 @@ pub trait AirBuilder {
         + Mul<Self::Var, Output = Self::Expr>
         + Mul<Self::Expr, Output = Self::Expr>;
 
+    type M: Matrix<Self::Var>;
+
+    /// Returns the local and next rows of the main trace.
+    fn main(&self) -> Self::M;
+
+    fn is_first_row(&self) -> Self::Expr;
+
+    fn is_last_row(&self) -> Self::Expr;
+
+    /// Returns an expression that is one on every row except the last one.
+    fn is_transition(&self) -> Self::Expr;
+
+    /// Returns a sub-builder whose constraints are enforced only when `condition` is nonzero.
+    fn when<I: Into<Self::Expr>>(&mut self, condition: I) -> FilteredAirBuilder<'_, Self> {
+        FilteredAirBuilder { inner: self, condition: condition.into() }
+    }
+
+    fn when_first_row(&mut self) -> FilteredAirBuilder<'_, Self> {
+        let condition = self.is_first_row();
+        self.when(condition)
+    }
+
+    fn when_last_row(&mut self) -> FilteredAirBuilder<'_, Self> {
+        let condition = self.is_last_row();
+        self.when(condition)
+    }
+
+    fn when_transition(&mut self) -> FilteredAirBuilder<'_, Self> {
+        let condition = self.is_transition();
+        self.when(condition)
+    }
+
     fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I);
 
+    fn assert_zeros<const N: usize, I: Into<Self::Expr>>(&mut self, array: [I; N]) {
+        for elem in array {
+            self.assert_zero(elem);
+        }
+    }
+
     fn assert_one<I: Into<Self::Expr>>(&mut self, x: I) {
         self.assert_zero(x.into() - Self::Expr::ONE);
     }
@@ -47,3 +101,47 @@ pub trait AirBuilder {
         self.assert_zero(x.clone() * (x - Self::Expr::ONE));
     }
 }
+
+pub trait PairBuilder: AirBuilder {
+    /// Returns the local and next rows of the preprocessed trace.
+    fn preprocessed(&self) -> Self::M;
+}
+
+pub struct FilteredAirBuilder<'a, AB: AirBuilder> {
+    pub inner: &'a mut AB,
+    condition: AB::Expr,
+}
+
+impl<AB: AirBuilder> FilteredAirBuilder<'_, AB> {
+    pub fn condition(&self) -> AB::Expr {
+        self.condition.clone()
+    }
+}
+
+impl<AB: AirBuilder> AirBuilder for FilteredAirBuilder<'_, AB> {
+    type F = AB::F;
+    type Expr = AB::Expr;
+    type Var = AB::Var;
+    type M = AB::M;
+
+    fn main(&self) -> Self::M {
+        self.inner.main()
+    }
+
+    fn is_first_row(&self) -> Self::Expr {
+        self.inner.is_first_row()
+    }
+
+    fn is_last_row(&self) -> Self::Expr {
+        self.inner.is_last_row()
+    }
+
+    fn is_transition(&self) -> Self::Expr {
+        self.inner.is_transition()
+    }
+
+    fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I) {
+        let condition = self.condition();
+        self.inner.assert_zero(condition * x.into());
+    }
+}
@@ -0,0 +1,135 @@
+use std::collections::HashMap;
+
+use super::{
+    air::{Air, AirBuilder, PairBuilder},
+    field::{F, FieldAlgebra},
+    interaction::{BusIndex, Interaction, InteractionBuilder},
+    matrix::{Matrix, RowMajorMatrix},
+};
+
+// Please refer to
+// https://github.com/openvm-org/stark-backend/blob/main/crates/stark-backend/src/air_builders/debug/mod.rs
+// for full implementation details.
+
+/// An [AirBuilder] that evaluates constraints on concrete trace rows and panics on the first
+/// constraint that does not vanish.
+pub struct DebugConstraintBuilder {
+    row_index: usize,
+    preprocessed: RowMajorMatrix<F>,
+    main: RowMajorMatrix<F>,
+    is_first_row: F,
+    is_last_row: F,
+    is_transition: F,
+    interactions: Vec<Interaction<F>>,
+}
+
+impl AirBuilder for DebugConstraintBuilder {
+    type F = F;
+    type Expr = F;
+    type Var = F;
+    type M = RowMajorMatrix<F>;
+
+    fn main(&self) -> Self::M {
+        self.main.clone()
+    }
+
+    fn is_first_row(&self) -> Self::Expr {
+        self.is_first_row
+    }
+
+    fn is_last_row(&self) -> Self::Expr {
+        self.is_last_row
+    }
+
+    fn is_transition(&self) -> Self::Expr {
+        self.is_transition
+    }
+
+    fn assert_zero<I: Into<Self::Expr>>(&mut self, x: I) {
+        assert_eq!(x.into(), F::ZERO, "constraints had nonzero value on row {}", self.row_index);
+    }
+}
+
+impl PairBuilder for DebugConstraintBuilder {
+    fn preprocessed(&self) -> Self::M {
+        self.preprocessed.clone()
+    }
+}
+
+impl InteractionBuilder for DebugConstraintBuilder {
+    fn push_interaction<E: Into<Self::Expr>>(
+        &mut self,
+        bus_index: BusIndex,
+        fields: impl IntoIterator<Item = E>,
+        count: impl Into<Self::Expr>,
+        count_weight: u32,
+    ) {
+        self.interactions.push(Interaction {
+            message: fields.into_iter().map(Into::into).collect(),
+            count: count.into(),
+            bus_index,
+            count_weight,
+        });
+    }
+}
+
+/// Returns the window of `[local, next]` rows starting at row `r`, wrapping around at the end.
+fn window(trace: &RowMajorMatrix<F>, r: usize) -> RowMajorMatrix<F> {
+    let next = (r + 1) % trace.height();
+    RowMajorMatrix::new([trace.row_slice(r), trace.row_slice(next)].concat(), trace.width())
+}
+
+/// Evaluates the constraints of `air` on every row of `main` and returns the interactions pushed
+/// along the way, with their messages and counts evaluated on the trace.
+///
+/// Panics if a constraint does not hold.
+pub fn check_constraints<A>(air: &A, main: &RowMajorMatrix<F>) -> Vec<Interaction<F>>
+where
+    A: Air<DebugConstraintBuilder>,
+{
+    assert_eq!(main.width(), air.width(), "main trace width does not match the AIR");
+    let height = main.height();
+    let preprocessed = air.preprocessed_trace();
+    if let Some(preprocessed) = &preprocessed {
+        assert_eq!(preprocessed.height(), height, "preprocessed trace height mismatch");
+    }
+
+    let mut builder = DebugConstraintBuilder {
+        row_index: 0,
+        preprocessed: RowMajorMatrix::new(vec![], 0),
+        main: RowMajorMatrix::new(vec![], 0),
+        is_first_row: F::ZERO,
+        is_last_row: F::ZERO,
+        is_transition: F::ZERO,
+        interactions: vec![],
+    };
+    for r in 0..height {
+        builder.row_index = r;
+        builder.main = window(main, r);
+        builder.preprocessed = match &preprocessed {
+            Some(preprocessed) => window(preprocessed, r),
+            None => RowMajorMatrix::new(vec![], 0),
+        };
+        builder.is_first_row = F::from_bool(r == 0);
+        builder.is_last_row = F::from_bool(r == height - 1);
+        builder.is_transition = F::from_bool(r != height - 1);
+        air.eval(&mut builder);
+    }
+    builder.interactions
+}
+
+/// Checks that, for every bus, the counts of each message sum to zero across all `interactions`.
+///
+/// Panics with the first unbalanced message found.
+pub fn verify_interactions(interactions: impl IntoIterator<Item = Interaction<F>>) {
+    let mut balances: HashMap<(BusIndex, Vec<F>), F> = HashMap::new();
+    for Interaction { message, count, bus_index, .. } in interactions {
+        *balances.entry((bus_index, message)).or_default() += count;
+    }
+    let mut unbalanced: Vec<_> =
+        balances.into_iter().filter(|(_, count)| *count != F::ZERO).collect();
+    unbalanced.sort_by_key(|((bus_index, _), _)| *bus_index);
+    if let Some(((bus_index, message), count)) = unbalanced.first() {
+        panic!("bus {bus_index} is unbalanced for message {message:?}: total count {count:?}");
+    }
+}
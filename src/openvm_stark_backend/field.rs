@@ -4,6 +4,7 @@
 
 use core::{
     fmt::Debug,
+    hash::Hash,
     iter::{Product, Sum},
     ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
@@ -27,19 +28,49 @@ pub trait FieldAlgebra:
 
     const ZERO: Self;
     const ONE: Self;
+
+    fn from_bool(b: bool) -> Self;
+
+    fn from_canonical_u8(n: u8) -> Self;
+
+    fn from_canonical_u16(n: u16) -> Self;
+
+    fn from_canonical_u32(n: u32) -> Self;
+
+    fn from_canonical_usize(n: usize) -> Self;
 }
 
-pub trait Field: FieldAlgebra<F = Self> {}
+pub trait Field: FieldAlgebra<F = Self> + Copy + Eq + Hash {
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// The multiplicative inverse of this field element, if it exists.
+    fn try_inverse(&self) -> Option<Self>;
 
-// My own implementation of a field type
-#[derive(Clone, Debug, Copy)]
+    fn inverse(&self) -> Self {
+        self.try_inverse().expect("Tried to invert zero")
+    }
+}
+
+pub trait PrimeField32: Field {
+    const ORDER_U32: u32;
+
+    /// Returns the canonical representative of this field element in `[0, ORDER_U32)`.
+    fn as_canonical_u32(&self) -> u32;
+}
+
+// My own implementation of a field type, over the BabyBear prime `15 * 2^27 + 1`
+#[derive(Clone, Debug, Copy, Default, PartialEq, Eq, Hash)]
 pub struct F {
-    value: i32, // Placeholder for the actual field value type
+    value: i32, // The canonical representative in `[0, P)`, which always fits in an `i32`
 }
 
 impl F {
+    const P: i64 = 0x78000001;
+
     pub fn new(value: i32) -> Self {
-        F { value }
+        Self::reduce(value as i64)
     }
 
     pub fn zero() -> Self {
@@ -47,13 +78,23 @@ impl F {
     }
 
     pub fn from_i32(value: i32) -> Self {
-        F { value }
+        Self::reduce(value as i64)
     }
-}
 
-impl Default for F {
-    fn default() -> Self {
-        F { value: 0 }
+    fn reduce(value: i64) -> Self {
+        F { value: value.rem_euclid(Self::P) as i32 }
+    }
+
+    fn exp_u64(self, mut power: u64) -> Self {
+        let (mut base, mut acc) = (self, F::ONE);
+        while power > 0 {
+            if power & 1 == 1 {
+                acc *= base;
+            }
+            base *= base;
+            power >>= 1;
+        }
+        acc
     }
 }
 
@@ -61,13 +102,13 @@ impl Add for F {
     type Output = F;
 
     fn add(self, rhs: F) -> F {
-        F { value: self.value + rhs.value }
+        Self::reduce(self.value as i64 + rhs.value as i64)
     }
 }
 
 impl AddAssign for F {
     fn add_assign(&mut self, rhs: F) {
-        self.value += rhs.value;
+        *self = *self + rhs;
     }
 }
 
@@ -75,13 +116,13 @@ impl Sub for F {
     type Output = F;
 
     fn sub(self, rhs: F) -> F {
-        F { value: self.value - rhs.value }
+        Self::reduce(self.value as i64 - rhs.value as i64)
     }
 }
 
 impl SubAssign for F {
     fn sub_assign(&mut self, rhs: F) {
-        self.value -= rhs.value;
+        *self = *self - rhs;
     }
 }
 
@@ -89,13 +130,13 @@ impl Mul for F {
     type Output = F;
 
     fn mul(self, rhs: F) -> F {
-        F { value: self.value * rhs.value }
+        Self::reduce(self.value as i64 * rhs.value as i64)
     }
 }
 
 impl MulAssign for F {
     fn mul_assign(&mut self, rhs: F) {
-        self.value *= rhs.value;
+        *self = *self * rhs;
     }
 }
 
@@ -103,7 +144,7 @@ impl Neg for F {
     type Output = F;
 
     fn neg(self) -> F {
-        F { value: -self.value }
+        Self::reduce(-(self.value as i64))
     }
 }
 
@@ -119,11 +160,44 @@ impl Product for F {
     }
 }
 
-impl Field for F {}
+impl Field for F {
+    fn try_inverse(&self) -> Option<Self> {
+        // By Fermat's little theorem, `x^(p - 2)` is the inverse of any non-zero `x`.
+        (!self.is_zero()).then(|| self.exp_u64(Self::P as u64 - 2))
+    }
+}
+
+impl PrimeField32 for F {
+    const ORDER_U32: u32 = F::P as u32;
+
+    fn as_canonical_u32(&self) -> u32 {
+        self.value as u32
+    }
+}
 
 impl FieldAlgebra for F {
     type F = F;
 
     const ZERO: Self = F { value: 0 };
     const ONE: Self = F { value: 1 };
+
+    fn from_bool(b: bool) -> Self {
+        F { value: b as i32 }
+    }
+
+    fn from_canonical_u8(n: u8) -> Self {
+        F { value: n as i32 }
+    }
+
+    fn from_canonical_u16(n: u16) -> Self {
+        F { value: n as i32 }
+    }
+
+    fn from_canonical_u32(n: u32) -> Self {
+        Self::reduce(n as i64)
+    }
+
+    fn from_canonical_usize(n: usize) -> Self {
+        Self::reduce(n as i64)
+    }
 }
@@ -1,4 +1,4 @@
-use crate::openvm_stark_backend::air::AirBuilder;
+use crate::openvm_stark_backend::{air::AirBuilder, field::FieldAlgebra};
 
 // Please refer to
 // https://github.com/openvm-org/stark-backend/blob/main/crates/stark-backend/src/interaction/mod.rs
@@ -6,7 +6,7 @@ use crate::openvm_stark_backend::air::AirBuilder;
 
 pub type BusIndex = usize;
 
-#[derive(Clone, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Interaction<Expr> {
     pub message: Vec<Expr>,
     pub count: Expr,
@@ -44,7 +44,67 @@ pub trait InteractionBuilder: AirBuilder {
     // fn all_interactions(&self) -> &[Interaction<Self::Expr>];
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
+/// A bus on which every message sent must also be received, i.e. the multisets of sent and
+/// received messages are equal.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct PermutationCheckBus {
+    pub index: BusIndex,
+}
+
+impl PermutationCheckBus {
+    pub const fn new(index: BusIndex) -> Self {
+        Self { index }
+    }
+
+    /// Sends `message` with multiplicity `enabled`.
+    ///
+    /// Caller must constrain that `enabled` is boolean.
+    pub fn send<AB, E>(
+        &self,
+        builder: &mut AB,
+        message: impl IntoIterator<Item = E>,
+        enabled: impl Into<AB::Expr>,
+    ) where
+        AB: InteractionBuilder,
+        E: Into<AB::Expr>,
+    {
+        // The multiplicity of a message is at most one per row, so `count_weight = 1` keeps the
+        // total number of sends below p.
+        builder.push_interaction(self.index, message, enabled, 1);
+    }
+
+    /// Receives `message` with multiplicity `enabled`.
+    ///
+    /// Caller must constrain that `enabled` is boolean.
+    pub fn receive<AB, E>(
+        &self,
+        builder: &mut AB,
+        message: impl IntoIterator<Item = E>,
+        enabled: impl Into<AB::Expr>,
+    ) where
+        AB: InteractionBuilder,
+        E: Into<AB::Expr>,
+    {
+        builder.push_interaction(self.index, message, -enabled.into(), 1);
+    }
+
+    /// Sends `message` if `direction = 1` and receives it if `direction = -1`.
+    ///
+    /// Caller must constrain that `direction` is in `{-1, 0, 1}`.
+    pub fn interact<AB, E>(
+        &self,
+        builder: &mut AB,
+        message: impl IntoIterator<Item = E>,
+        direction: impl Into<AB::Expr>,
+    ) where
+        AB: InteractionBuilder,
+        E: Into<AB::Expr>,
+    {
+        builder.push_interaction(self.index, message, direction, 1);
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub struct LookupBus {
     pub index: BusIndex,
 }
@@ -54,6 +114,20 @@ impl LookupBus {
         Self { index }
     }
 
+    /// Returns an interaction asserting that `key` is present in the lookup table. See
+    /// [LookupBus::lookup_key].
+    #[must_use]
+    pub fn lookup<T, const N: usize>(&self, key: [T; N]) -> LookupInteraction<T, N> {
+        LookupInteraction { key, bus: *self, direction: LookupDirection::Lookup }
+    }
+
+    /// Returns an interaction adding `key` to the lookup table. See
+    /// [LookupBus::add_key_with_lookups].
+    #[must_use]
+    pub fn add_key<T, const N: usize>(&self, key: [T; N]) -> LookupInteraction<T, N> {
+        LookupInteraction { key, bus: *self, direction: LookupDirection::AddKey }
+    }
+
     /// Performs a lookup on the given bus.
     ///
     /// This method asserts that `key` is present in the lookup table. The parameter `enabled`
@@ -81,7 +155,6 @@ impl LookupBus {
     /// Adds a key to the lookup table.
     ///
     /// The `num_lookups` parameter should equal the number of enabled lookups performed.
-
     pub fn add_key_with_lookups<AB, E>(
         &self,
         builder: &mut AB,
@@ -96,3 +169,65 @@ impl LookupBus {
         builder.push_interaction(self.index, key, -num_lookups.into(), 0);
     }
 }
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum LookupDirection {
+    /// The key is looked up in the table.
+    Lookup,
+    /// The key is added to the table.
+    AddKey,
+}
+
+/// A not-yet-evaluated interaction on a [LookupBus] with a key of arity `N`.
+#[derive(Clone, Debug)]
+pub struct LookupInteraction<T, const N: usize> {
+    key: [T; N],
+    pub bus: LookupBus,
+    direction: LookupDirection,
+}
+
+impl<T, const N: usize> LookupInteraction<T, N> {
+    pub fn key(&self) -> &[T; N] {
+        &self.key
+    }
+
+    pub fn direction(&self) -> LookupDirection {
+        self.direction
+    }
+
+    pub fn is_lookup(&self) -> bool {
+        self.direction == LookupDirection::Lookup
+    }
+
+    /// Converts into the [Interaction] that [LookupInteraction::eval] pushes to the builder.
+    ///
+    /// For [LookupDirection::AddKey], `count` is the number of lookups of the key and is negated.
+    pub fn into_interaction<E>(self, count: impl Into<E>) -> Interaction<E>
+    where
+        T: Into<E>,
+        E: FieldAlgebra,
+    {
+        // See the comments in `LookupBus::lookup_key` and `LookupBus::add_key_with_lookups` for
+        // the choice of `count_weight`.
+        let (count, count_weight) = match self.direction {
+            LookupDirection::Lookup => (count.into(), 1),
+            LookupDirection::AddKey => (-count.into(), 0),
+        };
+        Interaction {
+            message: self.key.into_iter().map(Into::into).collect(),
+            count,
+            bus_index: self.bus.index,
+            count_weight,
+        }
+    }
+
+    pub fn eval<AB>(self, builder: &mut AB, count: impl Into<AB::Expr>)
+    where
+        AB: InteractionBuilder,
+        T: Into<AB::Expr>,
+    {
+        let Interaction { message, count, bus_index, count_weight } =
+            self.into_interaction::<AB::Expr>(count);
+        builder.push_interaction(bus_index, message, count, count_weight);
+    }
+}
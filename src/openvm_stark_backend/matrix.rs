@@ -0,0 +1,52 @@
+// Please refer to
+// https://github.com/Plonky3/Plonky3/blob/b2f9bf3fbba465f1a04f595ae369889ffd4b66ca/matrix/src/dense.rs
+// for full implementation details.
+
+pub trait Matrix<T> {
+    fn width(&self) -> usize;
+
+    fn height(&self) -> usize;
+
+    /// Returns the `r`-th row of the matrix.
+    fn row_slice(&self, r: usize) -> &[T];
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowMajorMatrix<T> {
+    /// All values, stored in row-major order.
+    pub values: Vec<T>,
+    pub width: usize,
+}
+
+impl<T> RowMajorMatrix<T> {
+    pub fn new(values: Vec<T>, width: usize) -> Self {
+        debug_assert!(width == 0 || values.len().is_multiple_of(width));
+        Self { values, width }
+    }
+
+    pub fn row_mut(&mut self, r: usize) -> &mut [T] {
+        &mut self.values[r * self.width..(r + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.values.chunks_exact(self.width)
+    }
+
+    pub fn rows_mut(&mut self) -> impl Iterator<Item = &mut [T]> {
+        self.values.chunks_exact_mut(self.width)
+    }
+}
+
+impl<T> Matrix<T> for RowMajorMatrix<T> {
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.values.len().checked_div(self.width).unwrap_or(0)
+    }
+
+    fn row_slice(&self, r: usize) -> &[T] {
+        &self.values[r * self.width..(r + 1) * self.width]
+    }
+}
@@ -1,3 +1,5 @@
 pub mod air;
+pub mod debug;
 pub mod field;
 pub mod interaction;
+pub mod matrix;
@@ -0,0 +1,153 @@
+use crate::{
+    bus::ProgramBus,
+    execution::DEFAULT_PC_STEP,
+    instructions::{Instruction, NUM_OPERANDS},
+    openvm_stark_backend::{
+        air::{Air, BaseAir, PairBuilder},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    utils::next_power_of_two_or_zero,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/system/program/mod.rs
+// for full implementation details.
+
+/// The multiplicity of the instruction, i.e. the number of times it was executed.
+pub const NUM_PROGRAM_COLS: usize = 1;
+/// The pc, the opcode and the operands of the instruction.
+pub const NUM_PROGRAM_PREPROCESSED_COLS: usize = 2 + NUM_OPERANDS;
+
+/// A program whose `i`-th instruction is at `pc_base + i * DEFAULT_PC_STEP`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Program<F> {
+    pub instructions: Vec<Instruction<F>>,
+    pub pc_base: u32,
+}
+
+impl<F> Program<F> {
+    pub fn new(instructions: Vec<Instruction<F>>, pc_base: u32) -> Self {
+        Self { instructions, pc_base }
+    }
+
+    pub fn from_instructions(instructions: &[Instruction<F>]) -> Self
+    where
+        F: Clone,
+    {
+        Self::new(instructions.to_vec(), 0)
+    }
+
+    pub fn len(&self) -> usize {
+        self.instructions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instructions.is_empty()
+    }
+
+    /// The pc of the `index`-th instruction.
+    pub fn pc(&self, index: usize) -> u32 {
+        self.pc_base + index as u32 * DEFAULT_PC_STEP
+    }
+
+    /// The index of the instruction at `pc`, if any.
+    pub fn index(&self, pc: u32) -> Option<usize> {
+        let offset = pc.checked_sub(self.pc_base)?;
+        let index = (offset % DEFAULT_PC_STEP == 0).then_some(offset / DEFAULT_PC_STEP)? as usize;
+        (index < self.len()).then_some(index)
+    }
+
+    pub fn get_instruction(&self, pc: u32) -> Option<&Instruction<F>> {
+        self.index(pc).map(|index| &self.instructions[index])
+    }
+}
+
+/// The program table: one preprocessed row `(pc, opcode, a, ..., g)` per instruction, added to
+/// the [ProgramBus] with the number of times the instruction was executed.
+#[derive(Clone, Debug)]
+pub struct ProgramAir {
+    pub bus: ProgramBus,
+    pub program: Program<F>,
+}
+
+impl BaseAir<F> for ProgramAir {
+    fn width(&self) -> usize {
+        NUM_PROGRAM_COLS
+    }
+
+    /// Padding rows are all zeros, which are never looked up with a nonzero multiplicity.
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<F>> {
+        let height = next_power_of_two_or_zero(self.program.len());
+        let mut values = Vec::with_capacity(height * NUM_PROGRAM_PREPROCESSED_COLS);
+        for (i, instruction) in self.program.instructions.iter().enumerate() {
+            values.push(F::from_canonical_u32(self.program.pc(i)));
+            values.push(F::from_canonical_usize(instruction.opcode.as_usize()));
+            values.extend(instruction.operands());
+        }
+        values.resize(height * NUM_PROGRAM_PREPROCESSED_COLS, F::ZERO);
+        Some(RowMajorMatrix::new(values, NUM_PROGRAM_PREPROCESSED_COLS))
+    }
+}
+
+impl<AB> Air<AB> for ProgramAir
+where
+    AB: InteractionBuilder + PairBuilder<F = F>,
+{
+    fn eval(&self, builder: &mut AB) {
+        let preprocessed = builder.preprocessed();
+        let prep_local = preprocessed.row_slice(0);
+        let main = builder.main();
+        let local = main.row_slice(0);
+        self.bus
+            .add_instruction::<AB::Expr>(
+                prep_local[0],
+                prep_local[1],
+                prep_local[2..].iter().copied(),
+            )
+            .eval(builder, local[0]);
+    }
+}
+
+/// Host-side counterpart of [ProgramAir] that fetches instructions and counts how many times
+/// each of them is executed.
+pub struct ProgramChip {
+    pub air: ProgramAir,
+    execution_frequencies: Vec<u32>,
+}
+
+impl ProgramChip {
+    pub fn new(bus: ProgramBus, program: Program<F>) -> Self {
+        let execution_frequencies = vec![0; program.len()];
+        Self { air: ProgramAir { bus, program }, execution_frequencies }
+    }
+
+    pub fn program(&self) -> &Program<F> {
+        &self.air.program
+    }
+
+    pub fn air_width(&self) -> usize {
+        NUM_PROGRAM_COLS
+    }
+
+    /// Returns the instruction at `pc` and counts one execution of it, or `None` if there is no
+    /// instruction at `pc`.
+    pub fn get_instruction(&mut self, pc: u32) -> Option<&Instruction<F>> {
+        let index = self.air.program.index(pc)?;
+        self.execution_frequencies[index] += 1;
+        Some(&self.air.program.instructions[index])
+    }
+
+    /// Generates the multiplicity trace and resets the execution counts.
+    pub fn generate_trace(&mut self) -> RowMajorMatrix<F> {
+        let height = next_power_of_two_or_zero(self.air.program.len());
+        let mut values: Vec<F> = self
+            .execution_frequencies
+            .iter_mut()
+            .map(|count| F::from_canonical_u32(core::mem::take(count)))
+            .collect();
+        values.resize(height * NUM_PROGRAM_COLS, F::ZERO);
+        RowMajorMatrix::new(values, NUM_PROGRAM_COLS)
+    }
+}
@@ -0,0 +1,131 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicU32, Ordering},
+};
+
+use crate::{
+    bus::RangeTupleCheckerBus,
+    openvm_stark_backend::{
+        air::{Air, BaseAir, PairBuilder},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/range_tuple/mod.rs
+// for full implementation details.
+
+/// The multiplicity of the tuple in the same row of the preprocessed trace.
+pub const NUM_RANGE_TUPLE_COLS: usize = 1;
+
+#[derive(Clone, Copy, Debug)]
+pub struct RangeTupleCheckerAir<const N: usize> {
+    pub bus: RangeTupleCheckerBus<N>,
+}
+
+impl<const N: usize> RangeTupleCheckerAir<N> {
+    pub fn sizes(&self) -> &[u32; N] {
+        &self.bus.sizes
+    }
+
+    /// The table has one row per tuple in the product space of the ranges.
+    pub fn height(&self) -> usize {
+        self.bus.sizes.iter().map(|&size| size as usize).product()
+    }
+}
+
+impl<const N: usize> BaseAir<F> for RangeTupleCheckerAir<N> {
+    fn width(&self) -> usize {
+        NUM_RANGE_TUPLE_COLS
+    }
+
+    /// Enumerates the tuples with the first element varying fastest.
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<F>> {
+        let mut tuple = [0u32; N];
+        let mut values = Vec::with_capacity(self.height() * N);
+        for _ in 0..self.height() {
+            values.extend(tuple.map(F::from_canonical_u32));
+            for (elem, &size) in tuple.iter_mut().zip(self.sizes()) {
+                *elem += 1;
+                if *elem < size {
+                    break;
+                }
+                *elem = 0;
+            }
+        }
+        Some(RowMajorMatrix::new(values, N))
+    }
+}
+
+impl<const N: usize, AB> Air<AB> for RangeTupleCheckerAir<N>
+where
+    AB: InteractionBuilder + PairBuilder<F = F>,
+{
+    fn eval(&self, builder: &mut AB) {
+        let preprocessed = builder.preprocessed();
+        let prep_local: [AB::Var; N] = preprocessed.row_slice(0).try_into().unwrap();
+        let main = builder.main();
+        let local = main.row_slice(0);
+        self.bus.receive(prep_local).eval(builder, local[0]);
+    }
+}
+
+/// A [RangeTupleCheckerChip] shared by the chips that request range checks from it.
+pub type SharedRangeTupleCheckerChip<const N: usize> = Arc<RangeTupleCheckerChip<N>>;
+
+/// Host-side counterpart of [RangeTupleCheckerAir] that records the multiplicity of every
+/// requested tuple.
+pub struct RangeTupleCheckerChip<const N: usize> {
+    pub air: RangeTupleCheckerAir<N>,
+    count: Vec<AtomicU32>,
+}
+
+impl<const N: usize> RangeTupleCheckerChip<N> {
+    pub fn new(bus: RangeTupleCheckerBus<N>) -> Self {
+        let air = RangeTupleCheckerAir { bus };
+        let count = (0..air.height()).map(|_| AtomicU32::new(0)).collect();
+        Self { air, count }
+    }
+
+    pub fn bus(&self) -> RangeTupleCheckerBus<N> {
+        self.air.bus
+    }
+
+    pub fn sizes(&self) -> &[u32; N] {
+        self.air.sizes()
+    }
+
+    pub fn air_width(&self) -> usize {
+        NUM_RANGE_TUPLE_COLS
+    }
+
+    /// Records one range check of `ids` against the per-element sizes.
+    pub fn add_count(&self, ids: &[u32; N]) {
+        let mut idx = 0;
+        let mut stride = 1;
+        for (i, (&id, &size)) in ids.iter().zip(self.sizes()).enumerate() {
+            assert!(id < size, "element {i} of tuple {ids:?} out of range for size {size}");
+            idx += id as usize * stride;
+            stride *= size as usize;
+        }
+        self.count[idx].fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn clear(&self) {
+        for count in &self.count {
+            count.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Generates the multiplicity trace and resets all counts.
+    pub fn generate_trace(&self) -> RowMajorMatrix<F> {
+        let values = self
+            .count
+            .iter()
+            .map(|count| F::from_canonical_u32(count.swap(0, Ordering::Relaxed)))
+            .collect();
+        RowMajorMatrix::new(values, NUM_RANGE_TUPLE_COLS)
+    }
+}
@@ -0,0 +1,430 @@
+use core::{
+    array,
+    borrow::{Borrow, BorrowMut},
+};
+
+use crate::{
+    adapters::Rv32BaseAluAdapterChip,
+    aligned_borrow,
+    bitwise_op_lookup::SharedBitwiseOperationLookupChip,
+    bus::{BitwiseOperationLookupBus, VariableRangeCheckerBus},
+    core::{RV32_CELL_BITS, RV32_LIMB_MAX, RV32_REGISTER_NUM_LIMBS},
+    instructions::{Instruction, LocalOpcode, ShiftOpcode},
+    integration_api::{
+        AdapterAirContext, AdapterRuntimeContext, MinimalInstruction, VmAdapterInterface,
+        VmChipWrapper, VmCoreAir, VmCoreChip,
+    },
+    openvm_stark_backend::{
+        air::{AirBuilder, BaseAir},
+        field::{F, Field, FieldAlgebra, PrimeField32},
+        interaction::InteractionBuilder,
+    },
+    utils::not,
+    var_range::SharedVariableRangeCheckerChip,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/extensions/rv32im/circuit/src/shift/core.rs
+// for full implementation details.
+
+/// The number of bits of a register, which the shift amount is reduced modulo.
+const RV32_REGISTER_BITS: usize = RV32_REGISTER_NUM_LIMBS * RV32_CELL_BITS;
+
+pub type Rv32ShiftChip = VmChipWrapper<Rv32BaseAluAdapterChip, Rv32ShiftCoreChip>;
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct Rv32ShiftCoreCols<T> {
+        pub a: [T; RV32_REGISTER_NUM_LIMBS],
+        pub b: [T; RV32_REGISTER_NUM_LIMBS],
+        pub c: [T; RV32_REGISTER_NUM_LIMBS],
+
+        pub opcode_sll_flag: T,
+        pub opcode_srl_flag: T,
+        pub opcode_sra_flag: T,
+
+        // bit_multiplier = 2^bit_shift
+        pub bit_multiplier_left: T,
+        pub bit_multiplier_right: T,
+
+        // Sign of x for SRA
+        pub b_sign: T,
+
+        // Boolean columns that are 1 exactly at the index of the bit/limb shift amount
+        pub bit_shift_marker: [T; RV32_CELL_BITS],
+        pub limb_shift_marker: [T; RV32_REGISTER_NUM_LIMBS],
+
+        // Part of each x[i] that gets bit shifted to the next limb
+        pub bit_shift_carry: [T; RV32_REGISTER_NUM_LIMBS],
+    }
+}
+
+/// Constrains `a = b << c`, `a = b >> c` (logical) or `a = b >> c` (arithmetic), where the shift
+/// amount is `c` modulo 32. The shift is split into a limb shift and a bit shift, both given as
+/// one-hot markers.
+#[derive(Clone, Copy)]
+pub struct Rv32ShiftCoreAir {
+    pub bitwise_lookup_bus: BitwiseOperationLookupBus<RV32_CELL_BITS>,
+    pub range_bus: VariableRangeCheckerBus,
+}
+
+impl BaseAir<F> for Rv32ShiftCoreAir {
+    fn width(&self) -> usize {
+        Rv32ShiftCoreCols::<F>::width()
+    }
+}
+
+impl<AB, I> VmCoreAir<AB, I> for Rv32ShiftCoreAir
+where
+    AB: InteractionBuilder<F = F>,
+    I: VmAdapterInterface<AB::Expr>,
+    I::Reads: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[AB::Expr; RV32_REGISTER_NUM_LIMBS]; 1]>,
+    I::ProcessedInstruction: From<MinimalInstruction<AB::Expr>>,
+{
+    fn eval(
+        &self,
+        builder: &mut AB,
+        local_core: &[AB::Var],
+        _from_pc: AB::Var,
+    ) -> AdapterAirContext<AB::Expr, I> {
+        let cols: &Rv32ShiftCoreCols<AB::Var> = local_core.borrow();
+        let flags = [cols.opcode_sll_flag, cols.opcode_srl_flag, cols.opcode_sra_flag];
+
+        let is_valid = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| {
+            builder.assert_bool(flag);
+            acc + flag
+        });
+        builder.assert_bool(is_valid.clone());
+
+        let a = &cols.a;
+        let b = &cols.b;
+        let c = &cols.c;
+        let right_shift = cols.opcode_srl_flag + cols.opcode_sra_flag;
+
+        // Constrain that bit_shift, bit_multiplier are correct, i.e. that bit_multiplier =
+        // 1 << bit_shift. Because the sum of all bit_shift_marker[i] is constrained to be 1,
+        // bit_shift is guaranteed to be in range.
+        let mut bit_marker_sum = AB::Expr::ZERO;
+        let mut bit_shift = AB::Expr::ZERO;
+
+        for i in 0..RV32_CELL_BITS {
+            builder.assert_bool(cols.bit_shift_marker[i]);
+            bit_marker_sum += cols.bit_shift_marker[i].into();
+            bit_shift += AB::Expr::from_canonical_usize(i) * cols.bit_shift_marker[i];
+
+            let mut when_bit_shift = builder.when(cols.bit_shift_marker[i]);
+            when_bit_shift.assert_eq(
+                cols.bit_multiplier_left,
+                AB::Expr::from_canonical_usize(1 << i) * cols.opcode_sll_flag,
+            );
+            when_bit_shift.assert_eq(
+                cols.bit_multiplier_right,
+                AB::Expr::from_canonical_usize(1 << i) * right_shift.clone(),
+            );
+        }
+        builder.when(is_valid.clone()).assert_one(bit_marker_sum);
+
+        // Check that a[i] = b[i] <</>> c[i] both on the bit and limb shift level if c <
+        // RV32_REGISTER_BITS.
+        let mut limb_marker_sum = AB::Expr::ZERO;
+        let mut limb_shift = AB::Expr::ZERO;
+        let limb_size = AB::F::from_canonical_usize(1 << RV32_CELL_BITS);
+        for i in 0..RV32_REGISTER_NUM_LIMBS {
+            builder.assert_bool(cols.limb_shift_marker[i]);
+            limb_marker_sum += cols.limb_shift_marker[i].into();
+            limb_shift += AB::Expr::from_canonical_usize(i) * cols.limb_shift_marker[i];
+
+            let mut when_limb_shift = builder.when(cols.limb_shift_marker[i]);
+
+            for j in 0..RV32_REGISTER_NUM_LIMBS {
+                // SLL constraints
+                if j < i {
+                    when_limb_shift.assert_zero(a[j] * cols.opcode_sll_flag);
+                } else {
+                    let expected_a_left = if j - i == 0 {
+                        AB::Expr::ZERO
+                    } else {
+                        cols.bit_shift_carry[j - i - 1] * cols.opcode_sll_flag
+                    } + b[j - i] * cols.bit_multiplier_left
+                        - AB::Expr::from(limb_size)
+                            * cols.bit_shift_carry[j - i]
+                            * cols.opcode_sll_flag;
+                    when_limb_shift.assert_eq(a[j] * cols.opcode_sll_flag, expected_a_left);
+                }
+
+                // SRL and SRA constraints. Combining with above would require an additional
+                // column.
+                if j + i > RV32_REGISTER_NUM_LIMBS - 1 {
+                    when_limb_shift.assert_eq(
+                        a[j] * right_shift.clone(),
+                        cols.b_sign * AB::F::from_canonical_u32(RV32_LIMB_MAX),
+                    );
+                } else {
+                    let expected_a_right = if j + i == RV32_REGISTER_NUM_LIMBS - 1 {
+                        cols.b_sign * (cols.bit_multiplier_right - AB::F::ONE)
+                    } else {
+                        cols.bit_shift_carry[j + i + 1] * right_shift.clone()
+                    } * limb_size
+                        + right_shift.clone() * (b[j + i] - cols.bit_shift_carry[j + i]);
+                    when_limb_shift.assert_eq(a[j] * cols.bit_multiplier_right, expected_a_right);
+                }
+            }
+        }
+        builder.when(is_valid.clone()).assert_one(limb_marker_sum);
+
+        // Check that bit_shift and limb_shift are correct, i.e. that c[0] is the shift amount
+        // plus a multiple of RV32_REGISTER_BITS.
+        let num_bits = AB::F::from_canonical_usize(RV32_REGISTER_BITS);
+        self.range_bus
+            .range_check::<AB::Expr>(
+                (c[0]
+                    - limb_shift * AB::F::from_canonical_usize(RV32_CELL_BITS)
+                    - bit_shift.clone())
+                    * num_bits.inverse(),
+                RV32_CELL_BITS - RV32_REGISTER_BITS.ilog2() as usize,
+            )
+            .eval(builder, is_valid.clone());
+
+        // Check b_sign & b[RV32_REGISTER_NUM_LIMBS - 1] == b_sign using XOR
+        builder.assert_bool(cols.b_sign);
+        builder.when(not::<AB::Expr>(cols.opcode_sra_flag)).assert_zero(cols.b_sign);
+
+        let mask = AB::F::from_canonical_u32(1 << (RV32_CELL_BITS - 1));
+        let b_sign_shifted = cols.b_sign * mask;
+        self.bitwise_lookup_bus
+            .send_xor::<AB::Expr>(
+                b[RV32_REGISTER_NUM_LIMBS - 1],
+                mask,
+                b[RV32_REGISTER_NUM_LIMBS - 1] + mask
+                    - (AB::Expr::from_canonical_u32(2) * b_sign_shifted),
+            )
+            .eval(builder, cols.opcode_sra_flag);
+
+        for i in 0..(RV32_REGISTER_NUM_LIMBS / 2) {
+            self.bitwise_lookup_bus
+                .send_range::<AB::Expr>(a[i * 2], a[i * 2 + 1])
+                .eval(builder, is_valid.clone());
+        }
+
+        for carry in cols.bit_shift_carry {
+            self.range_bus
+                .send::<AB::Expr>(carry, bit_shift.clone())
+                .eval(builder, is_valid.clone());
+        }
+
+        let expected_opcode = VmCoreAir::<AB, I>::expr_to_global_expr(
+            self,
+            flags.iter().zip(ShiftOpcode::ALL).fold(AB::Expr::ZERO, |acc, (&flag, opcode)| {
+                acc + flag * AB::Expr::from_canonical_usize(opcode.local_usize())
+            }),
+        );
+
+        AdapterAirContext {
+            to_pc: None,
+            reads: [cols.b.map(Into::into), cols.c.map(Into::into)].into(),
+            writes: [cols.a.map(Into::into)].into(),
+            instruction: MinimalInstruction { is_valid, opcode: expected_opcode }.into(),
+        }
+    }
+
+    fn start_offset(&self) -> usize {
+        ShiftOpcode::CLASS_OFFSET
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rv32ShiftCoreRecord<F> {
+    pub opcode: ShiftOpcode,
+    pub a: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b: [F; RV32_REGISTER_NUM_LIMBS],
+    pub c: [F; RV32_REGISTER_NUM_LIMBS],
+    pub b_sign: F,
+    pub bit_shift_carry: [u32; RV32_REGISTER_NUM_LIMBS],
+    pub bit_shift: usize,
+    pub limb_shift: usize,
+}
+
+/// Executes SLL, SRL and SRA and fills the core columns of [Rv32ShiftCoreAir].
+pub struct Rv32ShiftCoreChip {
+    pub air: Rv32ShiftCoreAir,
+    pub bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+    pub range_checker_chip: SharedVariableRangeCheckerChip,
+}
+
+impl Rv32ShiftCoreChip {
+    pub fn new(
+        bitwise_lookup_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+        range_checker_chip: SharedVariableRangeCheckerChip,
+    ) -> Self {
+        assert!(
+            range_checker_chip.range_max_bits() >= RV32_CELL_BITS,
+            "the range checker must check at least {RV32_CELL_BITS} bits"
+        );
+        Self {
+            air: Rv32ShiftCoreAir {
+                bitwise_lookup_bus: bitwise_lookup_chip.bus(),
+                range_bus: range_checker_chip.bus(),
+            },
+            bitwise_lookup_chip,
+            range_checker_chip,
+        }
+    }
+}
+
+impl<I> VmCoreChip<I> for Rv32ShiftCoreChip
+where
+    I: VmAdapterInterface<F>,
+    I::Reads: Into<[[F; RV32_REGISTER_NUM_LIMBS]; 2]>,
+    I::Writes: From<[[F; RV32_REGISTER_NUM_LIMBS]; 1]>,
+{
+    type Record = Rv32ShiftCoreRecord<F>;
+    type Air = Rv32ShiftCoreAir;
+
+    /// Executes `a = b op c` and requests the lookups and range checks that [Rv32ShiftCoreAir]
+    /// sends for it.
+    fn execute_instruction(
+        &self,
+        instruction: &Instruction<F>,
+        _from_pc: u32,
+        reads: I::Reads,
+    ) -> (AdapterRuntimeContext<F, I>, Self::Record) {
+        let shift_opcode: ShiftOpcode = instruction
+            .opcode
+            .local_opcode()
+            .unwrap_or_else(|| panic!("opcode {} is not a shift opcode", instruction.opcode));
+        let [b, c] = reads.into();
+        let b_limbs = b.map(|x| x.as_canonical_u32());
+        let c_limbs = c.map(|x| x.as_canonical_u32());
+        let (a_limbs, limb_shift, bit_shift) = run_shift(shift_opcode, &b_limbs, &c_limbs);
+
+        let bit_shift_carry = array::from_fn(|i| match shift_opcode {
+            ShiftOpcode::SLL => b_limbs[i] >> (RV32_CELL_BITS - bit_shift),
+            ShiftOpcode::SRL | ShiftOpcode::SRA => b_limbs[i] % (1 << bit_shift),
+        });
+
+        let mut b_sign = 0;
+        if shift_opcode == ShiftOpcode::SRA {
+            b_sign = b_limbs[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1);
+            self.bitwise_lookup_chip
+                .request_xor(b_limbs[RV32_REGISTER_NUM_LIMBS - 1], 1 << (RV32_CELL_BITS - 1));
+        }
+
+        for i in 0..(RV32_REGISTER_NUM_LIMBS / 2) {
+            self.bitwise_lookup_chip.request_range(a_limbs[i * 2], a_limbs[i * 2 + 1]);
+        }
+
+        let num_bits_log = RV32_REGISTER_BITS.ilog2();
+        self.range_checker_chip.add_count(
+            ((c_limbs[0] as usize - bit_shift - limb_shift * RV32_CELL_BITS) >> num_bits_log)
+                as u32,
+            RV32_CELL_BITS - num_bits_log as usize,
+        );
+        for carry_val in bit_shift_carry {
+            self.range_checker_chip.add_count(carry_val, bit_shift);
+        }
+
+        let a = a_limbs.map(F::from_canonical_u32);
+        let output = AdapterRuntimeContext::without_pc([a]);
+        let record = Rv32ShiftCoreRecord {
+            opcode: shift_opcode,
+            a,
+            b,
+            c,
+            b_sign: F::from_canonical_u32(b_sign),
+            bit_shift_carry,
+            bit_shift,
+            limb_shift,
+        };
+        (output, record)
+    }
+
+    fn generate_trace_row(&self, row_slice: &mut [F], record: Self::Record) {
+        let row_slice: &mut Rv32ShiftCoreCols<F> = row_slice.borrow_mut();
+        row_slice.a = record.a;
+        row_slice.b = record.b;
+        row_slice.c = record.c;
+        row_slice.bit_multiplier_left = match record.opcode {
+            ShiftOpcode::SLL => F::from_canonical_usize(1 << record.bit_shift),
+            _ => F::ZERO,
+        };
+        row_slice.bit_multiplier_right = match record.opcode {
+            ShiftOpcode::SLL => F::ZERO,
+            _ => F::from_canonical_usize(1 << record.bit_shift),
+        };
+        row_slice.b_sign = record.b_sign;
+        row_slice.bit_shift_marker = array::from_fn(|i| F::from_bool(i == record.bit_shift));
+        row_slice.limb_shift_marker = array::from_fn(|i| F::from_bool(i == record.limb_shift));
+        row_slice.bit_shift_carry = record.bit_shift_carry.map(F::from_canonical_u32);
+        row_slice.opcode_sll_flag = F::from_bool(record.opcode == ShiftOpcode::SLL);
+        row_slice.opcode_srl_flag = F::from_bool(record.opcode == ShiftOpcode::SRL);
+        row_slice.opcode_sra_flag = F::from_bool(record.opcode == ShiftOpcode::SRA);
+    }
+
+    fn air(&self) -> &Self::Air {
+        &self.air
+    }
+}
+
+/// Returns the limbs of `x op y` for the little-endian limbs `x` and `y`, together with the limb
+/// and the bit parts of the shift amount.
+pub fn run_shift(
+    opcode: ShiftOpcode,
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> ([u32; RV32_REGISTER_NUM_LIMBS], usize, usize) {
+    match opcode {
+        ShiftOpcode::SLL => run_shift_left(x, y),
+        ShiftOpcode::SRL => run_shift_right(x, y, true),
+        ShiftOpcode::SRA => run_shift_right(x, y, false),
+    }
+}
+
+fn run_shift_left(
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+) -> ([u32; RV32_REGISTER_NUM_LIMBS], usize, usize) {
+    let mut result = [0u32; RV32_REGISTER_NUM_LIMBS];
+
+    let (limb_shift, bit_shift) = get_shift(y);
+
+    for i in limb_shift..RV32_REGISTER_NUM_LIMBS {
+        result[i] = if i > limb_shift {
+            ((x[i - limb_shift] << bit_shift)
+                + (x[i - limb_shift - 1] >> (RV32_CELL_BITS - bit_shift)))
+                & RV32_LIMB_MAX
+        } else {
+            (x[i - limb_shift] << bit_shift) & RV32_LIMB_MAX
+        };
+    }
+    (result, limb_shift, bit_shift)
+}
+
+fn run_shift_right(
+    x: &[u32; RV32_REGISTER_NUM_LIMBS],
+    y: &[u32; RV32_REGISTER_NUM_LIMBS],
+    logical: bool,
+) -> ([u32; RV32_REGISTER_NUM_LIMBS], usize, usize) {
+    let fill = if logical {
+        0
+    } else {
+        RV32_LIMB_MAX * (x[RV32_REGISTER_NUM_LIMBS - 1] >> (RV32_CELL_BITS - 1))
+    };
+    let mut result = [fill; RV32_REGISTER_NUM_LIMBS];
+
+    let (limb_shift, bit_shift) = get_shift(y);
+
+    for i in 0..(RV32_REGISTER_NUM_LIMBS - limb_shift) {
+        let next =
+            if i + limb_shift + 1 < RV32_REGISTER_NUM_LIMBS { x[i + limb_shift + 1] } else { fill };
+        result[i] = ((x[i + limb_shift] >> bit_shift) + (next << (RV32_CELL_BITS - bit_shift)))
+            & RV32_LIMB_MAX;
+    }
+    (result, limb_shift, bit_shift)
+}
+
+fn get_shift(y: &[u32; RV32_REGISTER_NUM_LIMBS]) -> (usize, usize) {
+    // RV32_REGISTER_BITS <= 2^RV32_CELL_BITS, so the shift is defined entirely in y[0].
+    let shift = (y[0] as usize) % RV32_REGISTER_BITS;
+    (shift / RV32_CELL_BITS, shift % RV32_CELL_BITS)
+}
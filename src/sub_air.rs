@@ -0,0 +1,45 @@
+use crate::openvm_stark_backend::air::AirBuilder;
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/sub_air.rs
+// for full implementation details.
+
+/// Trait with associated types intended to allow re-use of constraint logic inside other AIRs.
+///
+/// A `SubAir` is a gadget: it evaluates constraints on a subset of the columns of the AIR that
+/// uses it, and does not own a trace of its own. The context is usually a pair `(io, aux)` where
+/// `io` holds expressions supplied by the caller and `aux` borrows the gadget's auxiliary columns.
+pub trait SubAir<AB: AirBuilder> {
+    /// Type to define the context, typically in terms of `AB::Expr` and `AB::Var`, that is passed
+    /// to [SubAir::eval].
+    type AirContext<'a>
+    where
+        Self: 'a,
+        AB: 'a,
+        AB::Var: 'a,
+        AB::Expr: 'a;
+
+    fn eval<'a>(&'a self, builder: &'a mut AB, ctx: Self::AirContext<'a>)
+    where
+        AB::Var: 'a,
+        AB::Expr: 'a;
+}
+
+/// Trace generation counterpart of [SubAir]: fills the gadget's auxiliary columns in one row of
+/// the parent AIR's trace.
+pub trait TraceSubRowGenerator<T> {
+    /// The information needed to fill the sub-row, typically the values of the `io` passed to
+    /// [SubAir::eval] together with any chips that must record lookups.
+    type TraceContext<'a>
+    where
+        Self: 'a,
+        T: 'a;
+
+    /// The mutable view of the sub-row to fill.
+    type ColsMut<'a>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn generate_subrow<'a>(&'a self, ctx: Self::TraceContext<'a>, sub_row: Self::ColsMut<'a>);
+}
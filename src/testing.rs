@@ -0,0 +1,260 @@
+use core::borrow::BorrowMut;
+use std::sync::Arc;
+
+use crate::{
+    adapters::RV32_REGISTER_AS,
+    aligned_borrow::AlignedBorrow,
+    bitwise_op_lookup::{BitwiseOperationLookupChip, SharedBitwiseOperationLookupChip},
+    bus::{
+        BitwiseOperationLookupBus, ExecutionBus, MemoryBus, ProgramBus, RangeTupleCheckerBus,
+        VariableRangeCheckerBus,
+    },
+    core::{RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS},
+    execution::{ExecutionBridge, ExecutionState},
+    instructions::Instruction,
+    integration_api::{VmAdapterChip, VmAirWrapper, VmChipWrapper, VmCoreChip},
+    memory::{MemoryBridge, MemoryConfig, MemoryController},
+    openvm_stark_backend::{
+        air::Air,
+        debug::{DebugConstraintBuilder, check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::Interaction,
+        matrix::RowMajorMatrix,
+    },
+    var_range::{SharedVariableRangeCheckerChip, VariableRangeCheckerChip},
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/arch/testing/mod.rs
+// for full implementation details.
+
+pub const BITWISE_OP_LOOKUP_BUS: BitwiseOperationLookupBus<RV32_CELL_BITS> =
+    BitwiseOperationLookupBus::new(0);
+pub const RANGE_CHECKER_BUS: VariableRangeCheckerBus = VariableRangeCheckerBus::new(1, 8);
+pub const MEMORY_BUS: MemoryBus = MemoryBus::new(2);
+pub const EXECUTION_BUS: ExecutionBus = ExecutionBus::new(3);
+pub const PROGRAM_BUS: ProgramBus = ProgramBus::new(4);
+/// Wide enough for the carries of the high multiplication, whose chip is the only one that needs
+/// its own range tuple checker in tests.
+pub const RANGE_TUPLE_CHECKER_BUS: RangeTupleCheckerBus<2> =
+    RangeTupleCheckerBus::new(5, [1 << RV32_CELL_BITS, 8 << RV32_CELL_BITS]);
+
+pub const TEST_MEMORY_CONFIG: MemoryConfig =
+    MemoryConfig { address_space_max_bits: 2, pointer_max_bits: 16, timestamp_max_bits: 16 };
+
+/// Executes single instructions on a chip and balances everything the chip's AIR sends: it
+/// stands in for the program and the other chips on the execution bus, and owns the memory and
+/// the lookup chips.
+pub struct VmChipTestBuilder {
+    pub memory: MemoryController,
+    pub range_checker: SharedVariableRangeCheckerChip,
+    pub bitwise_chip: SharedBitwiseOperationLookupChip<RV32_CELL_BITS>,
+    /// The program and execution bus interactions of the executed instructions, and the memory
+    /// bus interactions of the writes done by the tester.
+    interactions: Vec<Interaction<F>>,
+}
+
+impl Default for VmChipTestBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VmChipTestBuilder {
+    pub fn new() -> Self {
+        let range_checker = Arc::new(VariableRangeCheckerChip::new(RANGE_CHECKER_BUS));
+        Self {
+            memory: MemoryController::new(MEMORY_BUS, TEST_MEMORY_CONFIG, range_checker.clone()),
+            range_checker,
+            bitwise_chip: Arc::new(BitwiseOperationLookupChip::new(BITWISE_OP_LOOKUP_BUS)),
+            interactions: Vec::new(),
+        }
+    }
+
+    pub fn execution_bridge(&self) -> ExecutionBridge {
+        ExecutionBridge::new(EXECUTION_BUS, PROGRAM_BUS)
+    }
+
+    pub fn memory_bridge(&self) -> MemoryBridge {
+        self.memory.bridge
+    }
+
+    /// Sets the initial value of register `reg`. Must be called before any access to it.
+    pub fn set_register(&mut self, reg: usize, value: [u32; RV32_REGISTER_NUM_LIMBS]) {
+        let pointer = (reg * RV32_REGISTER_NUM_LIMBS) as u32;
+        self.memory.set_initial(RV32_REGISTER_AS, pointer, value.map(F::from_canonical_u32));
+    }
+
+    /// Writes `value` to register `reg` as an instruction would, and balances the memory bus
+    /// interactions of the write. Unlike [VmChipTestBuilder::set_register], this can be called at
+    /// any time.
+    pub fn write_register(&mut self, reg: usize, value: [u32; RV32_REGISTER_NUM_LIMBS]) {
+        let pointer = (reg * RV32_REGISTER_NUM_LIMBS) as u32;
+        let record = self.memory.write(RV32_REGISTER_AS, pointer, value.map(F::from_canonical_u32));
+        for (data, timestamp, count) in [
+            (record.prev_data, record.prev_timestamp, -F::ONE),
+            (record.data, record.timestamp, F::ONE),
+        ] {
+            let address = [RV32_REGISTER_AS, pointer].map(F::from_canonical_u32);
+            self.interactions.push(Interaction {
+                message: address
+                    .into_iter()
+                    .chain(data)
+                    .chain([F::from_canonical_u32(timestamp)])
+                    .collect(),
+                count,
+                bus_index: MEMORY_BUS.inner.index,
+                count_weight: 1,
+            });
+        }
+    }
+
+    /// Returns the current value of register `reg` without accessing it.
+    pub fn read_register(&self, reg: usize) -> [F; RV32_REGISTER_NUM_LIMBS] {
+        self.memory.unsafe_read(RV32_REGISTER_AS, (reg * RV32_REGISTER_NUM_LIMBS) as u32)
+    }
+
+    /// Executes `instruction` on `chip` at `pc` and returns the execution state after it.
+    pub fn execute<A, C>(
+        &mut self,
+        chip: &mut VmChipWrapper<A, C>,
+        instruction: &Instruction<F>,
+        pc: u32,
+    ) -> ExecutionState<u32>
+    where
+        A: VmAdapterChip,
+        C: VmCoreChip<A::Interface>,
+    {
+        let from_state = ExecutionState::new(pc, self.memory.timestamp());
+        let to_state = chip.execute(&mut self.memory, instruction, from_state);
+
+        let opcode = F::from_canonical_usize(instruction.opcode.as_usize());
+        self.interactions.push(
+            PROGRAM_BUS
+                .add_instruction::<F>(F::from_canonical_u32(pc), opcode, instruction.operands())
+                .into_interaction(F::ONE),
+        );
+        for (state, count) in [(from_state, F::ONE), (to_state, -F::ONE)] {
+            self.interactions.push(Interaction {
+                message: vec![
+                    F::from_canonical_u32(state.pc),
+                    F::from_canonical_u32(state.timestamp),
+                ],
+                count,
+                bus_index: EXECUTION_BUS.inner.index,
+                count_weight: 1,
+            });
+        }
+        to_state
+    }
+
+    /// Checks the constraints of the memory boundary and of the lookup chips, and that all buses
+    /// are balanced together with `chip_interactions`.
+    pub fn verify(self, chip_interactions: Vec<Interaction<F>>) {
+        let mut interactions = chip_interactions;
+        interactions.extend(self.interactions);
+        interactions.extend(check_constraints(
+            &self.memory.boundary_air,
+            &self.memory.generate_boundary_trace(),
+        ));
+        interactions
+            .extend(check_constraints(&self.bitwise_chip.air, &self.bitwise_chip.generate_trace()));
+        interactions.extend(check_constraints(
+            &self.range_checker.air,
+            &self.range_checker.generate_trace(),
+        ));
+        verify_interactions(interactions);
+    }
+
+    /// Like [VmChipTestBuilder::verify] for a single chip, after applying `modify_trace` to the
+    /// trace of `chip`.
+    pub fn verify_chip<A, C>(
+        self,
+        chip: &mut VmChipWrapper<A, C>,
+        modify_trace: impl FnOnce(&mut RowMajorMatrix<F>),
+    ) where
+        A: VmAdapterChip,
+        C: VmCoreChip<A::Interface>,
+        VmAirWrapper<A::Air, C::Air>: Air<DebugConstraintBuilder>,
+    {
+        let mut trace = chip.generate_trace(&self.memory);
+        modify_trace(&mut trace);
+        let interactions = check_constraints(&chip.air(), &trace);
+        self.verify(interactions);
+    }
+
+    /// Like [VmChipTestBuilder::verify_chip] for a trace that `modify_trace` forges, with the
+    /// bitwise lookup multiplicities rebuilt from the forged trace. The multiplicities recorded by
+    /// the honest execution would reject any forged lookup, even one that is a row of the table,
+    /// so this is the check to use when a lookup is what must reject the forgery.
+    pub fn verify_forged_chip<A, C>(
+        self,
+        chip: &mut VmChipWrapper<A, C>,
+        modify_trace: impl FnOnce(&mut RowMajorMatrix<F>),
+    ) where
+        A: VmAdapterChip,
+        C: VmCoreChip<A::Interface>,
+        VmAirWrapper<A::Air, C::Air>: Air<DebugConstraintBuilder>,
+    {
+        let mut trace = chip.generate_trace(&self.memory);
+        modify_trace(&mut trace);
+        let interactions = check_constraints(&chip.air(), &trace);
+        self.rebuild_bitwise_lookups(&interactions);
+        self.verify(interactions);
+    }
+
+    /// Replaces the requested bitwise lookups with those sent in `interactions`.
+    ///
+    /// Panics if one of them is not a row of the table.
+    pub fn rebuild_bitwise_lookups(&self, interactions: &[Interaction<F>]) {
+        self.bitwise_chip.clear();
+        let bus_index = self.bitwise_chip.bus().inner.index;
+        for interaction in interactions.iter().filter(|i| i.bus_index == bus_index) {
+            self.bitwise_chip.request_interaction(interaction);
+        }
+    }
+}
+
+/// The little-endian limbs of a register holding `value`.
+pub fn limbs(value: u32) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    value.to_le_bytes().map(u32::from)
+}
+
+/// The core columns of a trace row of a [VmAirWrapper] whose adapter columns are `AdapterCols`.
+pub fn core_cols<AdapterCols, CoreCols>(trace_row: &mut [F]) -> &mut CoreCols
+where
+    AdapterCols: AlignedBorrow<F>,
+    [F]: BorrowMut<CoreCols>,
+{
+    trace_row[AdapterCols::WIDTH..].borrow_mut()
+}
+
+/// A xorshift generator for reproducible random tests.
+#[derive(Clone, Debug)]
+pub struct TestRng(u64);
+
+impl Default for TestRng {
+    fn default() -> Self {
+        Self::new(0x5eed_5eed_5eed_5eed)
+    }
+}
+
+impl TestRng {
+    /// `seed` must be nonzero.
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "xorshift needs a nonzero seed");
+        Self(seed)
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        (self.0 >> 32) as u32
+    }
+
+    /// A value in `0..n`, for `n > 0`.
+    pub fn gen_below(&mut self, n: u32) -> u32 {
+        self.next_u32() % n
+    }
+}
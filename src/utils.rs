@@ -0,0 +1,37 @@
+use crate::openvm_stark_backend::field::FieldAlgebra;
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/utils.rs
+// for full implementation details.
+
+/// Return either 0 if n is zero or the next power of two of n.
+/// Used to resize the number of rows in a trace matrix.
+pub const fn next_power_of_two_or_zero(n: usize) -> usize {
+    if n == 0 { 0 } else { n.next_power_of_two() }
+}
+
+pub fn not<T: FieldAlgebra>(a: impl Into<T>) -> T {
+    T::ONE - a.into()
+}
+
+pub fn and<T: FieldAlgebra>(a: impl Into<T>, b: impl Into<T>) -> T {
+    a.into() * b.into()
+}
+
+/// Assumes that a and b are boolean
+pub fn or<T: FieldAlgebra>(a: impl Into<T>, b: impl Into<T>) -> T {
+    let a = a.into();
+    let b = b.into();
+    a.clone() + b.clone() - and(a, b)
+}
+
+/// Assumes that a and b are boolean
+pub fn implies<T: FieldAlgebra>(a: impl Into<T>, b: impl Into<T>) -> T {
+    or(T::ONE - a.into(), b.into())
+}
+
+/// Assumes that `cond` is boolean. Returns `a` if `cond` is true, otherwise returns `b`.
+pub fn select<T: FieldAlgebra>(cond: impl Into<T>, a: impl Into<T>, b: impl Into<T>) -> T {
+    let cond = cond.into();
+    cond.clone() * a.into() + (T::ONE - cond) * b.into()
+}
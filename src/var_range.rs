@@ -0,0 +1,192 @@
+use std::{
+    error::Error,
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
+};
+
+use crate::{
+    bus::VariableRangeCheckerBus,
+    openvm_stark_backend::{
+        air::{Air, BaseAir, PairBuilder},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/circuits/primitives/src/var_range/mod.rs
+// for full implementation details.
+
+/// The multiplicity of the `(value, max_bits)` pair in the same row of the preprocessed trace.
+pub const NUM_VARIABLE_RANGE_COLS: usize = 1;
+/// The `(value, max_bits)` pair.
+pub const NUM_VARIABLE_RANGE_PREPROCESSED_COLS: usize = 2;
+
+#[derive(Clone, Copy, Debug)]
+pub struct VariableRangeCheckerAir {
+    pub bus: VariableRangeCheckerBus,
+}
+
+impl VariableRangeCheckerAir {
+    pub fn range_max_bits(&self) -> usize {
+        self.bus.range_max_bits
+    }
+
+    /// A dummy `(0, 0)` row followed by every `(value, max_bits)` with `value < 2^max_bits`,
+    /// which is `2^(range_max_bits + 1)` rows in total.
+    pub fn height(&self) -> usize {
+        1 << (self.range_max_bits() + 1)
+    }
+}
+
+impl BaseAir<F> for VariableRangeCheckerAir {
+    fn width(&self) -> usize {
+        NUM_VARIABLE_RANGE_COLS
+    }
+
+    fn preprocessed_trace(&self) -> Option<RowMajorMatrix<F>> {
+        let values = [F::ZERO; NUM_VARIABLE_RANGE_PREPROCESSED_COLS]
+            .into_iter()
+            .chain((0..=self.range_max_bits()).flat_map(|bits| {
+                (0..(1u32 << bits)).flat_map(move |value| {
+                    [F::from_canonical_u32(value), F::from_canonical_usize(bits)]
+                })
+            }))
+            .collect();
+        Some(RowMajorMatrix::new(values, NUM_VARIABLE_RANGE_PREPROCESSED_COLS))
+    }
+}
+
+impl<AB> Air<AB> for VariableRangeCheckerAir
+where
+    AB: InteractionBuilder + PairBuilder<F = F>,
+{
+    fn eval(&self, builder: &mut AB) {
+        let preprocessed = builder.preprocessed();
+        let prep_local = preprocessed.row_slice(0);
+        let main = builder.main();
+        let local = main.row_slice(0);
+        self.bus.receive(prep_local[0], prep_local[1]).eval(builder, local[0]);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecomposeError {
+    /// `value` does not fit in `bits` bits.
+    ValueOutOfRange { value: u32, bits: usize },
+    /// `len` limbs of `range_max_bits` bits cannot hold `bits` bits.
+    NotEnoughLimbs { len: usize, needed: usize },
+}
+
+impl fmt::Display for DecomposeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ValueOutOfRange { value, bits } => {
+                write!(f, "value {value} does not fit in {bits} bits")
+            }
+            Self::NotEnoughLimbs { len, needed } => {
+                write!(f, "Not enough limbs: len {len}, needed {needed}")
+            }
+        }
+    }
+}
+
+impl Error for DecomposeError {}
+
+/// A [VariableRangeCheckerChip] shared by the chips that request range checks from it.
+pub type SharedVariableRangeCheckerChip = Arc<VariableRangeCheckerChip>;
+
+/// Host-side counterpart of [VariableRangeCheckerAir] that records the multiplicity of every
+/// requested `(value, max_bits)` range check.
+pub struct VariableRangeCheckerChip {
+    pub air: VariableRangeCheckerAir,
+    count: Vec<AtomicU32>,
+}
+
+impl VariableRangeCheckerChip {
+    pub fn new(bus: VariableRangeCheckerBus) -> Self {
+        let air = VariableRangeCheckerAir { bus };
+        let count = (0..air.height()).map(|_| AtomicU32::new(0)).collect();
+        Self { air, count }
+    }
+
+    pub fn bus(&self) -> VariableRangeCheckerBus {
+        self.air.bus
+    }
+
+    pub fn range_max_bits(&self) -> usize {
+        self.air.range_max_bits()
+    }
+
+    pub fn air_width(&self) -> usize {
+        NUM_VARIABLE_RANGE_COLS
+    }
+
+    /// Records one range check of `value` against `2^max_bits`.
+    pub fn add_count(&self, value: u32, max_bits: usize) {
+        assert!(
+            max_bits <= self.range_max_bits(),
+            "max_bits {max_bits} exceeds range_max_bits {}",
+            self.range_max_bits()
+        );
+        assert!(value < (1 << max_bits), "value {value} out of range for {max_bits} bits");
+        // The rows for `max_bits` start right after the `2^max_bits - 1` rows of smaller ranges
+        // and the dummy row.
+        let idx = (1 << max_bits) + value as usize;
+        self.count[idx].fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decomposes `value` into `range_max_bits`-bit limbs, least significant first, and records a
+    /// range check for each of them. Each limb is checked against the part of `bits` it covers,
+    /// so any extra limbs are checked to be zero.
+    ///
+    /// Returns an error, without recording anything, if `value` does not fit in `bits` bits or if
+    /// `limbs` is too short to hold them.
+    pub fn decompose(
+        &self,
+        mut value: u32,
+        bits: usize,
+        limbs: &mut [F],
+    ) -> Result<(), DecomposeError> {
+        let range_max_bits = self.range_max_bits();
+        if bits < u32::BITS as usize && value >> bits != 0 {
+            return Err(DecomposeError::ValueOutOfRange { value, bits });
+        }
+        let needed = bits.div_ceil(range_max_bits);
+        if limbs.len() < needed {
+            return Err(DecomposeError::NotEnoughLimbs { len: limbs.len(), needed });
+        }
+
+        let mask = (1 << range_max_bits) - 1;
+        let mut bits_remaining = bits;
+        for limb in limbs.iter_mut() {
+            let limb_u32 = value & mask;
+            *limb = F::from_canonical_u32(limb_u32);
+            self.add_count(limb_u32, bits_remaining.min(range_max_bits));
+
+            value = value.checked_shr(range_max_bits as u32).unwrap_or(0);
+            bits_remaining = bits_remaining.saturating_sub(range_max_bits);
+        }
+        Ok(())
+    }
+
+    pub fn clear(&self) {
+        for count in &self.count {
+            count.store(0, Ordering::Relaxed);
+        }
+    }
+
+    /// Generates the multiplicity trace and resets all counts.
+    pub fn generate_trace(&self) -> RowMajorMatrix<F> {
+        let values = self
+            .count
+            .iter()
+            .map(|count| F::from_canonical_u32(count.swap(0, Ordering::Relaxed)))
+            .collect();
+        RowMajorMatrix::new(values, NUM_VARIABLE_RANGE_COLS)
+    }
+}
@@ -0,0 +1,190 @@
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::{
+    aligned_borrow,
+    bus::{MemoryBus, VariableRangeCheckerBus},
+    is_less_than_array::{
+        IsLtArrayAuxCols, IsLtArrayIo, IsLtArraySubAir, IsLtArrayWhenTransitionAir,
+    },
+    memory::{AUX_LEN, MEMORY_BLOCK_SIZE, MemoryAddress},
+    openvm_stark_backend::{
+        air::{Air, AirBuilder, BaseAir},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+    utils::next_power_of_two_or_zero,
+    var_range::VariableRangeCheckerChip,
+};
+
+// Please refer to
+// https://github.com/openvm-org/openvm/blob/3c800070d363237832a66dbe5501d3c365f3c549/crates/vm/src/system/memory/volatile/mod.rs
+// for full implementation details.
+
+aligned_borrow! {
+    #[derive(Clone, Copy, Debug)]
+    pub struct VolatileBoundaryCols<T> {
+        pub address_space: T,
+        /// The limbs of the pointer, least significant first, of `range_max_bits` bits each.
+        pub pointer_limbs: [T; AUX_LEN],
+        pub initial_data: [T; MEMORY_BLOCK_SIZE],
+        pub final_data: [T; MEMORY_BLOCK_SIZE],
+        pub final_timestamp: T,
+        pub is_valid: T,
+        pub addr_lt_aux: IsLtArrayAuxCols<T, 2, AUX_LEN>,
+    }
+}
+
+/// The boundary of volatile memory: every accessed block is sent with its initial data at
+/// timestamp 0 and received with its final data at the timestamp of its last access, which
+/// balances the [MemoryBus].
+///
+/// Valid rows come first and are strictly sorted by `(address_space, pointer)`, so each block
+/// appears at most once.
+#[derive(Clone, Copy, Debug)]
+pub struct VolatileBoundaryAir {
+    pub memory_bus: MemoryBus,
+    pub range_bus: VariableRangeCheckerBus,
+    pub address_space_max_bits: usize,
+    pub pointer_max_bits: usize,
+    pub addr_lt_air: IsLtArrayWhenTransitionAir<2>,
+}
+
+impl VolatileBoundaryAir {
+    pub fn new(
+        memory_bus: MemoryBus,
+        range_bus: VariableRangeCheckerBus,
+        address_space_max_bits: usize,
+        pointer_max_bits: usize,
+    ) -> Self {
+        let range_max_bits = range_bus.range_max_bits;
+        assert!(
+            address_space_max_bits <= range_max_bits,
+            "address_space_max_bits {address_space_max_bits} exceeds range_max_bits \
+             {range_max_bits}"
+        );
+        let addr_lt_air =
+            IsLtArraySubAir::new(range_bus, address_space_max_bits.max(pointer_max_bits));
+        assert_eq!(
+            addr_lt_air.lt.decomp_limbs, AUX_LEN,
+            "pointers of {pointer_max_bits} bits must decompose into {AUX_LEN} limbs"
+        );
+        Self {
+            memory_bus,
+            range_bus,
+            address_space_max_bits,
+            pointer_max_bits,
+            addr_lt_air: addr_lt_air.when_transition(),
+        }
+    }
+
+    fn pointer<T: FieldAlgebra>(&self, pointer_limbs: [impl Into<T>; AUX_LEN]) -> T {
+        pointer_limbs.into_iter().enumerate().fold(T::ZERO, |acc, (i, limb)| {
+            acc + limb.into() * T::from_canonical_usize(1 << (i * self.range_bus.range_max_bits))
+        })
+    }
+}
+
+impl BaseAir<F> for VolatileBoundaryAir {
+    fn width(&self) -> usize {
+        VolatileBoundaryCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for VolatileBoundaryAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let local: &VolatileBoundaryCols<AB::Var> = local.borrow();
+        let next: &VolatileBoundaryCols<AB::Var> = next.borrow();
+
+        builder.assert_bool(local.is_valid);
+        // Padding rows come last.
+        builder.when_transition().when(next.is_valid).assert_one(local.is_valid);
+
+        // Range check the address so that the sorting below is sound.
+        self.range_bus
+            .range_check(local.address_space, self.address_space_max_bits)
+            .eval(builder, local.is_valid);
+        let mut bits_remaining = self.pointer_max_bits;
+        for limb in local.pointer_limbs {
+            let range_bits = bits_remaining.min(self.range_bus.range_max_bits);
+            self.range_bus.range_check(limb, range_bits).eval(builder, local.is_valid);
+            bits_remaining -= range_bits;
+        }
+
+        // Strictly increasing addresses among the valid rows. The range checks of the comparison
+        // are done with the next row's `is_valid`, which on the last row is that of the first.
+        let local_pointer: AB::Expr = self.pointer(local.pointer_limbs);
+        let next_pointer: AB::Expr = self.pointer(next.pointer_limbs);
+        let io = IsLtArrayIo {
+            x: [local.address_space.into(), local_pointer.clone()],
+            y: [next.address_space.into(), next_pointer],
+            out: AB::Expr::ONE,
+            count: next.is_valid.into(),
+        };
+        self.addr_lt_air.eval(builder, (io, (&local.addr_lt_aux).into()));
+
+        let address = MemoryAddress::new(local.address_space, local_pointer);
+        self.memory_bus
+            .send(address.clone(), local.initial_data.to_vec(), AB::Expr::ZERO)
+            .eval(builder, local.is_valid);
+        self.memory_bus
+            .receive(address, local.final_data.to_vec(), local.final_timestamp)
+            .eval(builder, local.is_valid);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VolatileBoundaryRecord {
+    pub address: MemoryAddress<u32, u32>,
+    pub initial_data: [F; MEMORY_BLOCK_SIZE],
+    pub final_data: [F; MEMORY_BLOCK_SIZE],
+    pub final_timestamp: u32,
+}
+
+impl VolatileBoundaryAir {
+    /// Generates one row per record, padded to a power of two, and records the range checks of
+    /// the rows. `records` must be strictly sorted by address.
+    pub fn generate_trace(
+        &self,
+        range_checker: &VariableRangeCheckerChip,
+        records: &[VolatileBoundaryRecord],
+    ) -> RowMajorMatrix<F> {
+        let width = BaseAir::<F>::width(self);
+        let height = next_power_of_two_or_zero(records.len());
+        let mut trace = RowMajorMatrix::new(vec![F::ZERO; height * width], width);
+        let address = |i: usize| {
+            records.get(i % height).map_or([F::ZERO; 2], |record| {
+                [record.address.address_space, record.address.pointer].map(F::from_canonical_u32)
+            })
+        };
+        for (i, row) in trace.rows_mut().enumerate() {
+            let cols: &mut VolatileBoundaryCols<F> = row.borrow_mut();
+            if let Some(record) = records.get(i) {
+                let MemoryAddress { address_space, pointer } = record.address;
+                cols.address_space = F::from_canonical_u32(address_space);
+                range_checker.add_count(address_space, self.address_space_max_bits);
+                range_checker
+                    .decompose(pointer, self.pointer_max_bits, &mut cols.pointer_limbs)
+                    .expect("pointer_limbs has AUX_LEN limbs");
+                cols.initial_data = record.initial_data;
+                cols.final_data = record.final_data;
+                cols.final_timestamp = F::from_canonical_u32(record.final_timestamp);
+                cols.is_valid = F::ONE;
+            }
+            // The comparison with the next row is range checked whenever the next row is valid,
+            // including on the last row if the first row is valid.
+            if records.get((i + 1) % height).is_some() {
+                let mut out = F::ZERO;
+                self.addr_lt_air.0.generate_subrow(
+                    (range_checker, &address(i), &address(i + 1)),
+                    ((&mut cols.addr_lt_aux).into(), &mut out),
+                );
+                debug_assert!(i + 1 == height || out == F::ONE, "records must be sorted");
+            }
+        }
+        trace
+    }
+}
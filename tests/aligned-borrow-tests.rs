@@ -0,0 +1,117 @@
+use core::borrow::{Borrow, BorrowMut};
+use miri_test::{
+    aligned_borrow::{AlignedBorrow, BorrowError, try_borrow, try_borrow_mut},
+    core::Rv32AuipcCoreCols,
+    is_less_than_array::{IsLtArrayAuxCols, IsLtArrayAuxColsMut},
+};
+
+/// A column struct whose alignment is larger than its cell's.
+#[repr(C)]
+#[derive(Debug)]
+struct OverAlignedCols {
+    _a: u64,
+}
+
+// SAFETY: deliberately wrong, the checks in `try_borrow` must reject misaligned rows.
+unsafe impl AlignedBorrow<u32> for OverAlignedCols {
+    const WIDTH: usize = 2;
+}
+
+/// A column struct that claims more columns than it has.
+#[repr(C)]
+#[derive(Debug)]
+struct ShortCols {
+    _a: u32,
+}
+
+// SAFETY: deliberately wrong, the checks in `try_borrow` must reject the size mismatch.
+unsafe impl AlignedBorrow<u32> for ShortCols {
+    const WIDTH: usize = 2;
+}
+
+#[repr(C, align(8))]
+struct AlignedRow([u32; 4]);
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_width() {
+        assert_eq!(Rv32AuipcCoreCols::<u8>::width(), 10);
+        assert_eq!(Rv32AuipcCoreCols::<u32>::width(), 10);
+        assert_eq!(IsLtArrayAuxCols::<u8, 2, 3>::width(), 6);
+    }
+
+    #[test]
+    pub fn test_borrow_sees_every_column() {
+        let row: Vec<u32> = (0..10).collect();
+        let cols: &Rv32AuipcCoreCols<u32> = row[..].borrow();
+        assert_eq!(cols.is_valid, 0);
+        assert_eq!(cols.imm_limbs, [1, 2, 3]);
+        assert_eq!(cols.pc_limbs, [4, 5]);
+        assert_eq!(cols.rd_data, [6, 7, 8, 9]);
+        // The borrow is a view of the row, not a copy.
+        assert!(core::ptr::eq(&cols.rd_data[3], &row[9]));
+    }
+
+    #[test]
+    pub fn test_borrow_mut_writes_through() {
+        let mut row = vec![0u32; 10];
+        let cols: &mut Rv32AuipcCoreCols<u32> = row[..].borrow_mut();
+        cols.is_valid = 1;
+        cols.pc_limbs[1] = 5;
+        cols.rd_data = [6, 7, 8, 9];
+        assert_eq!(row, [1, 0, 0, 0, 0, 5, 6, 7, 8, 9]);
+    }
+
+    #[test]
+    pub fn test_nested_borrow_mut() {
+        let mut row = vec![0u32; 6];
+        let cols: &mut IsLtArrayAuxCols<u32, 2, 3> = row[..].borrow_mut();
+        let aux = IsLtArrayAuxColsMut::from(cols);
+        aux.diff_marker[1] = 1;
+        *aux.diff_inv = 2;
+        aux.lt_decomp.copy_from_slice(&[3, 4, 5]);
+        assert_eq!(row, [0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    pub fn test_wrong_length() {
+        let mut row = vec![0u32; 11];
+        assert_eq!(
+            try_borrow::<_, Rv32AuipcCoreCols<u32>>(&row[..9]).unwrap_err(),
+            BorrowError::WrongLength { expected: 10, actual: 9 }
+        );
+        assert_eq!(
+            try_borrow_mut::<_, Rv32AuipcCoreCols<u32>>(&mut row).unwrap_err(),
+            BorrowError::WrongLength { expected: 10, actual: 11 }
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Wrong row length: expected 10, got 4")]
+    pub fn test_borrow_wrong_length_panics() {
+        let row = [0u32; 4];
+        let _cols: &Rv32AuipcCoreCols<u32> = row[..].borrow();
+    }
+
+    #[test]
+    pub fn test_wrong_size() {
+        let row = [0u32; 2];
+        assert_eq!(
+            try_borrow::<_, ShortCols>(&row).unwrap_err(),
+            BorrowError::WrongSize { expected: 8, actual: 4 }
+        );
+    }
+
+    #[test]
+    pub fn test_misaligned() {
+        let mut row = AlignedRow([0; 4]);
+        assert!(try_borrow::<_, OverAlignedCols>(&row.0[..2]).is_ok());
+        assert_eq!(
+            try_borrow::<_, OverAlignedCols>(&row.0[1..3]).unwrap_err(),
+            BorrowError::Misaligned { align: 8 }
+        );
+        assert!(try_borrow_mut::<_, OverAlignedCols>(&mut row.0[1..3]).is_err());
+    }
+}
@@ -0,0 +1,295 @@
+use miri_test::{
+    asm::{
+        AsmError, assemble, assemble_instructions, disassemble, disassemble_all,
+        disassemble_instruction,
+    },
+    decoder::{DecodeError, Rv32Decoder},
+    instructions::{BaseAluOpcode, Instruction, LocalOpcode},
+    interpreter::Rv32Interpreter,
+    program::Program,
+    testing::TEST_MEMORY_CONFIG,
+};
+
+/// The words of the decoder tests, with their disassembly.
+const CANONICAL: &str = "\
+add gp, ra, sp
+sub gp, ra, sp
+sra gp, ra, sp
+sltu gp, ra, sp
+mul gp, ra, sp
+divu gp, ra, sp
+addi ra, zero, -1
+andi sp, ra, 2047
+slli ra, ra, 31
+srai ra, ra, 3
+lw t0, 8(sp)
+lbu t0, -1(sp)
+sw t0, -4(sp)
+beq ra, sp, -8
+jal ra, 16
+jal zero, -2048
+jalr zero, 0(ra)
+lui t0, 0x12345
+auipc ra, 0x12345
+";
+
+const WORDS: [u32; 19] = [
+    0x0020_81b3,
+    0x4020_81b3,
+    0x4020_d1b3,
+    0x0020_b1b3,
+    0x0220_81b3,
+    0x0220_d1b3,
+    0xfff0_0093,
+    0x7ff0_f113,
+    0x01f0_9093,
+    0x4030_d093,
+    0x0081_2283,
+    0xfff1_4283,
+    0xfe51_2e23,
+    0xfe20_8ce3,
+    0x0100_00ef,
+    0x801f_f06f,
+    0x0000_8067,
+    0x1234_52b7,
+    0x1234_5097,
+];
+
+/// Every mnemonic, with registers and immediates written in every accepted way.
+const ALL_MNEMONICS: &str = "
+    add x1, x2, x3
+    sub s0, fp, s11
+    sll t6, a7, a0
+    slt a1, a2, a3
+    sltu a4, a5, a6
+    xor s2, s3, s4
+    srl s5, s6, s7
+    sra s8, s9, s10
+    or t3, t4, t5
+    and tp, gp, sp
+    mul x31, x30, x29
+    mulh ra, ra, ra
+    mulhsu zero, zero, zero
+    mulhu t0, t1, t2
+    div a0, a1, a2
+    divu a0, a1, a2
+    rem a0, a1, a2
+    remu a0, a1, a2
+    addi a0, a0, -2048
+    slti a0, a0, 2047
+    sltiu a0, a0, 0x7ff
+    xori a0, a0, -0x800
+    ori a0, a0, 0b101
+    andi a0, a0, 0
+    slli a0, a0, 0
+    srli a0, a0, 31
+    srai a0, a0, 0x1f
+    lb a0, -2048(sp)
+    lh a0, (sp)
+    lw a0, 2047(sp)
+    lbu a0, 0(sp)
+    lhu a0, 2(sp)
+    sb a0, -1(sp)
+    sh a0, 0x10(sp)
+    sw a0, -0x10(sp)
+    beq a0, a1, -4096
+    bne a0, a1, 4094
+    blt a0, a1, 2
+    bge a0, a1, -2
+    bltu a0, a1, 0
+    bgeu a0, a1, 8
+    lui a0, 0xfffff
+    auipc a0, 0
+    jal a0, -1048576
+    jalr a0, -1(a0)
+";
+
+fn assemble_error(source: &str) -> AsmError {
+    assemble(source).unwrap_err()
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_assemble_canonical() {
+        assert_eq!(assemble(CANONICAL), Ok(WORDS.to_vec()));
+        assert_eq!(disassemble_all(&WORDS).unwrap(), CANONICAL);
+        assert_eq!(
+            assemble_instructions(CANONICAL),
+            Ok(Rv32Decoder::default().decode_all(&WORDS).unwrap())
+        );
+    }
+
+    #[test]
+    pub fn test_assemble_round_trip() {
+        let words = assemble(ALL_MNEMONICS).unwrap();
+        assert_eq!(words.len(), 45);
+        let text = disassemble_all(&words).unwrap();
+        assert_eq!(assemble(&text), Ok(words));
+        assert!(text.starts_with("add ra, sp, gp\nsub s0, s0, s11\n"));
+        assert!(text.ends_with("jal a0, -1048576\njalr a0, -1(a0)\n"));
+    }
+
+    #[test]
+    pub fn test_disassemble_instruction() {
+        let instructions = Rv32Decoder::default().decode_all(&WORDS).unwrap();
+        let text: String =
+            instructions.iter().map(|i| disassemble_instruction(i).unwrap() + "\n").collect();
+        assert_eq!(text, CANONICAL);
+
+        let instructions = assemble_instructions(ALL_MNEMONICS).unwrap();
+        let text: String =
+            instructions.iter().map(|i| disassemble_instruction(i).unwrap() + "\n").collect();
+        assert_eq!(assemble_instructions(&text), Ok(instructions));
+        // mulhsu zero, zero, zero is decoded to a nop.
+        assert!(text.contains("mulh ra, ra, ra\njal zero, 4\nmulhu t0, t1, t2\n"));
+
+        // There is no SUBI, and registers are multiples of four.
+        let subi = Instruction::from_usize(BaseAluOpcode::SUB.global_opcode(), [4, 4, 1, 1, 0]);
+        assert_eq!(disassemble_instruction(&subi), None);
+        let add = Instruction::from_usize(BaseAluOpcode::ADD.global_opcode(), [4, 4, 6, 1, 1]);
+        assert_eq!(disassemble_instruction(&add), None);
+    }
+
+    #[test]
+    pub fn test_assemble_labels_and_pseudos() {
+        let source = "
+            # Comments and labels on their own lines.
+            loop:
+                nop
+                mv a0, a1       # addi a0, a1, 0
+                li a0, -5
+            skip: j loop
+                jal skip
+                jr t0
+                ret
+            a: b: beq zero, zero, a
+        ";
+        let expected = "\
+addi zero, zero, 0
+addi a0, a1, 0
+addi a0, zero, -5
+jal zero, -12
+jal ra, -4
+jalr zero, 0(t0)
+jalr zero, 0(ra)
+beq zero, zero, 0
+";
+        assert_eq!(disassemble_all(&assemble(source).unwrap()).unwrap(), expected);
+    }
+
+    #[test]
+    pub fn test_assemble_li() {
+        let source = "
+                li a0, 0x12345678
+                li a1, 0x12345800   # the lower 12 bits are negative
+                li a2, -4096
+                li a3, 0xffffffff
+                j end
+                li a4, -2147483647
+            end:
+        ";
+        let expected = "\
+lui a0, 0x12345
+addi a0, a0, 1656
+lui a1, 0x12346
+addi a1, a1, -2048
+lui a2, 0xfffff
+addi a3, zero, -1
+jal zero, 12
+lui a4, 0x80000
+addi a4, a4, 1
+";
+        assert_eq!(disassemble_all(&assemble(source).unwrap()).unwrap(), expected);
+        assert_eq!(
+            assemble_error("li a0, 0x100000000"),
+            AsmError::ImmediateOutOfRange { line: 1, imm: 0x1_0000_0000 }
+        );
+    }
+
+    #[test]
+    pub fn test_assemble_offset_ranges() {
+        assert!(assemble("jal ra, 1048574").is_ok());
+        assert!(assemble("bne a0, a1, 4094").is_ok());
+        let out_of_range = |imm| AsmError::ImmediateOutOfRange { line: 1, imm };
+        assert_eq!(assemble_error("jal ra, 1048576"), out_of_range(1 << 20));
+        assert_eq!(assemble_error("jal ra, -1048578"), out_of_range(-(1 << 20) - 2));
+        assert_eq!(assemble_error("beq a0, a1, 4096"), out_of_range(4096));
+        assert_eq!(assemble_error("beq a0, a1, 3"), out_of_range(3));
+        assert_eq!(assemble_error("addi a0, a0, 2048"), out_of_range(2048));
+        assert_eq!(assemble_error("slli a0, a0, 32"), out_of_range(32));
+        assert_eq!(assemble_error("lw a0, -2049(sp)"), out_of_range(-2049));
+        assert_eq!(assemble_error("lui a0, 0x100000"), out_of_range(1 << 20));
+    }
+
+    #[test]
+    pub fn test_assemble_errors() {
+        let error = assemble_error("nop\n  fence");
+        assert_eq!(error, AsmError::UnknownMnemonic { line: 2, mnemonic: "fence".to_string() });
+        assert_eq!(error.to_string(), "line 2: unknown mnemonic fence");
+        assert_eq!(
+            assemble_error("add a0, a1"),
+            AsmError::OperandCount { line: 1, expected: 3, found: 2 }
+        );
+        assert_eq!(
+            assemble_error("ret ra"),
+            AsmError::OperandCount { line: 1, expected: 0, found: 1 }
+        );
+        for (source, operand) in [("add a0, a1, x32", "x32"), ("mv a0, a8", "a8")] {
+            assert_eq!(
+                assemble_error(source),
+                AsmError::InvalidRegister { line: 1, operand: operand.to_string() }
+            );
+        }
+        for (source, operand) in [("li a0, 0xg", "0xg"), ("lw a0, 4", "4"), ("li a0, --1", "--1")] {
+            assert_eq!(
+                assemble_error(source),
+                AsmError::InvalidImmediate { line: 1, operand: operand.to_string() }
+            );
+        }
+        assert_eq!(
+            assemble_error("1x: nop"),
+            AsmError::InvalidLabel { line: 1, label: "1x".to_string() }
+        );
+        assert_eq!(
+            assemble_error("a: nop\na: nop"),
+            AsmError::DuplicateLabel { line: 2, label: "a".to_string() }
+        );
+        assert_eq!(
+            assemble_error("j nowhere"),
+            AsmError::UnknownLabel { line: 1, label: "nowhere".to_string() }
+        );
+    }
+
+    #[test]
+    pub fn test_disassemble_errors() {
+        assert_eq!(disassemble(0x0000_4501), Err(DecodeError::Compressed { word: 0x4501 }));
+        assert_eq!(
+            disassemble_all(&[0x0020_81b3, 0x0000_0073]),
+            Err(DecodeError::Unsupported { word: 0x73, name: "ECALL" })
+        );
+    }
+
+    #[test]
+    pub fn test_auipc_jal_snippet() {
+        let instructions = assemble_instructions(include_str!("asm/auipc-jal.s")).unwrap();
+        let mut interpreter =
+            Rv32Interpreter::new(Program::new(instructions, 0x1000), 0x1000, TEST_MEMORY_CONFIG);
+        assert_eq!(interpreter.run(100), Ok(9));
+        let expected = [
+            (10, 0x1000),
+            (11, 0x4),
+            (12, 0x2008),
+            (1, 0x1010),
+            (5, 0x1014),
+            (6, 0x1018),
+            (13, 0x1024),
+            (7, 0x1024),
+        ];
+        for (reg, value) in expected {
+            assert_eq!(interpreter.register(reg), value, "x{reg}");
+        }
+        interpreter.verify();
+    }
+}
@@ -0,0 +1,14 @@
+# AUIPC and JAL edge cases, assembled and run from pc 0x1000 by asm-tests.rs.
+start:
+    auipc a0, 0             # a0 = 0x1000
+    auipc a1, 0xfffff       # a1 = 0x1004 - 0x1000, wrapping around
+    auipc a2, 0x1           # a2 = 0x1008 + 0x1000
+    jal forward             # ra = 0x1010
+back:
+    jal t0, done            # t0 = 0x1014
+forward: jal t1, back       # t1 = 0x1018, jumping backwards
+done:
+    auipc a3, 0             # a3 = 0x1018
+    addi a3, a3, 12         # a3 = end
+    jalr t2, 0(a3)          # t2 = 0x1024, jumping to the end
+end:
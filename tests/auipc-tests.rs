@@ -0,0 +1,270 @@
+use core::borrow::BorrowMut;
+use std::sync::Arc;
+
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32RdWriteAdapterChip, Rv32RdWriteAdapterCols},
+    bitwise_op_lookup::BitwiseOperationLookupChip,
+    bus::BitwiseOperationLookupBus,
+    core::{
+        RV32_CELL_BITS, RV32_REGISTER_NUM_LIMBS, Rv32AuipcCoreAir, Rv32AuipcCoreChip,
+        Rv32AuipcCoreCols, run_auipc,
+    },
+    execution::{DEFAULT_PC_STEP, ExecutionState},
+    instructions::{Instruction, LocalOpcode, Rv32AuipcOpcode},
+    integration_api::{
+        AdapterAirContext, BasicAdapterInterface, ImmInstruction, VmChipWrapper, VmCoreAir,
+    },
+    openvm_stark_backend::{
+        air::{Air, AirBuilder, BaseAir},
+        debug::{check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::{Interaction, InteractionBuilder},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    program::{Program, ProgramChip},
+    testing::{EXECUTION_BUS, PROGRAM_BUS, VmChipTestBuilder},
+};
+
+const BUS: BitwiseOperationLookupBus<RV32_CELL_BITS> = BitwiseOperationLookupBus::new(0);
+const WIDTH: usize = 2 + Rv32AuipcCoreCols::<u8>::width();
+
+type AuipcInterface<T> =
+    BasicAdapterInterface<T, ImmInstruction<T>, 0, 1, 0, RV32_REGISTER_NUM_LIMBS>;
+type Rv32AuipcChip = VmChipWrapper<Rv32RdWriteAdapterChip, Rv32AuipcCoreChip>;
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32AuipcChip {
+    VmChipWrapper::new(
+        Rv32RdWriteAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32AuipcCoreChip::new(tester.bitwise_chip.clone()),
+    )
+}
+
+/// `AUIPC rd, imm` where `imm` holds the upper 24 bits of the immediate.
+fn auipc(rd: usize, imm: usize) -> Instruction<F> {
+    Instruction::from_usize(
+        Rv32AuipcOpcode::AUIPC.global_opcode(),
+        [rd * RV32_REGISTER_NUM_LIMBS, 0, imm, RV32_REGISTER_AS as usize, 0],
+    )
+}
+
+/// Runs the core AIR alone on the columns `[from_pc, imm, core cols]`, standing in for an adapter
+/// that checks the returned context against `imm`.
+struct AuipcTestAir(Rv32AuipcCoreAir);
+
+impl BaseAir<F> for AuipcTestAir {
+    fn width(&self) -> usize {
+        WIDTH
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for AuipcTestAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let ctx: AdapterAirContext<AB::Expr, AuipcInterface<AB::Expr>> =
+            self.0.eval(builder, &local[2..], local[0]);
+        assert!(ctx.to_pc.is_none());
+        let ImmInstruction { is_valid, opcode, immediate } = ctx.instruction;
+        let mut when_valid = builder.when(is_valid);
+        when_valid.assert_eq(immediate, local[1]);
+        when_valid.assert_eq(
+            opcode,
+            F::from_canonical_usize(Rv32AuipcOpcode::AUIPC.global_opcode().as_usize()),
+        );
+        for (write, &rd) in ctx.writes[0].iter().zip(&local[WIDTH - RV32_REGISTER_NUM_LIMBS..]) {
+            builder.assert_eq(write.clone(), rd);
+        }
+    }
+}
+
+/// Executes AUIPC on every `(pc, imm)` with the core chip alone and returns the trace
+/// `[from_pc, imm, core cols]`. Padding rows have `from_pc = imm = 0`.
+fn generate_trace(chip: &Rv32AuipcCoreChip, cases: &[(u32, u32)]) -> RowMajorMatrix<F> {
+    let records = cases
+        .iter()
+        .map(|&(pc, imm)| {
+            let record = chip.execute(pc, imm);
+            assert_eq!(record.rd_data, run_auipc(pc, imm).map(F::from_canonical_u32));
+            record
+        })
+        .collect();
+    let core_trace = chip.generate_trace(records);
+    let values = core_trace
+        .rows()
+        .enumerate()
+        .flat_map(|(i, row)| {
+            let (from_pc, imm) = cases.get(i).copied().unwrap_or_default();
+            [from_pc, imm].map(F::from_canonical_u32).into_iter().chain(row.iter().copied())
+        })
+        .collect();
+    RowMajorMatrix::new(values, WIDTH)
+}
+
+/// Returns the row `[from_pc, imm, core cols]` for `rd = pc + (imm << 8)` together with the range
+/// checks that the AIR sends for it.
+fn auipc_row(pc: u32, imm: u32) -> (Vec<u32>, Vec<(u32, u32)>) {
+    let rd = pc.wrapping_add(imm << RV32_CELL_BITS).to_le_bytes().map(u32::from);
+    let imm_limbs = imm.to_le_bytes().map(u32::from);
+    let pc_limbs = pc.to_le_bytes().map(u32::from);
+    let mut row = vec![pc, imm, 1];
+    row.extend(&imm_limbs[..RV32_REGISTER_NUM_LIMBS - 1]);
+    row.extend(&pc_limbs[1..RV32_REGISTER_NUM_LIMBS - 1]);
+    row.extend(rd);
+    let ranges = vec![
+        (rd[0], rd[1]),
+        (rd[2], rd[3]),
+        (imm_limbs[0], imm_limbs[1]),
+        (imm_limbs[2], pc_limbs[1]),
+        (pc_limbs[2], pc_limbs[3] << 2),
+    ];
+    (row, ranges)
+}
+
+fn to_trace(rows: Vec<Vec<u32>>) -> RowMajorMatrix<F> {
+    RowMajorMatrix::new(rows.concat().into_iter().map(F::from_canonical_u32).collect(), WIDTH)
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_auipc() {
+        assert_eq!(run_auipc(0x1000, 0x12345), [0x00, 0x55, 0x23, 0x01]);
+        // The addition wraps around.
+        assert_eq!(run_auipc(0x3fff_fffc, 0xff_ffff), [0xfc, 0xfe, 0xff, 0x3f]);
+        assert_eq!(run_auipc(0x00ff_ff00, 0x1), [0x00, 0x00, 0x00, 0x01]);
+    }
+
+    #[test]
+    pub fn test_auipc_core() {
+        let bitwise_chip = Arc::new(BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(BUS));
+        let chip = Rv32AuipcCoreChip::new(bitwise_chip.clone());
+        let cases =
+            [(0, 0), (0x1000, 0x12345), (0x3fff_fffc, 0xff_ffff), (0x00ff_ff00, 0x1), (4, 0xfff00)];
+        let trace = generate_trace(&chip, &cases);
+        // Padded to a power of two with `is_valid = 0` rows.
+        assert_eq!(trace.height(), 8);
+        assert!(trace.rows().skip(cases.len()).all(|row| row[2] == F::ZERO));
+
+        let mut interactions = check_constraints(&AuipcTestAir(chip.air.clone()), &trace);
+        interactions.extend(check_constraints(&bitwise_chip.air, &bitwise_chip.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    pub fn test_auipc() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let cases = [
+            (1, 0, 0),
+            (2, 0x1000, 0x12345),
+            (31, 0x3fff_fffc, 0xff_ffff),
+            (2, 0x00ff_ff00, 0x1),
+            (7, 4, 0xfff00),
+        ];
+        for (rd, pc, imm) in cases {
+            let to_state = tester.execute(&mut chip, &auipc(rd, imm as usize), pc);
+            assert_eq!(to_state.pc, pc + DEFAULT_PC_STEP);
+            assert_eq!(tester.read_register(rd), run_auipc(pc, imm).map(F::from_canonical_u32));
+        }
+        assert_eq!(chip.current_trace_height(), cases.len());
+        tester.verify_chip(&mut chip, |trace| {
+            // Padded to a power of two with zero rows.
+            assert_eq!(trace.height(), 8);
+            assert_eq!(
+                trace.width(),
+                Rv32RdWriteAdapterCols::<F>::width() + Rv32AuipcCoreCols::<F>::width()
+            );
+        });
+    }
+
+    /// Runs a whole program through the program chip, with the execution bus balanced by the
+    /// initial and final execution states.
+    #[test]
+    pub fn test_auipc_program() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let program = Program::new(vec![auipc(1, 0x10), auipc(2, 0xff_ffff), auipc(1, 3)], 0x400);
+        let mut program_chip = ProgramChip::new(PROGRAM_BUS, program);
+
+        let initial_state = ExecutionState::new(0x400u32, tester.memory.timestamp());
+        let mut state = initial_state;
+        while let Some(instruction) = program_chip.get_instruction(state.pc) {
+            state = chip.execute(&mut tester.memory, &instruction.clone(), state);
+        }
+        assert_eq!(state, ExecutionState::new(0x40cu32, initial_state.timestamp + 3));
+        assert_eq!(tester.read_register(1), run_auipc(0x408, 3).map(F::from_canonical_u32));
+        assert_eq!(tester.read_register(2), run_auipc(0x404, 0xff_ffff).map(F::from_canonical_u32));
+
+        let mut interactions = check_constraints(&chip.air(), &chip.generate_trace(&tester.memory));
+        let program_trace = program_chip.generate_trace();
+        interactions.extend(check_constraints(&program_chip.air, &program_trace));
+        for (state, count) in [(initial_state, F::ONE), (state, -F::ONE)] {
+            interactions.push(Interaction {
+                message: vec![
+                    F::from_canonical_u32(state.pc),
+                    F::from_canonical_u32(state.timestamp),
+                ],
+                count,
+                bus_index: EXECUTION_BUS.inner.index,
+                count_weight: 1,
+            });
+        }
+        tester.verify(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 1")]
+    pub fn test_auipc_wrong_rd_data() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &auipc(1, 0), 0);
+        tester.execute(&mut chip, &auipc(2, 0x12345), 0x1000);
+        tester.verify_chip(&mut chip, |trace| {
+            let row = trace.row_mut(1);
+            let core_row = &mut row[Rv32RdWriteAdapterCols::<F>::width()..];
+            let core_cols: &mut Rv32AuipcCoreCols<F> = core_row.borrow_mut();
+            // Off by one in the second limb of rd.
+            core_cols.rd_data[1] += F::ONE;
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 2 is unbalanced")]
+    pub fn test_auipc_wrong_rd_ptr() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &auipc(1, 0x12345), 0x1000);
+        tester.verify_chip(&mut chip, |trace| {
+            let adapter_row = &mut trace.row_mut(0)[..Rv32RdWriteAdapterCols::<F>::width()];
+            let adapter_cols: &mut Rv32RdWriteAdapterCols<F> = adapter_row.borrow_mut();
+            // The memory bus is checked before the program bus.
+            adapter_cols.rd_ptr += F::from_canonical_usize(RV32_REGISTER_NUM_LIMBS);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "pc 1073741824 out of range for 30 bits")]
+    pub fn test_auipc_execute_pc_out_of_range() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &auipc(1, 0), 1 << 30);
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 0 is unbalanced")]
+    pub fn test_auipc_pc_out_of_range() {
+        let chip = BitwiseOperationLookupChip::<RV32_CELL_BITS>::new(BUS);
+        // The most significant limb of `pc` only has `PC_BITS - 24 = 6` bits.
+        let (row, ranges) = auipc_row(1 << 30, 0);
+        for (x, y) in ranges {
+            if x < (1 << RV32_CELL_BITS) && y < (1 << RV32_CELL_BITS) {
+                chip.request_range(x, y);
+            }
+        }
+        let air = AuipcTestAir(Rv32AuipcCoreAir { bus: BUS });
+        let mut interactions = check_constraints(&air, &to_trace(vec![row]));
+        interactions.extend(check_constraints(&chip.air, &chip.generate_trace()));
+        verify_interactions(interactions);
+    }
+}
@@ -0,0 +1,153 @@
+use core::borrow::BorrowMut;
+
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32BaseAluAdapterChip, Rv32BaseAluAdapterCols, compose},
+    base_alu::{Rv32BaseAluChip, Rv32BaseAluCoreChip, Rv32BaseAluCoreCols, run_alu},
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{BaseAluOpcode, Instruction, LocalOpcode},
+    integration_api::VmChipWrapper,
+    openvm_stark_backend::{
+        field::{F, FieldAlgebra},
+        matrix::Matrix,
+    },
+    testing::{TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32BaseAluChip {
+    VmChipWrapper::new(
+        Rv32BaseAluAdapterChip::new(
+            tester.memory_bridge(),
+            tester.execution_bridge(),
+            tester.bitwise_chip.clone(),
+        ),
+        Rv32BaseAluCoreChip::new(tester.bitwise_chip.clone()),
+    )
+}
+
+/// The register form `OP rd, rs1, rs2` of the five opcodes.
+fn alu(opcode: BaseAluOpcode, rd: usize, rs1: usize, rs2: usize) -> Instruction<F> {
+    let [rd, rs1, rs2] = [rd, rs1, rs2].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    let register_as = RV32_REGISTER_AS as usize;
+    Instruction::from_usize(opcode.global_opcode(), [rd, rs1, rs2, register_as, register_as])
+}
+
+/// `OPI rd, rs1, imm` where `imm` is a 12-bit immediate, sign-extended to 24 bits.
+fn alu_imm(opcode: BaseAluOpcode, rd: usize, rs1: usize, imm: i32) -> Instruction<F> {
+    assert!((-2048..2048).contains(&imm));
+    let imm = (imm as u32 & 0xff_ffff) as usize;
+    let [rd, rs1] = [rd, rs1].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    Instruction::from_usize(opcode.global_opcode(), [rd, rs1, imm, RV32_REGISTER_AS as usize, 0])
+}
+
+/// What `opcode` computes on `u32`s, wrapping around like RV32.
+fn reference(opcode: BaseAluOpcode, x: u32, y: u32) -> u32 {
+    match opcode {
+        BaseAluOpcode::ADD => x.wrapping_add(y),
+        BaseAluOpcode::SUB => x.wrapping_sub(y),
+        BaseAluOpcode::XOR => x ^ y,
+        BaseAluOpcode::OR => x | y,
+        BaseAluOpcode::AND => x & y,
+    }
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_alu() {
+        let cases = [(0, 0), (1, u32::MAX), (0x8000_0000, 0x8000_0000), (0x1234_5678, 0xfedc_ba98)];
+        for opcode in BaseAluOpcode::ALL {
+            for (x, y) in cases {
+                assert_eq!(
+                    run_alu(opcode, &limbs(x), &limbs(y)),
+                    limbs(reference(opcode, x, y)),
+                    "{opcode:?} {x:#x} {y:#x}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_base_alu_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut rng = TestRng::default();
+        let mut pc = 0;
+        for i in 0..100 {
+            let opcode = BaseAluOpcode::ALL[i % BaseAluOpcode::COUNT];
+            let [rd, rs1, rs2] = [(); 3].map(|_| 1 + rng.gen_below(31) as usize);
+            tester.write_register(rs1, limbs(rng.next_u32()));
+            let (instruction, y) = if i % 3 == 0 {
+                let imm = rng.gen_below(4096) as i32 - 2048;
+                (alu_imm(opcode, rd, rs1, imm), imm as u32)
+            } else {
+                // rs2 may alias rs1.
+                tester.write_register(rs2, limbs(rng.next_u32()));
+                (alu(opcode, rd, rs1, rs2), compose(&tester.read_register(rs2)))
+            };
+            let x = compose(&tester.read_register(rs1));
+            let to_state = tester.execute(&mut chip, &instruction, pc);
+            assert_eq!(to_state.pc, pc + DEFAULT_PC_STEP);
+            assert_eq!(compose(&tester.read_register(rd)), reference(opcode, x, y), "{opcode:?}");
+            pc = to_state.pc;
+        }
+        tester.verify_chip(&mut chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_base_alu_wrong_add() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x00ff_00ff));
+        tester.execute(&mut chip, &alu_imm(BaseAluOpcode::ADD, 2, 1, 1), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            let core_cols =
+                core_cols::<Rv32BaseAluAdapterCols<F>, Rv32BaseAluCoreCols<F>>(trace.row_mut(0));
+            // Drop the carry out of the first limb.
+            core_cols.a[1] -= F::ONE;
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bitwise lookup [240, 0, 2013265681, 1] is not in the table")]
+    pub fn test_base_alu_wrong_and() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0xf0f0_f0f0));
+        tester.write_register(2, limbs(0xff00_ff00));
+        tester.execute(&mut chip, &alu(BaseAluOpcode::AND, 3, 1, 2), 0);
+        tester.verify_forged_chip(&mut chip, |trace| {
+            let core_cols =
+                core_cols::<Rv32BaseAluAdapterCols<F>, Rv32BaseAluCoreCols<F>>(trace.row_mut(0));
+            // The OR of the operands instead of their AND. The write to rd is consistent with it,
+            // so only the bitwise lookup catches it.
+            core_cols.a = limbs(0xfff0_fff0).map(F::from_canonical_u32);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_base_alu_wrong_imm_sign() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &alu_imm(BaseAluOpcode::OR, 2, 1, -1), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            let adapter_row = &mut trace.row_mut(0)[..Rv32BaseAluAdapterCols::<F>::width()];
+            let adapter_cols: &mut Rv32BaseAluAdapterCols<F> = adapter_row.borrow_mut();
+            // The immediate claims to be the zero-extension of 0xffff.
+            adapter_cols.rs2 = F::from_canonical_u32(0xffff);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "immediate 0x12345 is not a sign-extended 16-bit value")]
+    pub fn test_base_alu_execute_invalid_imm() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut instruction = alu_imm(BaseAluOpcode::ADD, 2, 1, 0);
+        instruction.c = F::from_canonical_u32(0x12345);
+        tester.execute(&mut chip, &instruction, 0);
+    }
+}
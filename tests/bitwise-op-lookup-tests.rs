@@ -0,0 +1,99 @@
+use miri_test::{
+    bitwise_op_lookup::BitwiseOperationLookupChip,
+    bus::{BitwiseOperation, BitwiseOperationLookupBus},
+    openvm_stark_backend::{
+        air::{Air, BaseAir},
+        debug::{check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::{Interaction, InteractionBuilder, LookupDirection},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+};
+
+const NUM_BITS: usize = 4;
+const BUS: BitwiseOperationLookupBus<NUM_BITS> = BitwiseOperationLookupBus::new(0);
+
+/// Sends one `(x, y, z)` lookup for `op` per row, with columns `[x, y, z, count]`.
+struct DummyAir {
+    op: BitwiseOperation,
+}
+
+impl BaseAir<F> for DummyAir {
+    fn width(&self) -> usize {
+        4
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for DummyAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        BUS.push(local[0], local[1], local[2], self.op, true).eval(builder, local[3]);
+    }
+}
+
+fn dummy_trace(rows: &[[u32; 3]]) -> RowMajorMatrix<F> {
+    let values = rows
+        .iter()
+        .flat_map(|row| row.iter().copied().chain([1]))
+        .map(F::from_canonical_u32)
+        .collect();
+    RowMajorMatrix::new(values, 4)
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_bitwise_op_lookup_all_ops() {
+        let chip = BitwiseOperationLookupChip::<NUM_BITS>::new(BUS);
+        let mut interactions = vec![];
+        for op in BitwiseOperation::ALL {
+            let rows: Vec<[u32; 3]> = [(0, 0), (3, 9), (15, 15), (10, 5)]
+                .into_iter()
+                .map(|(x, y)| [x, y, chip.request(op, x, y)])
+                .collect();
+            interactions.extend(check_constraints(&DummyAir { op }, &dummy_trace(&rows)));
+        }
+        let trace = chip.generate_trace();
+        assert_eq!(trace.height(), 1 << (2 * NUM_BITS));
+        interactions.extend(check_constraints(&chip.air, &trace));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 0 is unbalanced")]
+    pub fn test_bitwise_op_lookup_wrong_and() {
+        let chip = BitwiseOperationLookupChip::<NUM_BITS>::new(BUS);
+        chip.request_and(12, 10);
+        let mut interactions = check_constraints(
+            &DummyAir { op: BitwiseOperation::And },
+            &dummy_trace(&[[12, 10, 12]]),
+        );
+        interactions.extend(check_constraints(&chip.air, &chip.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range for 4 bits")]
+    pub fn test_bitwise_op_lookup_out_of_range() {
+        let chip = BitwiseOperationLookupChip::<NUM_BITS>::new(BUS);
+        chip.request_range(16, 0);
+    }
+
+    #[test]
+    pub fn test_bitwise_op_lookup_interaction_inspection() {
+        let [x, y, z] = [5, 3, 6].map(F::from_canonical_u32);
+        let sent = BUS.send_xor::<F>(x, y, z);
+        assert_eq!(sent.direction(), LookupDirection::Lookup);
+        assert_eq!(sent.key(), &[x, y, z, BitwiseOperation::Xor.to_field()]);
+        let Interaction { message, count, bus_index, count_weight } = sent.into_interaction(F::ONE);
+        assert_eq!(message, vec![x, y, z, F::ONE]);
+        assert_eq!((count, bus_index, count_weight), (F::ONE, 0, 1));
+
+        let received = BUS.receive::<F>(x, y, F::ZERO, BitwiseOperation::Range);
+        assert!(!received.is_lookup());
+        let interaction = received.into_interaction(F::from_canonical_u32(2));
+        assert_eq!((interaction.count, interaction.count_weight), (-F::from_canonical_u32(2), 0));
+    }
+}
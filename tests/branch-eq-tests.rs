@@ -0,0 +1,131 @@
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32BranchAdapterChip, Rv32BranchAdapterCols},
+    branch_eq::{Rv32BranchEqualChip, Rv32BranchEqualCoreChip, Rv32BranchEqualCoreCols, run_eq},
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{BranchEqualOpcode, Instruction, LocalOpcode},
+    integration_api::VmChipWrapper,
+    openvm_stark_backend::{
+        field::{F, FieldAlgebra},
+        matrix::Matrix,
+    },
+    testing::{TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32BranchEqualChip {
+    VmChipWrapper::new(
+        Rv32BranchAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32BranchEqualCoreChip::new(),
+    )
+}
+
+/// `BEQ` or `BNE rs1, rs2, imm`, with the signed offset `imm` as a field element.
+fn branch(opcode: BranchEqualOpcode, rs1: usize, rs2: usize, imm: i32) -> Instruction<F> {
+    let [rs1, rs2] = [rs1, rs2].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    let register_as = RV32_REGISTER_AS as usize;
+    let mut instruction =
+        Instruction::from_usize(opcode.global_opcode(), [rs1, rs2, 0, register_as, register_as]);
+    instruction.c = match imm < 0 {
+        true => -F::from_canonical_u32(imm.unsigned_abs()),
+        false => F::from_canonical_u32(imm as u32),
+    };
+    instruction
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_eq() {
+        let values = [0, 1, 0x100, 0x8000_0000, u32::MAX];
+        for x in values {
+            for y in values {
+                let (beq, beq_idx, _) = run_eq(BranchEqualOpcode::BEQ, &limbs(x), &limbs(y));
+                let (bne, bne_idx, _) = run_eq(BranchEqualOpcode::BNE, &limbs(x), &limbs(y));
+                assert_eq!(beq, x == y, "{x:#x} {y:#x}");
+                assert_eq!(bne, x != y, "{x:#x} {y:#x}");
+                assert_eq!(beq_idx, bne_idx);
+                if x != y {
+                    assert_ne!(limbs(x)[beq_idx], limbs(y)[beq_idx]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_branch_eq_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut rng = TestRng::default();
+        let mut pc = 1 << 20;
+        for i in 0..100 {
+            let opcode = BranchEqualOpcode::ALL[i % BranchEqualOpcode::COUNT];
+            let [rs1, rs2] = [(); 2].map(|_| 1 + rng.gen_below(31) as usize);
+            let x = rng.next_u32();
+            tester.write_register(rs1, limbs(x));
+            // Equal operands half of the time, so both outcomes are covered for both opcodes.
+            let y = if i % 4 < 2 { x } else { rng.next_u32() };
+            tester.write_register(rs2, limbs(y));
+            let (x, y) = (tester.read_register(rs1), tester.read_register(rs2));
+            let imm = 2 * (rng.gen_below(4096) as i32 - 2048);
+            let to_state = tester.execute(&mut chip, &branch(opcode, rs1, rs2, imm), pc);
+            let taken = (x == y) == (opcode == BranchEqualOpcode::BEQ);
+            let expected_pc =
+                if taken { pc.wrapping_add_signed(imm) } else { pc + DEFAULT_PC_STEP };
+            assert_eq!(to_state.pc, expected_pc, "{opcode:?}");
+            pc = to_state.pc;
+        }
+        tester.verify_chip(&mut chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    pub fn test_branch_eq_taken_and_not_taken() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(7));
+        tester.write_register(2, limbs(7));
+        tester.write_register(3, limbs(8));
+        let cases = [
+            (BranchEqualOpcode::BEQ, 2, -8, 0x100 - 8),
+            (BranchEqualOpcode::BEQ, 3, -8, 0x100 + 4),
+            (BranchEqualOpcode::BNE, 2, 16, 0x100 + 4),
+            (BranchEqualOpcode::BNE, 3, 16, 0x100 + 16),
+        ];
+        for (opcode, rs2, imm, expected_pc) in cases {
+            let to_state = tester.execute(&mut chip, &branch(opcode, 1, rs2, imm), 0x100);
+            assert_eq!(to_state.pc, expected_pc, "{opcode:?} x{rs2}");
+        }
+        tester.verify_chip(&mut chip, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_branch_eq_wrong_cmp_result() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1234_5678));
+        tester.write_register(2, limbs(0x1234_5679));
+        tester.execute(&mut chip, &branch(BranchEqualOpcode::BEQ, 1, 2, 8), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            // Claim that the different operands are equal.
+            let cols =
+                core_cols::<Rv32BranchAdapterCols<F>, Rv32BranchEqualCoreCols<F>>(trace.row_mut(0));
+            cols.cmp_result = F::ONE;
+            cols.diff_inv_marker = [F::ZERO; RV32_REGISTER_NUM_LIMBS];
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 3 is unbalanced")]
+    pub fn test_branch_eq_wrong_imm() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(5));
+        tester.execute(&mut chip, &branch(BranchEqualOpcode::BEQ, 1, 1, 8), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            // Jump somewhere else than the instruction says.
+            core_cols::<Rv32BranchAdapterCols<F>, Rv32BranchEqualCoreCols<F>>(trace.row_mut(0))
+                .imm = F::from_canonical_u32(12);
+        });
+    }
+}
@@ -0,0 +1,152 @@
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32BranchAdapterChip, Rv32BranchAdapterCols, compose},
+    branch_lt::{
+        Rv32BranchLessThanChip, Rv32BranchLessThanCoreChip, Rv32BranchLessThanCoreCols, run_cmp,
+    },
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{BranchLessThanOpcode, Instruction, LocalOpcode},
+    integration_api::VmChipWrapper,
+    openvm_stark_backend::{
+        field::{F, Field, FieldAlgebra},
+        matrix::Matrix,
+    },
+    testing::{TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32BranchLessThanChip {
+    VmChipWrapper::new(
+        Rv32BranchAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32BranchLessThanCoreChip::new(tester.bitwise_chip.clone()),
+    )
+}
+
+/// `BLT`, `BLTU`, `BGE` or `BGEU rs1, rs2, imm`, where a negative `imm` is encoded as
+/// `p - |imm|`.
+fn branch(opcode: BranchLessThanOpcode, rs1: usize, rs2: usize, imm: i32) -> Instruction<F> {
+    let [rs1, rs2] = [rs1, rs2].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    let register_as = RV32_REGISTER_AS as usize;
+    let mut instruction =
+        Instruction::from_usize(opcode.global_opcode(), [rs1, rs2, 0, register_as, register_as]);
+    instruction.c = match imm < 0 {
+        true => -F::from_canonical_u32(imm.unsigned_abs()),
+        false => F::from_canonical_u32(imm as u32),
+    };
+    instruction
+}
+
+/// Whether the branch of `opcode` on `x` and `y` is taken.
+fn reference(opcode: BranchLessThanOpcode, x: u32, y: u32) -> bool {
+    match opcode {
+        BranchLessThanOpcode::BLT => (x as i32) < (y as i32),
+        BranchLessThanOpcode::BLTU => x < y,
+        BranchLessThanOpcode::BGE => (x as i32) >= (y as i32),
+        BranchLessThanOpcode::BGEU => x >= y,
+    }
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_cmp() {
+        let values = [0, 1, 0xff, 0x100, 0x7fff_ffff, 0x8000_0000, 0x8000_0001, u32::MAX];
+        for x in values {
+            for y in values {
+                for opcode in BranchLessThanOpcode::ALL {
+                    let (cmp_result, ..) = run_cmp(opcode, &limbs(x), &limbs(y));
+                    assert_eq!(cmp_result, reference(opcode, x, y), "{opcode:?} {x:#x} {y:#x}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_branch_lt_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut rng = TestRng::default();
+        let mut pc = 1 << 20;
+        for i in 0..100 {
+            let opcode = BranchLessThanOpcode::ALL[i % BranchLessThanOpcode::COUNT];
+            let [rs1, rs2] = [(); 2].map(|_| 1 + rng.gen_below(31) as usize);
+            let x = rng.next_u32();
+            tester.write_register(rs1, limbs(x));
+            let y = match i % 3 {
+                // Operands that only differ in a single limb, or not at all.
+                0 => x ^ ((rng.gen_below(2) * 0xff) << (8 * rng.gen_below(4))),
+                _ => rng.next_u32(),
+            };
+            tester.write_register(rs2, limbs(y));
+            let x = compose(&tester.read_register(rs1));
+            let y = compose(&tester.read_register(rs2));
+            let imm = 2 * (rng.gen_below(4096) as i32 - 2048);
+            let to_state = tester.execute(&mut chip, &branch(opcode, rs1, rs2, imm), pc);
+            let expected_pc = match reference(opcode, x, y) {
+                true => pc.wrapping_add_signed(imm),
+                false => pc + DEFAULT_PC_STEP,
+            };
+            assert_eq!(to_state.pc, expected_pc, "{opcode:?} {x:#x} {y:#x}");
+            pc = to_state.pc;
+        }
+        tester.verify_chip(&mut chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    pub fn test_branch_lt_taken_and_not_taken() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(-1i32 as u32));
+        tester.write_register(2, limbs(1));
+        // -1 < 1 as signed integers but 0xffff_ffff > 1 as unsigned integers.
+        let cases = [
+            (BranchLessThanOpcode::BLT, -12, 0x100 - 12),
+            (BranchLessThanOpcode::BLTU, -12, 0x100 + 4),
+            (BranchLessThanOpcode::BGE, 20, 0x100 + 4),
+            (BranchLessThanOpcode::BGEU, 20, 0x100 + 20),
+        ];
+        for (opcode, imm, expected_pc) in cases {
+            let to_state = tester.execute(&mut chip, &branch(opcode, 1, 2, imm), 0x100);
+            assert_eq!(to_state.pc, expected_pc, "{opcode:?}");
+        }
+        tester.verify_chip(&mut chip, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_branch_lt_cmp_lt_inconsistent() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(3));
+        tester.write_register(2, limbs(5));
+        tester.execute(&mut chip, &branch(BranchLessThanOpcode::BGEU, 1, 2, 8), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            // Take the branch while still claiming 3 < 5.
+            core_cols::<Rv32BranchAdapterCols<F>, Rv32BranchLessThanCoreCols<F>>(
+                trace.row_mut(0),
+            )
+            .cmp_result = F::ONE;
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bitwise lookup [2013265919, 0, 0, 0] is not in the table")]
+    pub fn test_branch_lt_negative_diff_val() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(3));
+        tester.write_register(2, limbs(5));
+        tester.execute(&mut chip, &branch(BranchLessThanOpcode::BGEU, 1, 2, 8), 0);
+        tester.verify_forged_chip(&mut chip, |trace| {
+            // Claim 3 >= 5 with the difference 3 - 5 = -2, which is non-zero but not positive, so
+            // the range check of diff_val rejects it.
+            let cols = core_cols::<Rv32BranchAdapterCols<F>, Rv32BranchLessThanCoreCols<F>>(
+                trace.row_mut(0),
+            );
+            cols.cmp_result = F::ONE;
+            cols.cmp_lt = F::ZERO;
+            cols.diff_val = -F::from_canonical_u32(2);
+            cols.diff_inv = cols.diff_val.inverse();
+        });
+    }
+}
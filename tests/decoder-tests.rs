@@ -0,0 +1,185 @@
+use miri_test::{
+    adapters::{RV32_MEMORY_AS, RV32_REGISTER_AS, Rv32RdWriteAdapterChip, compose},
+    core::Rv32AuipcCoreChip,
+    decoder::{DecodeError, Rv32Decoder, b_imm, i_imm, j_imm, s_imm, u_imm},
+    instructions::{
+        BaseAluOpcode, BranchEqualOpcode, DivRemOpcode, Instruction, LessThanOpcode, LocalOpcode,
+        MulOpcode, Rv32AuipcOpcode, Rv32JalLuiOpcode, Rv32JalrOpcode, Rv32LoadStoreOpcode,
+        ShiftOpcode, VmOpcode,
+    },
+    integration_api::VmChipWrapper,
+    openvm_stark_backend::field::{F, FieldAlgebra},
+    testing::VmChipTestBuilder,
+};
+
+const REG: usize = RV32_REGISTER_AS as usize;
+const MEM: usize = RV32_MEMORY_AS as usize;
+
+fn decode(word: u32) -> Instruction<F> {
+    Rv32Decoder::default().decode(word).unwrap()
+}
+
+fn instruction<const N: usize>(opcode: impl LocalOpcode, operands: [usize; N]) -> Instruction<F> {
+    Instruction::from_usize(opcode.global_opcode(), operands)
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_immediates() {
+        // addi x1, x0, -1
+        assert_eq!(i_imm(0xfff0_0093), -1);
+        // sw x5, -4(x2)
+        assert_eq!(s_imm(0xfe51_2e23), -4);
+        // beq x1, x2, -8
+        assert_eq!(b_imm(0xfe20_8ce3), -8);
+        // lui x5, 0x12345
+        assert_eq!(u_imm(0x1234_52b7), 0x1234_5000);
+        // jal ra, 16 and jal x0, -2048
+        assert_eq!(j_imm(0x0100_00ef), 16);
+        assert_eq!(j_imm(0x801f_f06f), -2048);
+    }
+
+    #[test]
+    pub fn test_decode_register_ops() {
+        // add x3, x1, x2 and sub x3, x1, x2
+        assert_eq!(decode(0x0020_81b3), instruction(BaseAluOpcode::ADD, [12, 4, 8, REG, REG]));
+        assert_eq!(decode(0x4020_81b3), instruction(BaseAluOpcode::SUB, [12, 4, 8, REG, REG]));
+        // sra x3, x1, x2 and sltu x3, x1, x2
+        assert_eq!(decode(0x4020_d1b3), instruction(ShiftOpcode::SRA, [12, 4, 8, REG, REG]));
+        assert_eq!(decode(0x0020_b1b3), instruction(LessThanOpcode::SLTU, [12, 4, 8, REG, REG]));
+        // mul x3, x1, x2 and divu x3, x1, x2
+        assert_eq!(decode(0x0220_81b3), instruction(MulOpcode::MUL, [12, 4, 8, REG, 0]));
+        assert_eq!(decode(0x0220_d1b3), instruction(DivRemOpcode::DIVU, [12, 4, 8, REG, 0]));
+    }
+
+    #[test]
+    pub fn test_decode_immediate_ops() {
+        // addi x1, x0, -1, sign-extended to 24 bits
+        assert_eq!(decode(0xfff0_0093), instruction(BaseAluOpcode::ADD, [4, 0, 0xff_ffff, REG, 0]));
+        // andi x2, x1, 0x7ff
+        assert_eq!(decode(0x7ff0_f113), instruction(BaseAluOpcode::AND, [8, 4, 0x7ff, REG, 0]));
+        // slli x1, x1, 31 and srai x1, x1, 3
+        assert_eq!(decode(0x01f0_9093), instruction(ShiftOpcode::SLL, [4, 4, 31, REG, 0]));
+        assert_eq!(decode(0x4030_d093), instruction(ShiftOpcode::SRA, [4, 4, 3, REG, 0]));
+        // addi x1, x0, 0x400 and addi x1, x0, 0x41f, whose upper immediate bits look like the
+        // funct7 of SUB
+        assert_eq!(decode(0x4000_0093), instruction(BaseAluOpcode::ADD, [4, 0, 0x400, REG, 0]));
+        assert_eq!(decode(0x41f0_0093), instruction(BaseAluOpcode::ADD, [4, 0, 0x41f, REG, 0]));
+    }
+
+    #[test]
+    pub fn test_decode_loads_and_stores() {
+        // lw x5, 8(x2)
+        assert_eq!(
+            decode(0x0081_2283),
+            instruction(Rv32LoadStoreOpcode::LOADW, [20, 8, 8, REG, MEM, 1, 0])
+        );
+        // lbu x5, -1(x2)
+        assert_eq!(
+            decode(0xfff1_4283),
+            instruction(Rv32LoadStoreOpcode::LOADBU, [20, 8, 0xffff, REG, MEM, 1, 1])
+        );
+        // sw x5, -4(x2)
+        assert_eq!(
+            decode(0xfe51_2e23),
+            instruction(Rv32LoadStoreOpcode::STOREW, [20, 8, 0xfffc, REG, MEM, 1, 1])
+        );
+    }
+
+    #[test]
+    pub fn test_decode_control_flow() {
+        // beq x1, x2, -8
+        let mut beq = instruction(BranchEqualOpcode::BEQ, [4, 8, 0, REG, REG]);
+        beq.c = -F::from_canonical_u32(8);
+        assert_eq!(decode(0xfe20_8ce3), beq);
+        // jal ra, 16
+        assert_eq!(decode(0x0100_00ef), instruction(Rv32JalLuiOpcode::JAL, [4, 0, 16, REG, 0, 1]));
+        // jalr x0, 0(x1), which does not write x0
+        assert_eq!(decode(0x0000_8067), instruction(Rv32JalrOpcode::JALR, [0, 4, 0, REG, 0, 0, 0]));
+        // lui x5, 0x12345 and auipc x1, 0x12345
+        assert_eq!(
+            decode(0x1234_52b7),
+            instruction(Rv32JalLuiOpcode::LUI, [20, 0, 0x12345, REG, 0, 1])
+        );
+        assert_eq!(
+            decode(0x1234_5097),
+            instruction(Rv32AuipcOpcode::AUIPC, [4, 0, 0x12_3450, REG, 0])
+        );
+    }
+
+    #[test]
+    pub fn test_decode_zero_register() {
+        // add x0, x1, x2, addi x0, x0, 0, lui x0, 0x12345 and auipc x0, 1 only write x0, so they
+        // are nops: jumps to the next instruction that do not write rd.
+        let nop = instruction(Rv32JalLuiOpcode::JAL, [0, 0, 4, REG, 0, 0]);
+        for word in [0x0020_8033, 0x0000_0013, 0x1234_5037, 0x0000_1017] {
+            assert_eq!(decode(word), nop, "{word:#010x}");
+        }
+        // lw x0, 4(x3) still accesses the memory, without writing x0.
+        assert_eq!(
+            decode(0x0041_a003),
+            instruction(Rv32LoadStoreOpcode::LOADW, [0, 12, 4, REG, MEM, 0, 0])
+        );
+        // Illegal encodings are rejected before they become nops.
+        let word = 0x2000_8033;
+        assert_eq!(
+            Rv32Decoder::default().decode(word),
+            Err(DecodeError::IllegalFunct { word, funct3: 0, funct7: 0x10 })
+        );
+    }
+
+    #[test]
+    pub fn test_decode_offset() {
+        let instruction = Rv32Decoder::new(0x1000).decode(0x0020_81b3).unwrap();
+        let expected = BaseAluOpcode::ADD.global_opcode().as_usize() + 0x1000;
+        assert_eq!(instruction.opcode, VmOpcode::from_usize(expected));
+        assert_eq!(instruction.opcode.local_opcode::<BaseAluOpcode>(), None);
+    }
+
+    #[test]
+    pub fn test_decode_errors() {
+        let decoder = Rv32Decoder::default();
+        assert_eq!(decoder.decode(0x0000_4501), Err(DecodeError::Compressed { word: 0x4501 }));
+        assert_eq!(
+            decoder.decode(0x0000_007f),
+            Err(DecodeError::UnknownOpcode { word: 0x7f, opcode: 0x7f })
+        );
+        // add with funct7 0x10, slli with funct7 0x20 and a load with funct3 0b011
+        for (word, funct3, funct7) in
+            [(0x2000_81b3, 0b000, 0x10), (0x4030_9093, 0b001, 0x20), (0x0081_3283, 0b011, 0)]
+        {
+            assert_eq!(
+                decoder.decode(word),
+                Err(DecodeError::IllegalFunct { word, funct3, funct7 })
+            );
+        }
+        for (word, name) in
+            [(0x0000_0073, "ECALL"), (0x0010_0073, "EBREAK"), (0x0ff0_000f, "FENCE")]
+        {
+            assert_eq!(decoder.decode(word), Err(DecodeError::Unsupported { word, name }));
+        }
+        assert_eq!(
+            decoder.decode_all(&[0x0020_81b3, 0x0000_0073]).unwrap_err().to_string(),
+            "word 0x00000073 is the unsupported instruction ECALL"
+        );
+    }
+
+    #[test]
+    pub fn test_decode_auipc_execute() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = VmChipWrapper::new(
+            Rv32RdWriteAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+            Rv32AuipcCoreChip::new(tester.bitwise_chip.clone()),
+        );
+        // auipc x1, 0x12345, auipc x2, 0xfffff and auipc x3, 0
+        let cases =
+            [(0x1234_5097, 1, 0x1234_5100), (0xffff_f117, 2, 0xffff_f104), (0x197, 3, 0x108)];
+        for (pc, (word, rd, expected)) in (0x100..).step_by(4).zip(cases) {
+            tester.execute(&mut chip, &decode(word), pc);
+            assert_eq!(compose(&tester.read_register(rd)), expected);
+        }
+        tester.verify_chip(&mut chip, |_| {});
+    }
+}
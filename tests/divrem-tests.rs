@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32MultAdapterChip, Rv32MultAdapterCols, compose},
+    core::RV32_REGISTER_NUM_LIMBS,
+    divrem::{
+        DivRemCoreSpecialCase, Rv32DivRemChip, Rv32DivRemCoreChip, Rv32DivRemCoreCols, run_divrem,
+    },
+    instructions::{DivRemOpcode, Instruction, LocalOpcode},
+    integration_api::VmChipWrapper,
+    openvm_stark_backend::{
+        debug::check_constraints,
+        field::{F, FieldAlgebra},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    range_tuple::{RangeTupleCheckerChip, SharedRangeTupleCheckerChip},
+    testing::{RANGE_TUPLE_CHECKER_BUS, TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> (Rv32DivRemChip, SharedRangeTupleCheckerChip<2>) {
+    let range_tuple_chip = Arc::new(RangeTupleCheckerChip::new(RANGE_TUPLE_CHECKER_BUS));
+    let chip = VmChipWrapper::new(
+        Rv32MultAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32DivRemCoreChip::new(tester.bitwise_chip.clone(), range_tuple_chip.clone()),
+    );
+    (chip, range_tuple_chip)
+}
+
+/// `DIV`, `DIVU`, `REM` or `REMU rd, rs1, rs2`.
+fn divrem(opcode: DivRemOpcode, rd: usize, rs1: usize, rs2: usize) -> Instruction<F> {
+    let [rd, rs1, rs2] = [rd, rs1, rs2].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    Instruction::from_usize(opcode.global_opcode(), [rd, rs1, rs2, RV32_REGISTER_AS as usize, 0])
+}
+
+/// The quotient or remainder RISC-V defines, including for division by zero and overflow.
+fn reference(opcode: DivRemOpcode, x: u32, y: u32) -> u32 {
+    match opcode {
+        DivRemOpcode::DIV if y == 0 => u32::MAX,
+        DivRemOpcode::DIV => (x as i32).wrapping_div(y as i32) as u32,
+        DivRemOpcode::DIVU => x.checked_div(y).unwrap_or(u32::MAX),
+        DivRemOpcode::REM if y == 0 => x,
+        DivRemOpcode::REM => (x as i32).wrapping_rem(y as i32) as u32,
+        DivRemOpcode::REMU => x.checked_rem(y).unwrap_or(x),
+    }
+}
+
+/// Like [VmChipTestBuilder::verify_chip], with the range tuple checker of the chip.
+fn verify(
+    tester: VmChipTestBuilder,
+    mut chip: Rv32DivRemChip,
+    range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    modify_trace: impl FnOnce(&mut RowMajorMatrix<F>),
+) {
+    let mut trace = chip.generate_trace(&tester.memory);
+    modify_trace(&mut trace);
+    let mut interactions = check_constraints(&chip.air(), &trace);
+    interactions
+        .extend(check_constraints(&range_tuple_chip.air, &range_tuple_chip.generate_trace()));
+    tester.verify(interactions);
+}
+
+/// Operands that cover the special cases and the signs of the operands and of the results.
+const EDGE_CASES: [(u32, u32); 10] = [
+    (7, 2),
+    (7, 0),
+    (0, 0),
+    (0x8000_0000, 0),
+    (0x8000_0000, u32::MAX),
+    (0x8000_0000, 1),
+    (-7i32 as u32, 2),
+    (7, -2i32 as u32),
+    (-7i32 as u32, -2i32 as u32),
+    (1, 0x8000_0000),
+];
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_divrem() {
+        for (x, y) in EDGE_CASES {
+            for signed in [false, true] {
+                let (q, r, _, _, _, case) = run_divrem(signed, &limbs(x), &limbs(y));
+                let (div, rem) = match signed {
+                    true => (DivRemOpcode::DIV, DivRemOpcode::REM),
+                    false => (DivRemOpcode::DIVU, DivRemOpcode::REMU),
+                };
+                assert_eq!(q, limbs(reference(div, x, y)), "{x:#x} / {y:#x}");
+                assert_eq!(r, limbs(reference(rem, x, y)), "{x:#x} % {y:#x}");
+                let expected_case = match (y, signed && x == 0x8000_0000 && y == u32::MAX) {
+                    (0, _) => DivRemCoreSpecialCase::ZeroDivisor,
+                    (_, true) => DivRemCoreSpecialCase::SignedOverflow,
+                    _ => DivRemCoreSpecialCase::None,
+                };
+                assert_eq!(case, expected_case);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_divrem_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        let mut rng = TestRng::default();
+        for i in 0..100 {
+            let opcode = DivRemOpcode::ALL[i % 4];
+            let [rd, rs1, rs2] = [(); 3].map(|_| 1 + rng.gen_below(31) as usize);
+            // Small divisors of either sign too, for quotients of all sizes.
+            let x = rng.next_u32();
+            let y = match rng.gen_below(3) {
+                0 => rng.next_u32(),
+                1 => rng.gen_below(1 << 12),
+                _ => rng.gen_below(1 << 12).wrapping_neg(),
+            };
+            tester.write_register(rs1, limbs(x));
+            tester.write_register(rs2, limbs(y));
+            let [x, y] = [rs1, rs2].map(|reg| compose(&tester.read_register(reg)));
+            tester.execute(&mut chip, &divrem(opcode, rd, rs1, rs2), 0x100);
+            assert_eq!(compose(&tester.read_register(rd)), reference(opcode, x, y), "{opcode:?}");
+        }
+        verify(tester, chip, range_tuple_chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    pub fn test_divrem_edge_cases() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        for (x, y) in EDGE_CASES {
+            tester.write_register(1, limbs(x));
+            tester.write_register(2, limbs(y));
+            for opcode in DivRemOpcode::ALL {
+                tester.execute(&mut chip, &divrem(opcode, 3, 1, 2), 0x100);
+                let result = compose(&tester.read_register(3));
+                assert_eq!(result, reference(opcode, x, y), "{opcode:?} {x:#x} {y:#x}");
+            }
+        }
+        verify(tester, chip, range_tuple_chip, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 5 is unbalanced")]
+    pub fn test_divrem_quotient_wraps() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(0));
+        tester.write_register(2, limbs(2));
+        tester.execute(&mut chip, &divrem(DivRemOpcode::REMU, 3, 1, 2), 0x100);
+        verify(tester, chip, range_tuple_chip, |trace| {
+            // 2 * 2^31 + 0 = 0 modulo 2^32, but not in the upper half of the product.
+            core_cols::<Rv32MultAdapterCols<F>, Rv32DivRemCoreCols<F>>(trace.row_mut(0)).q =
+                limbs(0x8000_0000).map(F::from_canonical_u32);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_divrem_missing_zero_divisor() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(5));
+        tester.write_register(2, limbs(0));
+        tester.execute(&mut chip, &divrem(DivRemOpcode::REMU, 3, 1, 2), 0x100);
+        verify(tester, chip, range_tuple_chip, |trace| {
+            core_cols::<Rv32MultAdapterCols<F>, Rv32DivRemCoreCols<F>>(trace.row_mut(0))
+                .zero_divisor = F::ZERO;
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_divrem_overflow_q_sign() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(0x8000_0000));
+        tester.write_register(2, limbs(u32::MAX));
+        tester.execute(&mut chip, &divrem(DivRemOpcode::DIV, 3, 1, 2), 0x100);
+        verify(tester, chip, range_tuple_chip, |trace| {
+            // The overflowing quotient 2^31 is not negative, as b and c have the same sign.
+            core_cols::<Rv32MultAdapterCols<F>, Rv32DivRemCoreCols<F>>(trace.row_mut(0)).q_sign =
+                F::ONE;
+        });
+    }
+}
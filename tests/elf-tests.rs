@@ -0,0 +1,218 @@
+use std::{collections::BTreeMap, fs, io};
+
+use miri_test::{
+    decoder::Rv32Decoder,
+    elf::{Elf, ElfError},
+};
+
+const MAX_MEM: u32 = 1 << 16;
+const PT_LOAD: u32 = 1;
+const PT_DYNAMIC: u32 = 2;
+const PF_RX: u32 = 0b101;
+const PF_RW: u32 = 0b110;
+const SHT_PROGBITS: u32 = 1;
+const SHT_STRTAB: u32 = 3;
+const SHT_RELA: u32 = 4;
+const SHT_DYNAMIC: u32 = 6;
+const SHT_NOBITS: u32 = 8;
+const SHT_REL: u32 = 9;
+
+struct Segment {
+    kind: u32,
+    vaddr: u32,
+    data: Vec<u8>,
+    mem_size: u32,
+    flags: u32,
+}
+
+fn code(vaddr: u32, words: &[u32]) -> Segment {
+    let data: Vec<u8> = words.iter().flat_map(|word| word.to_le_bytes()).collect();
+    Segment { kind: PT_LOAD, vaddr, mem_size: data.len() as u32, data, flags: PF_RX }
+}
+
+fn data(vaddr: u32, data: &[u8], mem_size: u32) -> Segment {
+    Segment { kind: PT_LOAD, vaddr, data: data.to_vec(), mem_size, flags: PF_RW }
+}
+
+fn put(out: &mut [u8], offset: usize, bytes: &[u8]) {
+    out[offset..offset + bytes.len()].copy_from_slice(bytes);
+}
+
+/// Writes a RISC-V ELF32 executable with the given segments and `(name, type)` sections, after
+/// which the section name string table is the last section.
+fn build_elf(entry: u32, segments: &[Segment], sections: &[(&str, u32)]) -> Vec<u8> {
+    let phoff = 52;
+    let mut out = vec![0; phoff + 32 * segments.len()];
+    out[..4].copy_from_slice(b"\x7fELF");
+    out[4..7].copy_from_slice(&[1, 1, 1]);
+    put(&mut out, 16, &2u16.to_le_bytes());
+    put(&mut out, 18, &0xf3u16.to_le_bytes());
+    put(&mut out, 20, &1u32.to_le_bytes());
+    put(&mut out, 24, &entry.to_le_bytes());
+    put(&mut out, 28, &(phoff as u32).to_le_bytes());
+    put(&mut out, 40, &52u16.to_le_bytes());
+    put(&mut out, 42, &32u16.to_le_bytes());
+    put(&mut out, 44, &(segments.len() as u16).to_le_bytes());
+    put(&mut out, 46, &40u16.to_le_bytes());
+
+    for (i, segment) in segments.iter().enumerate() {
+        let offset = out.len() as u32;
+        out.extend(&segment.data);
+        let fields = [
+            segment.kind,
+            offset,
+            segment.vaddr,
+            segment.vaddr,
+            segment.data.len() as u32,
+            segment.mem_size,
+            segment.flags,
+            4,
+        ];
+        for (j, field) in fields.into_iter().enumerate() {
+            put(&mut out, phoff + 32 * i + 4 * j, &field.to_le_bytes());
+        }
+    }
+
+    let sections: Vec<_> = sections.iter().copied().chain([(".shstrtab", SHT_STRTAB)]).collect();
+    let strtab_offset = out.len() as u32;
+    let mut names = Vec::new();
+    out.push(0);
+    for (name, _) in &sections {
+        names.push(out.len() as u32 - strtab_offset);
+        out.extend(name.as_bytes());
+        out.push(0);
+    }
+    let strtab_size = out.len() as u32 - strtab_offset;
+
+    let shoff = out.len();
+    out.resize(shoff + 40 * (sections.len() + 1), 0);
+    for (i, ((_, kind), name)) in sections.iter().zip(names).enumerate() {
+        let header = shoff + 40 * (i + 1);
+        put(&mut out, header, &name.to_le_bytes());
+        put(&mut out, header + 4, &kind.to_le_bytes());
+        if i == sections.len() - 1 {
+            put(&mut out, header + 16, &strtab_offset.to_le_bytes());
+            put(&mut out, header + 20, &strtab_size.to_le_bytes());
+        }
+    }
+    put(&mut out, 32, &(shoff as u32).to_le_bytes());
+    put(&mut out, 48, &(sections.len() as u16 + 1).to_le_bytes());
+    put(&mut out, 50, &(sections.len() as u16).to_le_bytes());
+    out
+}
+
+/// `addi x1, x0, -1`, `add x3, x1, x2` and `jalr x0, 0(x1)`.
+const WORDS: [u32; 3] = [0xfff0_0093, 0x0020_81b3, 0x0000_8067];
+
+fn program() -> Vec<u8> {
+    build_elf(
+        0x1004,
+        &[code(0x1000, &WORDS[..2]), code(0x1008, &WORDS[2..]), data(0x2000, &[1, 2, 3, 4, 5], 12)],
+        &[(".text", SHT_PROGBITS), (".data", SHT_PROGBITS), (".bss", SHT_NOBITS)],
+    )
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_elf_decode() {
+        let elf = Elf::decode(&program(), MAX_MEM).unwrap();
+        assert_eq!(elf.pc_start, 0x1004);
+        assert_eq!(elf.pc_base, 0x1000);
+        assert_eq!(elf.instructions, WORDS);
+        let image = BTreeMap::from([(0x2000, 0x0403_0201), (0x2004, 0x05), (0x2008, 0)]);
+        assert_eq!(elf.memory_image, image);
+        assert!(Rv32Decoder::default().decode_all(&elf.instructions).is_ok());
+    }
+
+    #[test]
+    pub fn test_elf_load() {
+        let path = std::env::temp_dir().join(format!("elf-tests-{}.elf", std::process::id()));
+        fs::write(&path, program()).unwrap();
+        let elf = Elf::load(&path, MAX_MEM);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(elf.unwrap(), Elf::decode(&program(), MAX_MEM).unwrap());
+        assert_eq!(Elf::load(&path, MAX_MEM), Err(ElfError::Io(io::ErrorKind::NotFound)));
+    }
+
+    #[test]
+    pub fn test_elf_reject_header() {
+        let cases: [(usize, &[u8], ElfError); 5] = [
+            (0, b"\x7fELG", ElfError::BadMagic),
+            (4, &[2], ElfError::UnsupportedClass(2)),
+            (5, &[2], ElfError::UnsupportedEncoding(2)),
+            (16, &[1, 0], ElfError::UnsupportedType(1)),
+            (18, &[0x3e, 0], ElfError::UnsupportedMachine(0x3e)),
+        ];
+        for (offset, bytes, error) in cases {
+            let mut elf = program();
+            elf[offset..offset + bytes.len()].copy_from_slice(bytes);
+            assert_eq!(Elf::decode(&elf, MAX_MEM), Err(error));
+        }
+        assert_eq!(
+            Elf::decode(&program()[..40], MAX_MEM),
+            Err(ElfError::Truncated { offset: 0, len: 52 })
+        );
+    }
+
+    #[test]
+    pub fn test_elf_reject_entry() {
+        for entry in [0x1002, MAX_MEM] {
+            let elf = build_elf(entry, &[code(0x1000, &WORDS)], &[]);
+            assert_eq!(Elf::decode(&elf, MAX_MEM), Err(ElfError::InvalidEntry(entry)));
+        }
+    }
+
+    #[test]
+    pub fn test_elf_reject_sections() {
+        let sections = [(".text", SHT_PROGBITS), (".rela.text", SHT_RELA)];
+        let elf = build_elf(0x1000, &[code(0x1000, &WORDS)], &sections);
+        let error = Elf::decode(&elf, MAX_MEM).unwrap_err();
+        assert_eq!(error, ElfError::Relocation { index: 2, name: ".rela.text".to_string() });
+        assert_eq!(error.to_string(), "section 2 (.rela.text) has unsupported relocations");
+
+        let elf = build_elf(0x1000, &[code(0x1000, &WORDS)], &[(".dynamic", SHT_DYNAMIC)]);
+        assert_eq!(
+            Elf::decode(&elf, MAX_MEM),
+            Err(ElfError::UnsupportedSection { index: 1, name: ".dynamic".to_string(), kind: 6 })
+        );
+
+        // A REL section whose name offset overflows past the end of the string table.
+        let mut elf = build_elf(0x1000, &[code(0x1000, &WORDS)], &[(".rel.text", SHT_REL)]);
+        let shoff = u32::from_le_bytes(elf[32..36].try_into().unwrap()) as usize;
+        put(&mut elf, shoff + 40, &u32::MAX.to_le_bytes());
+        let strtab = &elf[shoff + 80 + 16..shoff + 80 + 20];
+        let strtab_offset = u32::from_le_bytes(strtab.try_into().unwrap()) as usize;
+        assert_eq!(
+            Elf::decode(&elf, MAX_MEM),
+            Err(ElfError::Truncated { offset: strtab_offset, len: u32::MAX as usize })
+        );
+    }
+
+    #[test]
+    pub fn test_elf_reject_segments() {
+        let dynamic = Segment { kind: PT_DYNAMIC, ..data(0x2000, &[], 0) };
+        let elf = build_elf(0x1000, &[code(0x1000, &WORDS), dynamic], &[]);
+        assert_eq!(
+            Elf::decode(&elf, MAX_MEM),
+            Err(ElfError::UnsupportedSegment { index: 1, kind: PT_DYNAMIC })
+        );
+
+        for (vaddr, mem_size) in [(0x2002, 4), (MAX_MEM - 4, 8), (0xffff_fffc, 8)] {
+            let elf = build_elf(0x1000, &[code(0x1000, &WORDS), data(vaddr, &[], mem_size)], &[]);
+            assert_eq!(
+                Elf::decode(&elf, MAX_MEM),
+                Err(ElfError::InvalidSegment { index: 1, vaddr, mem_size })
+            );
+        }
+        let elf = build_elf(0x1000, &[data(0x2000, &[1, 2, 3, 4, 5], 4)], &[]);
+        assert_eq!(
+            Elf::decode(&elf, MAX_MEM),
+            Err(ElfError::InvalidSegment { index: 0, vaddr: 0x2000, mem_size: 4 })
+        );
+
+        let elf = build_elf(0x1000, &[code(0x1000, &WORDS), code(0x1010, &WORDS)], &[]);
+        assert_eq!(Elf::decode(&elf, MAX_MEM), Err(ElfError::NonContiguousCode { vaddr: 0x1010 }));
+    }
+}
@@ -0,0 +1,101 @@
+use miri_test::{
+    encoder::Encoder,
+    openvm_stark_backend::{
+        air::{Air, AirBuilder, BaseAir},
+        debug::check_constraints,
+        field::{F, FieldAlgebra},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+};
+
+const NUM_FLAGS: usize = 7;
+
+/// Columns `[vars[encoder.width()], flags[NUM_FLAGS]]`, where `flags` is the one-hot decoding of
+/// `vars`.
+struct FlagAir(Encoder);
+
+impl BaseAir<F> for FlagAir {
+    fn width(&self) -> usize {
+        self.0.width() + NUM_FLAGS
+    }
+}
+
+impl<AB: AirBuilder<F = F>> Air<AB> for FlagAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let (vars, flags) = local.split_at(self.0.width());
+        self.0.eval(builder, vars);
+
+        for (i, &flag) in flags.iter().enumerate() {
+            builder.assert_eq(flag, self.0.get_flag_expr::<AB>(i, vars));
+        }
+        let any = flags.iter().fold(AB::Expr::ZERO, |acc, &flag| acc + flag);
+        builder.assert_eq(any, self.0.is_valid::<AB>(vars));
+        let opcode = flags
+            .iter()
+            .enumerate()
+            .fold(AB::Expr::ZERO, |acc, (i, &flag)| acc + flag * F::from_canonical_usize(i + 1));
+        let idx_vals: Vec<_> = (0..NUM_FLAGS).map(|i| (i, i + 1)).collect();
+        builder.assert_eq(opcode, self.0.flag_with_val::<AB>(vars, &idx_vals));
+    }
+}
+
+fn flag_row(encoder: &Encoder, flag_idx: Option<usize>) -> Vec<F> {
+    let mut row = vec![F::ZERO; encoder.width() + NUM_FLAGS];
+    let (vars, flags) = row.split_at_mut(encoder.width());
+    encoder.generate_subrow(flag_idx, vars);
+    if let Some(i) = flag_idx {
+        flags[i] = F::ONE;
+    }
+    row
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_encoder_width() {
+        // 7 flags and the invalid point fit in the 10 points of degree at most 2 in 3 variables.
+        assert_eq!(Encoder::new(NUM_FLAGS, 2, true).width(), 3);
+        // 6 points of degree at most 2 in 2 variables.
+        assert_eq!(Encoder::new(6, 2, false).width(), 2);
+        assert_eq!(Encoder::new(6, 2, true).width(), 3);
+        assert_eq!(Encoder::new(4, 1, false).width(), 3);
+    }
+
+    #[test]
+    pub fn test_encoder_flags() {
+        for max_degree in 1..=3 {
+            let encoder = Encoder::new(NUM_FLAGS, max_degree, true);
+            let air = FlagAir(encoder.clone());
+            let mut values = flag_row(&encoder, None);
+            for i in 0..NUM_FLAGS {
+                values.extend(flag_row(&encoder, Some(i)));
+            }
+            check_constraints(&air, &RowMajorMatrix::new(values, air.width()));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_encoder_out_of_range_coordinate() {
+        let encoder = Encoder::new(NUM_FLAGS, 2, true);
+        let air = FlagAir(encoder.clone());
+        let mut row = flag_row(&encoder, None);
+        row[0] = F::from_canonical_u32(3);
+        check_constraints(&air, &RowMajorMatrix::new(row, air.width()));
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_encoder_unused_point() {
+        let encoder = Encoder::new(NUM_FLAGS, 2, true);
+        let air = FlagAir(encoder.clone());
+        // The last point `(2, 0, 0)` of the simplex encodes no flag.
+        let mut row = flag_row(&encoder, None);
+        row[0] = F::from_canonical_u32(2);
+        check_constraints(&air, &RowMajorMatrix::new(row, air.width()));
+    }
+}
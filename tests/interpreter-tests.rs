@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+
+use miri_test::{
+    asm::assemble_instructions,
+    decoder::Rv32Decoder,
+    execution::ExecutionError,
+    instructions::{BaseAluOpcode, Instruction, LocalOpcode, VmOpcode},
+    interpreter::Rv32Interpreter,
+    program::Program,
+    testing::TEST_MEMORY_CONFIG,
+};
+
+const PC_BASE: u32 = 0x1000;
+
+fn interpreter_with(decoder: Rv32Decoder, words: &[u32]) -> Rv32Interpreter {
+    let program = Program::new(decoder.decode_all(words).unwrap(), PC_BASE);
+    Rv32Interpreter::new(program, PC_BASE, TEST_MEMORY_CONFIG)
+}
+
+fn interpreter(words: &[u32]) -> Rv32Interpreter {
+    interpreter_with(Rv32Decoder::default(), words)
+}
+
+/// Sums `1..=10` in a loop, then goes through memory, a jump over an instruction, a
+/// multiplication, an AUIPC-relative indirect jump and a division by zero.
+const PROGRAM: [u32; 17] = [
+    0x00a0_0093, // addi x1, x0, 10
+    0x0000_0113, // addi x2, x0, 0
+    0x0011_0133, // add x2, x2, x1
+    0xfff0_8093, // addi x1, x1, -1
+    0xfe00_9ce3, // bne x1, x0, -8
+    0x0000_21b7, // lui x3, 0x2
+    0x0021_a223, // sw x2, 4(x3)
+    0x0080_02ef, // jal x5, 8
+    0x0010_0313, // addi x6, x0, 1
+    0x0041_a203, // lw x4, 4(x3)
+    0x0242_0233, // mul x4, x4, x4
+    0x0000_0397, // auipc x7, 0
+    0x00c3_8467, // jalr x8, 12(x7)
+    0x0020_0313, // addi x6, x0, 2
+    0x0212_54b3, // divu x9, x4, x1
+    0x0011_c503, // lbu x10, 1(x3)
+    0x0000_0013, // addi x0, x0, 0
+];
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_interpreter_program() {
+        let mut interpreter = interpreter(&PROGRAM);
+        interpreter.set_memory_image(&BTreeMap::from([(0x2000, 0x1234_5678)]));
+        assert_eq!(interpreter.run(100), Ok(42));
+        assert!(interpreter.is_halted());
+        assert_eq!(interpreter.state().pc, PC_BASE + 4 * PROGRAM.len() as u32);
+        assert_eq!(interpreter.state().timestamp, interpreter.memory.timestamp());
+
+        let expected = [0, 0, 55, 0x2000, 3025, 0x1020, 0, 0x102c, 0x1034, 0xffff_ffff, 0x56, 0];
+        for (reg, value) in expected.into_iter().enumerate() {
+            assert_eq!(interpreter.register(reg), value, "x{reg}");
+        }
+        assert_eq!(interpreter.memory_word(0x2004), 55);
+        // The final nop runs on the JAL chip.
+        assert_eq!(interpreter.chips.base_alu.current_trace_height(), 22);
+        assert_eq!(interpreter.chips.branch_eq.current_trace_height(), 10);
+        assert_eq!(interpreter.chips.load_store.current_trace_height(), 3);
+        interpreter.verify();
+    }
+
+    #[test]
+    pub fn test_interpreter_step() {
+        let mut interpreter = interpreter(&PROGRAM[..2]);
+        let initial_state = interpreter.state();
+        interpreter.step().unwrap();
+        assert_eq!(interpreter.register(1), 10);
+        assert_eq!(interpreter.state().pc, PC_BASE + 4);
+        assert!(interpreter.state().timestamp > initial_state.timestamp);
+        interpreter.step().unwrap();
+        assert!(interpreter.is_halted());
+        assert_eq!(
+            interpreter.step(),
+            Err(ExecutionError::PcNotFound { pc: PC_BASE + 8, pc_base: PC_BASE, program_len: 2 })
+        );
+    }
+
+    #[test]
+    pub fn test_interpreter_memory_faults() {
+        // lw x1, 2(x0)
+        assert_eq!(
+            interpreter(&[0x0020_2083]).run(1),
+            Err(ExecutionError::MisalignedAccess { pc: PC_BASE, address: 2, width: 4 })
+        );
+        // sh x1, 1(x0)
+        assert_eq!(
+            interpreter(&[0x0010_10a3]).run(1),
+            Err(ExecutionError::MisalignedAccess { pc: PC_BASE, address: 1, width: 2 })
+        );
+        // lw x1, 0(x2) beyond the 16 bits of the memory
+        let mut interpreter = interpreter(&[0x0001_2083]);
+        interpreter.set_register(2, 0x1_0000);
+        let error = interpreter.run(1).unwrap_err();
+        assert_eq!(error, ExecutionError::AddressOutOfBounds { pc: PC_BASE, address: 0x1_0000 });
+        assert_eq!(error.to_string(), "at pc 0x1000: address 0x10000 out of bounds");
+        assert_eq!(interpreter.state().pc, PC_BASE);
+    }
+
+    #[test]
+    pub fn test_interpreter_control_faults() {
+        // jalr x2, 0(x1) beyond the 30 bits of the pc
+        let mut jalr = interpreter(&[0x0000_8167]);
+        jalr.set_register(1, 0x4000_0000);
+        assert_eq!(
+            jalr.run(1),
+            Err(ExecutionError::PcOutOfBounds { pc: PC_BASE, to_pc: 0x4000_0000 })
+        );
+        // jal x1, -8 from pc 0 wraps around the field
+        let program = Program::new(Rv32Decoder::default().decode_all(&[0xff9f_f0ef]).unwrap(), 0);
+        let mut jal = Rv32Interpreter::new(program, 0, TEST_MEMORY_CONFIG);
+        assert_eq!(jal.run(1), Err(ExecutionError::PcOutOfBounds { pc: 0, to_pc: 0x77ff_fff9 }));
+        // beq x0, x0, 16 beyond the program
+        assert_eq!(
+            interpreter(&[0x0000_0863]).run(2),
+            Err(ExecutionError::PcNotFound { pc: PC_BASE + 16, pc_base: PC_BASE, program_len: 1 })
+        );
+        // beq x0, x0, 0
+        assert_eq!(
+            interpreter(&[0x0000_0063]).run(10),
+            Err(ExecutionError::StepLimit { max_steps: 10 })
+        );
+    }
+
+    #[test]
+    pub fn test_interpreter_zero_register_writes() {
+        // The writes to x0 of j, ret and the instructions in between are discarded.
+        let source = "
+            jal ra, call
+            j end
+            nop
+        call:
+            addi zero, ra, 1
+            lw zero, 0(zero)
+            lui zero, 1
+            ret
+        end:
+        ";
+        let program = Program::new(assemble_instructions(source).unwrap(), PC_BASE);
+        let mut interpreter = Rv32Interpreter::new(program, PC_BASE, TEST_MEMORY_CONFIG);
+        assert_eq!(interpreter.run(10), Ok(6));
+        assert_eq!(interpreter.register(0), 0);
+        assert_eq!(interpreter.register(1), PC_BASE + 4);
+        interpreter.verify();
+    }
+
+    #[test]
+    pub fn test_interpreter_zero_register_write() {
+        // addi x1, x0, 21, then add x0, x1, x1 built by hand, since the decoder makes it a nop.
+        let mut instructions = Rv32Decoder::default().decode_all(&[0x0150_0093]).unwrap();
+        instructions
+            .push(Instruction::from_usize(BaseAluOpcode::ADD.global_opcode(), [0, 4, 4, 1, 1]));
+        let program = Program::new(instructions, PC_BASE);
+        let mut interpreter = Rv32Interpreter::new(program, PC_BASE, TEST_MEMORY_CONFIG);
+        assert_eq!(interpreter.run(2), Err(ExecutionError::ZeroRegisterWrite { pc: PC_BASE + 4 }));
+        assert_eq!(interpreter.register(0), 0);
+        assert_eq!(interpreter.register(1), 21);
+    }
+
+    #[test]
+    pub fn test_interpreter_timestamp_overflow() {
+        let source = "
+        loop:
+            addi x1, x1, 1
+            jal x0, loop
+        ";
+        let program = Program::new(assemble_instructions(source).unwrap(), PC_BASE);
+        let mut interpreter = Rv32Interpreter::new(program, PC_BASE, TEST_MEMORY_CONFIG);
+        // Each iteration takes four of the 2^16 timestamps, which run out at the last JAL.
+        assert_eq!(
+            interpreter.run(100_000),
+            Err(ExecutionError::TimestampOverflow { pc: PC_BASE + 4, timestamp: 1 << 16 })
+        );
+        assert_eq!(interpreter.register(1), 1 << 14);
+    }
+
+    #[test]
+    pub fn test_interpreter_register_out_of_bounds() {
+        // add x1, x1, x1 with rs2 at the unaligned pointer 6 and then beyond the memory.
+        for (c, error) in [
+            (6, ExecutionError::MisalignedAccess { pc: PC_BASE, address: 6, width: 4 }),
+            (1 << 16, ExecutionError::AddressOutOfBounds { pc: PC_BASE, address: 1 << 16 }),
+        ] {
+            let add = Instruction::from_usize(BaseAluOpcode::ADD.global_opcode(), [4, 4, c, 1, 1]);
+            let program = Program::new(vec![add], PC_BASE);
+            let mut interpreter = Rv32Interpreter::new(program, PC_BASE, TEST_MEMORY_CONFIG);
+            assert_eq!(interpreter.run(1), Err(error));
+        }
+    }
+
+    #[test]
+    pub fn test_interpreter_disabled_operation() {
+        let opcode = VmOpcode::from_usize(BaseAluOpcode::ADD.global_opcode().as_usize() + 0x1000);
+        // add x3, x1, x2 with the opcodes of another VM
+        let mut interpreter = interpreter_with(Rv32Decoder::new(0x1000), &[0x0020_81b3]);
+        assert_eq!(
+            interpreter.run(1),
+            Err(ExecutionError::DisabledOperation { pc: PC_BASE, opcode })
+        );
+    }
+}
@@ -0,0 +1,204 @@
+use miri_test::{
+    bus::VariableRangeCheckerBus,
+    is_less_than::{IsLessThanIo, IsLtSubAir},
+    is_less_than_array::{
+        IsLtArrayAuxColsMut, IsLtArrayAuxColsRef, IsLtArrayIo, IsLtArraySubAir,
+        IsLtArrayWhenTransitionAir,
+    },
+    openvm_stark_backend::{
+        air::{Air, AirBuilder, BaseAir},
+        debug::{check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+    utils::implies,
+    var_range::VariableRangeCheckerChip,
+};
+
+const RANGE_MAX_BITS: usize = 4;
+const MAX_BITS: usize = 10;
+const AUX_LEN: usize = 3;
+const BUS: VariableRangeCheckerBus = VariableRangeCheckerBus::new(0, RANGE_MAX_BITS);
+
+/// Columns `[x, y, out, lower_decomp[AUX_LEN]]`.
+struct LtAir(IsLtSubAir);
+
+impl BaseAir<F> for LtAir {
+    fn width(&self) -> usize {
+        3 + AUX_LEN
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for LtAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let io = IsLessThanIo::new(local[0], local[1], local[2], AB::Expr::ONE);
+        self.0.eval(builder, (io, &local[3..]));
+    }
+}
+
+/// Address stored as address space, pointer
+const ADDR_ELTS: usize = 2;
+const SORTED_WIDTH: usize = ADDR_ELTS + 1 + ADDR_ELTS + 1 + AUX_LEN;
+
+/// A cut-down `VolatileBoundaryAir` whose valid rows hold strictly increasing addresses, with
+/// columns `[addr_space, pointer, is_valid, diff_marker[ADDR_ELTS], diff_inv, lt_decomp[AUX_LEN]]`.
+struct SortedAddressAir(IsLtArrayWhenTransitionAir<ADDR_ELTS>);
+
+fn aux_ref<T>(row: &[T]) -> IsLtArrayAuxColsRef<'_, T> {
+    let (diff_marker, rest) = row[3..].split_at(ADDR_ELTS);
+    IsLtArrayAuxColsRef { diff_marker, diff_inv: &rest[0], lt_decomp: &rest[1..] }
+}
+
+fn aux_mut<T>(row: &mut [T]) -> IsLtArrayAuxColsMut<'_, T> {
+    let (diff_marker, rest) = row[3..].split_at_mut(ADDR_ELTS);
+    let (diff_inv, lt_decomp) = rest.split_first_mut().unwrap();
+    IsLtArrayAuxColsMut { diff_marker, diff_inv, lt_decomp }
+}
+
+impl BaseAir<F> for SortedAddressAir {
+    fn width(&self) -> usize {
+        SORTED_WIDTH
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for SortedAddressAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let (local, next) = (main.row_slice(0), main.row_slice(1));
+        let (local_valid, next_valid) = (local[2], next[2]);
+
+        builder.assert_bool(local_valid);
+        // Ensuring all non-padding rows are at the top
+        builder.when_transition().assert_one(implies::<AB::Expr>(next_valid, local_valid));
+
+        let io = IsLtArrayIo {
+            x: [local[0], local[1]].map(Into::into),
+            y: [next[0], next[1]].map(Into::into),
+            out: AB::Expr::ONE,
+            count: next_valid.into(),
+        };
+        // N.B.: this will do range checks (but not other constraints) on the last row if the
+        // first row has is_valid = 1 due to wraparound
+        self.0.eval(builder, (io, aux_ref(local)));
+    }
+}
+
+/// Generates the trace for `addresses` padded to `height` rows. The aux columns of the last row
+/// are only filled when `fill_wraparound` is set.
+fn sorted_trace(
+    lt: &IsLtArraySubAir<ADDR_ELTS>,
+    range_checker: &VariableRangeCheckerChip,
+    addresses: &[[u32; ADDR_ELTS]],
+    height: usize,
+    fill_wraparound: bool,
+) -> RowMajorMatrix<F> {
+    let mut trace = RowMajorMatrix::new(vec![F::ZERO; height * SORTED_WIDTH], SORTED_WIDTH);
+    for (row, addr) in trace.rows_mut().zip(addresses) {
+        row[..ADDR_ELTS].copy_from_slice(&addr.map(F::from_canonical_u32));
+        row[2] = F::ONE;
+    }
+    let rows: Vec<Vec<F>> = trace.rows().map(<[F]>::to_vec).collect();
+    for (i, row) in trace.rows_mut().enumerate() {
+        let next = &rows[(i + 1) % height];
+        if next[2] == F::ZERO || (i == height - 1 && !fill_wraparound) {
+            continue;
+        }
+        let mut out = F::ZERO;
+        let x = row[..ADDR_ELTS].to_vec();
+        lt.generate_subrow((range_checker, &x, &next[..ADDR_ELTS]), (aux_mut(row), &mut out));
+    }
+    trace
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_is_less_than() {
+        let range_checker = VariableRangeCheckerChip::new(BUS);
+        let lt = IsLtSubAir::new(BUS, MAX_BITS);
+        let pairs = [(0, 0), (0, 1), (1, 0), (1023, 1023), (0, 1023), (1023, 0), (511, 512)];
+        let mut trace =
+            RowMajorMatrix::new(vec![F::ZERO; pairs.len() * (3 + AUX_LEN)], 3 + AUX_LEN);
+        for (row, &(x, y)) in trace.rows_mut().zip(&pairs) {
+            row[0] = F::from_canonical_u32(x);
+            row[1] = F::from_canonical_u32(y);
+            let (out, lower_decomp) = row[2..].split_first_mut().unwrap();
+            lt.generate_subrow((&range_checker, x, y), (lower_decomp, out));
+            assert_eq!(*out, F::from_bool(x < y));
+        }
+
+        let mut interactions = check_constraints(&LtAir(lt), &trace);
+        interactions.extend(check_constraints(&range_checker.air, &range_checker.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_is_less_than_wrong_out() {
+        let range_checker = VariableRangeCheckerChip::new(BUS);
+        let lt = IsLtSubAir::new(BUS, MAX_BITS);
+        let mut row = vec![F::from_canonical_u32(5), F::from_canonical_u32(3), F::ZERO];
+        row.extend([F::ZERO; AUX_LEN]);
+        let (out, lower_decomp) = row[2..].split_first_mut().unwrap();
+        lt.generate_subrow((&range_checker, 5, 3), (lower_decomp, out));
+        row[2] = F::ONE;
+        check_constraints(&LtAir(lt), &RowMajorMatrix::new(row, 3 + AUX_LEN));
+    }
+
+    #[test]
+    pub fn test_is_less_than_array_sorted() {
+        let range_checker = VariableRangeCheckerChip::new(BUS);
+        let lt = IsLtArraySubAir::<ADDR_ELTS>::new(BUS, MAX_BITS);
+        let addresses = [[1, 7], [1, 8], [2, 0], [2, 1023], [4, 3]];
+        let trace = sorted_trace(&lt, &range_checker, &addresses, 8, true);
+
+        let mut interactions = check_constraints(&SortedAddressAir(lt.when_transition()), &trace);
+        interactions.extend(check_constraints(&range_checker.air, &range_checker.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 1")]
+    pub fn test_is_less_than_array_unsorted() {
+        let range_checker = VariableRangeCheckerChip::new(BUS);
+        let lt = IsLtArraySubAir::<ADDR_ELTS>::new(BUS, MAX_BITS);
+        let addresses = [[1, 7], [2, 8], [2, 8], [3, 0]];
+        let trace = sorted_trace(&lt, &range_checker, &addresses, 4, true);
+        check_constraints(&SortedAddressAir(lt.when_transition()), &trace);
+    }
+
+    /// The last row wraps around to the first, which is valid, so the last row is range checked
+    /// even though `last < first` is not constrained.
+    #[test]
+    pub fn test_is_less_than_array_wraparound_is_not_constrained() {
+        let range_checker = VariableRangeCheckerChip::new(BUS);
+        let lt = IsLtArraySubAir::<ADDR_ELTS>::new(BUS, MAX_BITS);
+        // No padding, so the last row is valid and its address is larger than the first one.
+        let addresses = [[1, 7], [1, 8], [2, 0], [3, 5]];
+        let trace = sorted_trace(&lt, &range_checker, &addresses, 4, true);
+
+        let mut interactions = check_constraints(&SortedAddressAir(lt.when_transition()), &trace);
+        interactions.extend(check_constraints(&range_checker.air, &range_checker.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 0 is unbalanced")]
+    pub fn test_is_less_than_array_wraparound_is_range_checked() {
+        let range_checker = VariableRangeCheckerChip::new(BUS);
+        let lt = IsLtArraySubAir::<ADDR_ELTS>::new(BUS, MAX_BITS);
+        let addresses = [[1, 7], [1, 8], [2, 0]];
+        // The last row is padding, but it still range checks its (zero) limbs since the first row
+        // is valid, and those checks are never recorded in the range checker.
+        let trace = sorted_trace(&lt, &range_checker, &addresses, 4, false);
+
+        let mut interactions = check_constraints(&SortedAddressAir(lt.when_transition()), &trace);
+        interactions.extend(check_constraints(&range_checker.air, &range_checker.generate_trace()));
+        verify_interactions(interactions);
+    }
+}
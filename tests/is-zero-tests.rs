@@ -0,0 +1,159 @@
+use miri_test::{
+    is_equal::{IsEqSubAir, IsEqualIo},
+    is_equal_array::{IsEqArrayIo, IsEqArraySubAir},
+    is_zero::{IsZeroIo, IsZeroSubAir},
+    openvm_stark_backend::{
+        air::{Air, AirBuilder, BaseAir},
+        debug::check_constraints,
+        field::{F, FieldAlgebra},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    sub_air::{SubAir, TraceSubRowGenerator},
+};
+
+/// Columns `[x, out, inv, condition]`.
+struct IsZeroAir;
+
+impl BaseAir<F> for IsZeroAir {
+    fn width(&self) -> usize {
+        4
+    }
+}
+
+impl<AB: AirBuilder<F = F>> Air<AB> for IsZeroAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let io = IsZeroIo::new(local[0].into(), local[1].into(), local[3].into());
+        IsZeroSubAir.eval(builder, (io, local[2]));
+    }
+}
+
+/// Columns `[x, y, out, inv]`.
+struct IsEqAir;
+
+impl BaseAir<F> for IsEqAir {
+    fn width(&self) -> usize {
+        4
+    }
+}
+
+impl<AB: AirBuilder<F = F>> Air<AB> for IsEqAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let io = IsEqualIo::new(local[0].into(), local[1].into(), local[2].into(), AB::Expr::ONE);
+        IsEqSubAir.eval(builder, (io, local[3]));
+    }
+}
+
+const NUM: usize = 3;
+
+/// Columns `[x[NUM], y[NUM], out, diff_inv_marker[NUM]]`.
+struct IsEqArrayAir;
+
+impl BaseAir<F> for IsEqArrayAir {
+    fn width(&self) -> usize {
+        3 * NUM + 1
+    }
+}
+
+impl<AB: AirBuilder<F = F>> Air<AB> for IsEqArrayAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let io = IsEqArrayIo {
+            x: core::array::from_fn(|i| local[i].into()),
+            y: core::array::from_fn(|i| local[NUM + i].into()),
+            out: local[2 * NUM].into(),
+            condition: AB::Expr::ONE,
+        };
+        let diff_inv_marker = core::array::from_fn(|i| local[2 * NUM + 1 + i]);
+        IsEqArraySubAir::<NUM>.eval(builder, (io, diff_inv_marker));
+    }
+}
+
+fn is_zero_row(x: F) -> Vec<F> {
+    let (mut inv, mut out) = (F::ZERO, F::ZERO);
+    IsZeroSubAir.generate_subrow(x, (&mut inv, &mut out));
+    vec![x, out, inv, F::ONE]
+}
+
+fn is_eq_array_row(x: [u32; NUM], y: [u32; NUM]) -> Vec<F> {
+    let (x, y) = (x.map(F::from_canonical_u32), y.map(F::from_canonical_u32));
+    let (mut diff_inv_marker, mut out) = ([F::ZERO; NUM], F::ZERO);
+    IsEqArraySubAir::<NUM>.generate_subrow((&x, &y), (&mut diff_inv_marker, &mut out));
+    [x.as_slice(), &y, &[out], &diff_inv_marker].concat()
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_is_zero() {
+        let values = [F::ZERO, F::ONE, -F::ONE, F::from_canonical_u32(1 << 20)];
+        let rows: Vec<Vec<F>> = values.into_iter().map(is_zero_row).collect();
+        for (row, x) in rows.iter().zip(values) {
+            assert_eq!(row[1], F::from_bool(x == F::ZERO));
+        }
+        // Constraints are not imposed on rows with `condition = 0`.
+        let padding = vec![F::ZERO, F::ZERO, F::ONE, F::ZERO];
+        check_constraints(&IsZeroAir, &RowMajorMatrix::new([rows.concat(), padding].concat(), 4));
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_is_zero_wrong_out_for_zero() {
+        check_constraints(
+            &IsZeroAir,
+            &RowMajorMatrix::new(vec![F::ZERO, F::ZERO, F::ONE, F::ONE], 4),
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_is_zero_wrong_out_for_nonzero() {
+        check_constraints(
+            &IsZeroAir,
+            &RowMajorMatrix::new(vec![F::ONE, F::ONE, F::ZERO, F::ONE], 4),
+        );
+    }
+
+    #[test]
+    pub fn test_is_equal() {
+        let pairs = [(0, 0), (7, 7), (7, 8), (8, 7)];
+        let values = pairs
+            .into_iter()
+            .flat_map(|(x, y)| {
+                let (x, y) = (F::from_canonical_u32(x), F::from_canonical_u32(y));
+                let (mut inv, mut out) = (F::ZERO, F::ZERO);
+                IsEqSubAir.generate_subrow((x, y), (&mut inv, &mut out));
+                assert_eq!(out, F::from_bool(x == y));
+                [x, y, out, inv]
+            })
+            .collect();
+        check_constraints(&IsEqAir, &RowMajorMatrix::new(values, 4));
+    }
+
+    #[test]
+    pub fn test_is_equal_array() {
+        let rows = [
+            is_eq_array_row([1, 2, 3], [1, 2, 3]),
+            is_eq_array_row([0, 2, 3], [1, 2, 3]),
+            is_eq_array_row([1, 2, 3], [1, 2, 4]),
+            is_eq_array_row([0, 0, 0], [0, 0, 0]),
+        ];
+        let outs: Vec<F> = rows.iter().map(|row| row[2 * NUM]).collect();
+        assert_eq!(outs, [1, 0, 0, 1].map(F::from_canonical_u32));
+        check_constraints(&IsEqArrayAir, &RowMajorMatrix::new(rows.concat(), 3 * NUM + 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_is_equal_array_wrong_out() {
+        let mut row = is_eq_array_row([1, 2, 3], [1, 2, 4]);
+        row[2 * NUM] = F::ONE;
+        row[2 * NUM + 1..].fill(F::ZERO);
+        check_constraints(&IsEqArrayAir, &RowMajorMatrix::new(row, 3 * NUM + 1));
+    }
+}
@@ -0,0 +1,135 @@
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32CondRdWriteAdapterChip, Rv32CondRdWriteAdapterCols, compose},
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{Instruction, LocalOpcode, Rv32JalLuiOpcode},
+    integration_api::VmChipWrapper,
+    jal_lui::{Rv32JalLuiChip, Rv32JalLuiCoreChip, Rv32JalLuiCoreCols, run_jal_lui},
+    openvm_stark_backend::{
+        field::{F, FieldAlgebra},
+        matrix::Matrix,
+    },
+    testing::{TestRng, VmChipTestBuilder, core_cols},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32JalLuiChip {
+    VmChipWrapper::new(
+        Rv32CondRdWriteAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32JalLuiCoreChip::new(tester.bitwise_chip.clone()),
+    )
+}
+
+/// `JAL rd, imm` where the signed `imm` is encoded as a field element, and `rd` is only written
+/// if it is not `x0`.
+fn jal(rd: usize, imm: i32) -> Instruction<F> {
+    let mut instruction = Instruction::from_usize(
+        Rv32JalLuiOpcode::JAL.global_opcode(),
+        [rd * RV32_REGISTER_NUM_LIMBS, 0, 0, RV32_REGISTER_AS as usize, 0, (rd != 0) as usize],
+    );
+    instruction.c = match imm < 0 {
+        true => -F::from_canonical_u32(imm.unsigned_abs()),
+        false => F::from_canonical_u32(imm as u32),
+    };
+    instruction
+}
+
+/// `LUI rd, imm` where `imm` holds the upper 20 bits of `rd`.
+fn lui(rd: usize, imm: u32) -> Instruction<F> {
+    Instruction::from_usize(
+        Rv32JalLuiOpcode::LUI.global_opcode(),
+        [rd * RV32_REGISTER_NUM_LIMBS, 0, imm as usize, RV32_REGISTER_AS as usize, 0, 1],
+    )
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_jal_lui() {
+        assert_eq!(run_jal_lui(Rv32JalLuiOpcode::JAL, 0x1000, -8), (0xff8, [0x04, 0x10, 0, 0]));
+        assert_eq!(
+            run_jal_lui(Rv32JalLuiOpcode::JAL, 0x3fff_fff8, 0x800),
+            (0x4000_07f8, [0xfc, 0xff, 0xff, 0x3f])
+        );
+        assert_eq!(
+            run_jal_lui(Rv32JalLuiOpcode::LUI, 0x1000, 0xfffff),
+            (0x1004, [0x00, 0xf0, 0xff, 0xff])
+        );
+        assert_eq!(run_jal_lui(Rv32JalLuiOpcode::LUI, 0, 0x12345), (4, [0x00, 0x50, 0x34, 0x12]));
+    }
+
+    #[test]
+    pub fn test_jal_lui_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut rng = TestRng::default();
+        let mut pc: u32 = 1 << 20;
+        for i in 0..100 {
+            // Every fifth JAL links to x0, which is not written.
+            let rd = match i % 10 {
+                0 => 0,
+                _ => 1 + rng.gen_below(31) as usize,
+            };
+            let (instruction, expected_pc, expected_rd) = if i % 2 == 0 {
+                let imm = 2 * (rng.gen_below(1 << 12) as i32 - (1 << 11));
+                let link = if rd == 0 { 0 } else { pc + DEFAULT_PC_STEP };
+                (jal(rd, imm), pc.wrapping_add_signed(imm), link)
+            } else {
+                let imm = rng.gen_below(1 << 20);
+                (lui(rd, imm), pc + DEFAULT_PC_STEP, imm << 12)
+            };
+            let to_state = tester.execute(&mut chip, &instruction, pc);
+            assert_eq!(to_state.pc, expected_pc);
+            assert_eq!(compose(&tester.read_register(rd)), expected_rd);
+            pc = to_state.pc;
+        }
+        tester.verify_chip(&mut chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_lui_wrong_rd_data() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &lui(1, 0x12345), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            // Also set the lowest limb of rd, which the immediate does not cover.
+            core_cols::<Rv32CondRdWriteAdapterCols<F>, Rv32JalLuiCoreCols<F>>(trace.row_mut(0))
+                .rd_data[0] = F::ONE;
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_jal_wrong_rd_data() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &jal(1, 16), 0x1000);
+        tester.verify_chip(&mut chip, |trace| {
+            // Link to the jump target instead of the next instruction.
+            core_cols::<Rv32CondRdWriteAdapterCols<F>, Rv32JalLuiCoreCols<F>>(trace.row_mut(0))
+                .rd_data[0] = F::from_canonical_u32(0x10);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 3 is unbalanced")]
+    pub fn test_jal_wrong_imm() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &jal(1, -16), 0x1000);
+        tester.verify_chip(&mut chip, |trace| {
+            // Jump somewhere else than the instruction says.
+            core_cols::<Rv32CondRdWriteAdapterCols<F>, Rv32JalLuiCoreCols<F>>(trace.row_mut(0))
+                .imm = F::from_canonical_u32(16);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "imm 1048576 out of range for LUI")]
+    pub fn test_lui_execute_imm_out_of_range() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.execute(&mut chip, &lui(1, 1 << 20), 0);
+    }
+}
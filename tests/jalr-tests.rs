@@ -0,0 +1,134 @@
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32JalrAdapterChip, Rv32JalrAdapterCols, compose},
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{Instruction, LocalOpcode, Rv32JalrOpcode},
+    integration_api::VmChipWrapper,
+    jalr::{Rv32JalrChip, Rv32JalrCoreChip, Rv32JalrCoreCols, run_jalr},
+    openvm_stark_backend::{
+        field::{F, Field, FieldAlgebra},
+        matrix::Matrix,
+    },
+    testing::{TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32JalrChip {
+    VmChipWrapper::new(
+        Rv32JalrAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32JalrCoreChip::new(tester.bitwise_chip.clone()),
+    )
+}
+
+/// `JALR rd, rs1, imm` where `imm` is a 12-bit immediate, split into its lower 16 bits and its
+/// sign, and `rd` is only written if it is not `x0`.
+fn jalr(rd: usize, rs1: usize, imm: i32) -> Instruction<F> {
+    assert!((-2048..2048).contains(&imm));
+    Instruction::from_usize(
+        Rv32JalrOpcode::JALR.global_opcode(),
+        [
+            rd * RV32_REGISTER_NUM_LIMBS,
+            rs1 * RV32_REGISTER_NUM_LIMBS,
+            (imm as u32 & 0xffff) as usize,
+            RV32_REGISTER_AS as usize,
+            0,
+            usize::from(rd != 0),
+            usize::from(imm < 0),
+        ],
+    )
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_jalr() {
+        assert_eq!(run_jalr(0x100, 0x1001, 0), (0x1000, limbs(0x104)));
+        assert_eq!(run_jalr(0x100, 0x1000, -1i32 as u32), (0xffe, limbs(0x104)));
+        assert_eq!(run_jalr(0x3fff_fff8, 0x20, 0x7ff), (0x81e, limbs(0x3fff_fffc)));
+    }
+
+    #[test]
+    pub fn test_jalr_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut rng = TestRng::default();
+        let mut pc = 0x100;
+        for _ in 0..100 {
+            let [rd, rs1] = [(); 2].map(|_| 1 + rng.gen_below(31) as usize);
+            // Odd targets too, whose least significant bit is cleared.
+            let rs1_value = 2048 + rng.gen_below((1 << 29) - 2048);
+            tester.write_register(rs1, limbs(rs1_value));
+            let rs1_value = compose(&tester.read_register(rs1));
+            let imm = rng.gen_below(4096) as i32 - 2048;
+            let to_state = tester.execute(&mut chip, &jalr(rd, rs1, imm), pc);
+            assert_eq!(to_state.pc, rs1_value.wrapping_add_signed(imm) & !1);
+            assert_eq!(compose(&tester.read_register(rd)), pc + DEFAULT_PC_STEP);
+            pc = to_state.pc;
+        }
+        tester.verify_chip(&mut chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    pub fn test_jalr_clears_lsb() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1000));
+        let cases = [(1, 0x1000), (3, 0x1002), (-1, 0xffe), (-0x800, 0x800)];
+        for (imm, expected_pc) in cases {
+            let to_state = tester.execute(&mut chip, &jalr(2, 1, imm), 0x100);
+            assert_eq!(to_state.pc, expected_pc, "{imm}");
+        }
+        tester.verify_chip(&mut chip, |_| {});
+    }
+
+    #[test]
+    pub fn test_jalr_without_write() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1000));
+        // ret, i.e. jalr x0, 0(x1)
+        let to_state = tester.execute(&mut chip, &jalr(0, 1, 0), 0x100);
+        assert_eq!(to_state.pc, 0x1000);
+        assert_eq!(compose(&tester.read_register(0)), 0);
+        tester.verify_chip(&mut chip, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "bitwise lookup [1006632961, 1, 0, 0] is not in the table")]
+    pub fn test_jalr_keep_lsb() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1000));
+        tester.execute(&mut chip, &jalr(2, 1, 1), 0x100);
+        tester.verify_forged_chip(&mut chip, |trace| {
+            // Jump to the odd 0x1001 by halving the odd limb in the field. Its double 1 is in
+            // range, so only the range check of the halved limb itself rejects it.
+            let cols = core_cols::<Rv32JalrAdapterCols<F>, Rv32JalrCoreCols<F>>(trace.row_mut(0));
+            cols.to_pc_least_sig_bit = F::ZERO;
+            cols.to_pc_limbs[0] = F::from_canonical_u32(2).inverse();
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_jalr_wrong_rd_data() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1000));
+        tester.execute(&mut chip, &jalr(2, 1, 0), 0x100);
+        tester.verify_chip(&mut chip, |trace| {
+            // Link to the current instruction instead of the next one.
+            core_cols::<Rv32JalrAdapterCols<F>, Rv32JalrCoreCols<F>>(trace.row_mut(0)).rd_data[0] =
+                F::from_canonical_u32(0);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "to_pc 0x40000000 out of range for 30 bits")]
+    pub fn test_jalr_execute_to_pc_out_of_range() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x3fff_ffff));
+        tester.execute(&mut chip, &jalr(2, 1, 1), 0x100);
+    }
+}
@@ -0,0 +1,259 @@
+use miri_test::{
+    adapters::{
+        RV32_MEMORY_AS, RV32_REGISTER_AS, Rv32LoadStoreAdapterChip, Rv32LoadStoreAdapterCols,
+        compose,
+    },
+    core::RV32_REGISTER_NUM_LIMBS,
+    instructions::{
+        Instruction, LocalOpcode,
+        Rv32LoadStoreOpcode::{self, *},
+    },
+    integration_api::VmChipWrapper,
+    loadstore::{
+        LOADSTORE_CASES, Rv32LoadStoreChip, Rv32LoadStoreCoreChip, Rv32LoadStoreCoreCols,
+        access_width, is_load_opcode, run_write_data,
+    },
+    openvm_stark_backend::{
+        field::{F, FieldAlgebra, PrimeField32},
+        matrix::Matrix,
+    },
+    sub_air::TraceSubRowGenerator,
+    testing::{TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32LoadStoreChip {
+    VmChipWrapper::new(
+        Rv32LoadStoreAdapterChip::new(
+            tester.memory_bridge(),
+            tester.execution_bridge(),
+            tester.bitwise_chip.clone(),
+            tester.memory.config().pointer_max_bits,
+        ),
+        Rv32LoadStoreCoreChip::new(tester.bitwise_chip.clone()),
+    )
+}
+
+/// `OP rd_rs2, imm(rs1)` where `imm` is a 12-bit immediate, split into its lower 16 bits and its
+/// sign.
+fn loadstore(opcode: Rv32LoadStoreOpcode, rd_rs2: usize, rs1: usize, imm: i32) -> Instruction<F> {
+    assert!((-2048..2048).contains(&imm));
+    Instruction::from_usize(
+        opcode.global_opcode(),
+        [
+            rd_rs2 * RV32_REGISTER_NUM_LIMBS,
+            rs1 * RV32_REGISTER_NUM_LIMBS,
+            (imm as u32 & 0xffff) as usize,
+            RV32_REGISTER_AS as usize,
+            RV32_MEMORY_AS as usize,
+            1,
+            usize::from(imm < 0),
+        ],
+    )
+}
+
+fn read_memory(tester: &VmChipTestBuilder, pointer: u32) -> [u32; RV32_REGISTER_NUM_LIMBS] {
+    tester.memory.unsafe_read(RV32_MEMORY_AS, pointer).map(|x| x.as_canonical_u32())
+}
+
+/// Executes `opcode` on the word `value` stored at `0x1000` with the given shift amount, and
+/// returns the loaded register or the stored memory word.
+fn run_single(opcode: Rv32LoadStoreOpcode, shift: i32, value: u32, rs2: u32) -> u32 {
+    let mut tester = VmChipTestBuilder::new();
+    let mut chip = create_chip(&tester);
+    tester.memory.set_initial(RV32_MEMORY_AS, 0x1000, limbs(value).map(F::from_canonical_u32));
+    tester.write_register(1, limbs(0x1000));
+    tester.write_register(2, limbs(rs2));
+    tester.execute(&mut chip, &loadstore(opcode, 2, 1, shift), 0x100);
+    let result = match is_load_opcode(opcode) {
+        true => compose(&tester.read_register(2)),
+        false => u32::from_le_bytes(read_memory(&tester, 0x1000).map(|x| x as u8)),
+    };
+    tester.verify_chip(&mut chip, |_| {});
+    result
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_write_data() {
+        let read = [0x12, 0x84, 0x56, 0xf8];
+        let prev = [1, 2, 3, 4];
+        assert_eq!(run_write_data(LOADW, 0, &read, &prev), (read, 0));
+        assert_eq!(run_write_data(LOADBU, 1, &read, &prev), ([0x84, 0, 0, 0], 0));
+        assert_eq!(run_write_data(LOADB, 1, &read, &prev), ([0x84, 0xff, 0xff, 0xff], 1));
+        assert_eq!(run_write_data(LOADB, 2, &read, &prev), ([0x56, 0, 0, 0], 0));
+        assert_eq!(run_write_data(LOADHU, 2, &read, &prev), ([0x56, 0xf8, 0, 0], 0));
+        assert_eq!(run_write_data(LOADH, 2, &read, &prev), ([0x56, 0xf8, 0xff, 0xff], 1));
+        assert_eq!(run_write_data(STOREW, 0, &read, &prev), (read, 0));
+        assert_eq!(run_write_data(STOREH, 2, &read, &prev), ([1, 2, 0x12, 0x84], 0));
+        assert_eq!(run_write_data(STOREB, 3, &read, &prev), ([1, 2, 3, 0x12], 0));
+    }
+
+    #[test]
+    pub fn test_loadstore_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut rng = TestRng::default();
+        for block in 0..64 {
+            let data =
+                [(); RV32_REGISTER_NUM_LIMBS].map(|_| F::from_canonical_u32(rng.gen_below(256)));
+            tester.memory.set_initial(RV32_MEMORY_AS, 0x1000 + 4 * block, data);
+        }
+        let opcodes = [LOADW, LOADBU, LOADHU, STOREW, STOREH, STOREB, LOADB, LOADH];
+        for _ in 0..200 {
+            let opcode = opcodes[rng.gen_below(opcodes.len() as u32) as usize];
+            let [rd_rs2, rs1] = [(); 2].map(|_| 1 + rng.gen_below(31) as usize);
+            let width = access_width(opcode) as u32;
+            let ptr = 0x1000 + width * rng.gen_below(256 / width);
+            let imm = rng.gen_below(4096) as i32 - 2048;
+            tester.write_register(rs1, limbs(ptr.wrapping_add_signed(-imm)));
+
+            let block_ptr = ptr & !3;
+            let shift = ptr % 4;
+            let block = read_memory(&tester, block_ptr);
+            let rd_rs2_data = tester.read_register(rd_rs2).map(|x| x.as_canonical_u32());
+            tester.execute(&mut chip, &loadstore(opcode, rd_rs2, rs1, imm), 0x100);
+            if is_load_opcode(opcode) {
+                let (expected, _) = run_write_data(opcode, shift, &block, &rd_rs2_data);
+                let rd = tester.read_register(rd_rs2).map(|x| x.as_canonical_u32());
+                assert_eq!(rd, expected, "{opcode:?} at {ptr:#x}");
+            } else {
+                let (expected, _) = run_write_data(opcode, shift, &rd_rs2_data, &block);
+                assert_eq!(read_memory(&tester, block_ptr), expected, "{opcode:?} at {ptr:#x}");
+            }
+        }
+        tester.verify_chip(&mut chip, |trace| assert_eq!(trace.height(), 256));
+    }
+
+    #[test]
+    pub fn test_loadstore_extension() {
+        let value = 0x80ff_7f01;
+        let cases = [
+            (LOADW, 0, value),
+            (LOADB, 0, 0x01),
+            (LOADB, 1, 0x7f),
+            (LOADB, 2, 0xffff_ffff),
+            (LOADB, 3, 0xffff_ff80),
+            (LOADBU, 3, 0x80),
+            (LOADH, 0, 0x7f01),
+            (LOADH, 2, 0xffff_80ff),
+            (LOADHU, 2, 0x80ff),
+        ];
+        for (opcode, shift, expected) in cases {
+            assert_eq!(run_single(opcode, shift, value, 0), expected, "{opcode:?} {shift}");
+        }
+    }
+
+    #[test]
+    pub fn test_loadstore_store_shifts() {
+        let value = 0x1122_3344;
+        let rs2 = 0xaabb_ccdd;
+        let cases = [
+            (STOREW, 0, rs2),
+            (STOREH, 0, 0x1122_ccdd),
+            (STOREH, 2, 0xccdd_3344),
+            (STOREB, 1, 0x1122_dd44),
+            (STOREB, 3, 0xdd22_3344),
+        ];
+        for (opcode, shift, expected) in cases {
+            assert_eq!(run_single(opcode, shift, value, rs2), expected, "{opcode:?} {shift}");
+        }
+    }
+
+    #[test]
+    pub fn test_loadstore_negative_imm() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.memory.set_initial(
+            RV32_MEMORY_AS,
+            0x800,
+            limbs(0xdead_beef).map(F::from_canonical_u32),
+        );
+        tester.write_register(1, limbs(0x1000));
+        tester.execute(&mut chip, &loadstore(LOADW, 2, 1, -0x800), 0x100);
+        assert_eq!(compose(&tester.read_register(2)), 0xdead_beef);
+        tester.verify_chip(&mut chip, |_| {});
+    }
+
+    #[test]
+    pub fn test_loadstore_load_without_write() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.memory.set_initial(
+            RV32_MEMORY_AS,
+            0x1000,
+            limbs(0xdead_beef).map(F::from_canonical_u32),
+        );
+        tester.write_register(1, limbs(0x1000));
+        let mut instruction = loadstore(LOADW, 0, 1, 0);
+        instruction.f = F::ZERO;
+        tester.execute(&mut chip, &instruction, 0x100);
+        assert_eq!(compose(&tester.read_register(0)), 0);
+        tester.verify_chip(&mut chip, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "unaligned LOADW at address 0x1002")]
+    pub fn test_loadstore_execute_unaligned_word() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1000));
+        tester.execute(&mut chip, &loadstore(LOADW, 2, 1, 2), 0x100);
+    }
+
+    #[test]
+    #[should_panic(expected = "unaligned STOREH at address 0x1003")]
+    pub fn test_loadstore_execute_unaligned_halfword() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1000));
+        tester.execute(&mut chip, &loadstore(STOREH, 2, 1, 3), 0x100);
+    }
+
+    #[test]
+    #[should_panic(expected = "address 0x10000 out of range for 16 bits")]
+    pub fn test_loadstore_execute_address_out_of_range() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0xfffc));
+        tester.execute(&mut chip, &loadstore(LOADW, 2, 1, 4), 0x100);
+    }
+
+    #[test]
+    #[should_panic(expected = "bitwise lookup [1509949441, 1, 0, 0] is not in the table")]
+    pub fn test_loadstore_unaligned_word() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1000));
+        tester.execute(&mut chip, &loadstore(LOADBU, 2, 1, 1), 0x100);
+        let encoder = chip.core.air.encoder.clone();
+        tester.verify_forged_chip(&mut chip, |trace| {
+            // Claim a LOADW at 0x1001, which writes the same zeros. The adapter cannot divide
+            // the unshifted address by four within a limb.
+            let loadw = LOADSTORE_CASES.iter().position(|&case| case == (LOADW, 0)).unwrap();
+            let cols = core_cols::<Rv32LoadStoreAdapterCols<F>, Rv32LoadStoreCoreCols<F>>(
+                trace.row_mut(0),
+            );
+            encoder.generate_subrow(Some(loadw), &mut cols.flags);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bitwise lookup [2013265919, 0, 0, 0] is not in the table")]
+    pub fn test_loadstore_wrong_sign_extension() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.memory.set_initial(RV32_MEMORY_AS, 0x1000, limbs(0x7f).map(F::from_canonical_u32));
+        tester.write_register(1, limbs(0x1000));
+        tester.execute(&mut chip, &loadstore(LOADB, 2, 1, 0), 0x100);
+        tester.verify_forged_chip(&mut chip, |trace| {
+            // Sign extend the positive byte 0x7f, which fails its seven bit range check.
+            let cols = core_cols::<Rv32LoadStoreAdapterCols<F>, Rv32LoadStoreCoreCols<F>>(
+                trace.row_mut(0),
+            );
+            cols.data_most_sig_bit = F::ONE;
+            cols.write_data[1..].fill(F::from_canonical_u32(0xff));
+        });
+    }
+}
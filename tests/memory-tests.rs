@@ -0,0 +1,191 @@
+use core::borrow::{Borrow, BorrowMut};
+use std::{
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::Arc,
+};
+
+use miri_test::{
+    aligned_borrow,
+    bus::{MemoryBus, VariableRangeCheckerBus},
+    memory::{
+        MEMORY_BLOCK_SIZE, MemoryAddress, MemoryBridge, MemoryConfig, MemoryController,
+        MemoryWriteAuxCols, MemoryWriteRecord,
+    },
+    openvm_stark_backend::{
+        air::{Air, BaseAir},
+        debug::{check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    var_range::VariableRangeCheckerChip,
+    volatile::VolatileBoundaryCols,
+};
+
+const RANGE_BUS: VariableRangeCheckerBus = VariableRangeCheckerBus::new(0, 8);
+const MEMORY_BUS: MemoryBus = MemoryBus::new(1);
+const CONFIG: MemoryConfig =
+    MemoryConfig { address_space_max_bits: 2, pointer_max_bits: 16, timestamp_max_bits: 16 };
+
+aligned_borrow! {
+    pub struct WriteCols<T> {
+        pub address_space: T,
+        pub pointer: T,
+        pub data: [T; MEMORY_BLOCK_SIZE],
+        pub timestamp: T,
+        pub is_valid: T,
+        pub aux: MemoryWriteAuxCols<T, MEMORY_BLOCK_SIZE>,
+    }
+}
+
+/// Performs one memory write per valid row.
+struct WriteAir(MemoryBridge);
+
+impl BaseAir<F> for WriteAir {
+    fn width(&self) -> usize {
+        WriteCols::<F>::width()
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for WriteAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local: &WriteCols<AB::Var> = main.row_slice(0).borrow();
+        self.0
+            .write(
+                MemoryAddress::new(local.address_space, local.pointer),
+                local.data,
+                local.timestamp,
+                &local.aux,
+            )
+            .eval(builder, local.is_valid);
+    }
+}
+
+fn new_memory() -> MemoryController {
+    MemoryController::new(MEMORY_BUS, CONFIG, Arc::new(VariableRangeCheckerChip::new(RANGE_BUS)))
+}
+
+fn write_trace(memory: &MemoryController, records: &[MemoryWriteRecord]) -> RowMajorMatrix<F> {
+    let width = WriteCols::<F>::width();
+    let mut trace = RowMajorMatrix::new(vec![F::ZERO; records.len() * width], width);
+    for (row, record) in trace.rows_mut().zip(records) {
+        let cols: &mut WriteCols<F> = row.borrow_mut();
+        cols.address_space = F::from_canonical_u32(record.address.address_space);
+        cols.pointer = F::from_canonical_u32(record.address.pointer);
+        cols.data = record.data;
+        cols.timestamp = F::from_canonical_u32(record.timestamp);
+        cols.is_valid = F::ONE;
+        memory.fill_write_aux(record, &mut cols.aux);
+    }
+    trace
+}
+
+/// Checks the write trace, the memory boundary and the range checker together.
+fn verify(memory: &MemoryController, write_trace: &RowMajorMatrix<F>, boundary: RowMajorMatrix<F>) {
+    let mut interactions = check_constraints(&WriteAir(memory.bridge), write_trace);
+    interactions.extend(check_constraints(&memory.boundary_air, &boundary));
+    let range_checker = &memory.range_checker;
+    interactions.extend(check_constraints(&range_checker.air, &range_checker.generate_trace()));
+    verify_interactions(interactions);
+}
+
+fn data(values: [u32; MEMORY_BLOCK_SIZE]) -> [F; MEMORY_BLOCK_SIZE] {
+    values.map(F::from_canonical_u32)
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_memory_writes() {
+        let mut memory = new_memory();
+        memory.set_initial(2, 0x100, data([1, 2, 3, 4]));
+        let records = [
+            memory.write(1, 4, data([5, 6, 7, 8])),
+            memory.write(2, 0x100, data([9, 10, 11, 12])),
+            memory.write(1, 4, data([13, 14, 15, 16])),
+            memory.write(1, 0, data([17, 18, 19, 20])),
+        ];
+        assert_eq!(records[1].prev_data, data([1, 2, 3, 4]));
+        assert_eq!((records[1].prev_timestamp, records[1].timestamp), (0, 2));
+        assert_eq!(records[2].prev_data, data([5, 6, 7, 8]));
+        assert_eq!((records[2].prev_timestamp, records[2].timestamp), (1, 3));
+        assert_eq!(memory.timestamp(), 5);
+        assert_eq!(memory.unsafe_read(1, 4), data([13, 14, 15, 16]));
+        // The initial data of untouched blocks is zero.
+        assert_eq!(memory.unsafe_read(1, 8), data([0; MEMORY_BLOCK_SIZE]));
+
+        let read = memory.read(1, 0);
+        assert_eq!(read.data, data([17, 18, 19, 20]));
+        assert_eq!((read.prev_timestamp, read.timestamp), (4, 5));
+
+        // A read is a write that leaves the data unchanged.
+        let read_as_write = MemoryWriteRecord {
+            address: read.address,
+            data: read.data,
+            prev_data: read.data,
+            prev_timestamp: read.prev_timestamp,
+            timestamp: read.timestamp,
+        };
+        let trace = write_trace(&memory, &[records.as_slice(), &[read_as_write]].concat());
+        let boundary = memory.generate_boundary_trace();
+        assert_eq!(boundary.height(), 4);
+        verify(&memory, &trace, boundary);
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 1")]
+    pub fn test_memory_write_stale_timestamp() {
+        let mut memory = new_memory();
+        let records = [memory.write(1, 4, data([1; 4])), memory.write(1, 4, data([2; 4]))];
+        let mut trace = write_trace(&memory, &records);
+        // The second write claims that the block was last accessed at its own timestamp.
+        let cols: &mut WriteCols<F> = trace.row_mut(1).borrow_mut();
+        cols.aux.base.prev_timestamp = cols.timestamp;
+        let boundary = memory.generate_boundary_trace();
+        verify(&memory, &trace, boundary);
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_memory_boundary_duplicate_address() {
+        let mut memory = new_memory();
+        let records = [memory.write(1, 4, data([1; 4])), memory.write(1, 8, data([2; 4]))];
+        let trace = write_trace(&memory, &records);
+        let mut boundary = memory.generate_boundary_trace();
+        // Both rows of the boundary claim the block at 1:4.
+        let cols: &mut VolatileBoundaryCols<F> = boundary.row_mut(1).borrow_mut();
+        cols.pointer_limbs[0] = F::from_canonical_u32(4);
+        verify(&memory, &trace, boundary);
+    }
+
+    #[test]
+    #[should_panic(expected = "unaligned memory access at 1:2")]
+    pub fn test_memory_unaligned_access() {
+        new_memory().read(1, 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "block at 1:4 is already accessed")]
+    pub fn test_memory_set_initial_after_access() {
+        let mut memory = new_memory();
+        memory.read(1, 4);
+        memory.set_initial(1, 4, data([1; 4]));
+    }
+
+    #[test]
+    pub fn test_memory_set_initial_after_access_keeps_block() {
+        let mut memory = new_memory();
+        let records = [memory.write(1, 4, data([1; 4]))];
+        let set_initial = catch_unwind(AssertUnwindSafe(|| {
+            memory.set_initial(1, 4, data([2; 4]));
+        }));
+        assert!(set_initial.is_err());
+        // The rejected call leaves the accessed block as it was.
+        assert_eq!(memory.unsafe_read(1, 4), data([1; 4]));
+        let trace = write_trace(&memory, &records);
+        let boundary = memory.generate_boundary_trace();
+        verify(&memory, &trace, boundary);
+    }
+}
@@ -0,0 +1,107 @@
+use std::sync::Arc;
+
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32MultAdapterChip, Rv32MultAdapterCols, compose},
+    core::RV32_REGISTER_NUM_LIMBS,
+    instructions::{Instruction, LocalOpcode, MulOpcode},
+    integration_api::VmChipWrapper,
+    mul::{
+        Rv32MultiplicationChip, Rv32MultiplicationCoreChip, Rv32MultiplicationCoreCols, run_mul,
+    },
+    openvm_stark_backend::{
+        debug::check_constraints,
+        field::{F, FieldAlgebra},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    range_tuple::{RangeTupleCheckerChip, SharedRangeTupleCheckerChip},
+    testing::{RANGE_TUPLE_CHECKER_BUS, TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(
+    tester: &VmChipTestBuilder,
+) -> (Rv32MultiplicationChip, SharedRangeTupleCheckerChip<2>) {
+    let range_tuple_chip = Arc::new(RangeTupleCheckerChip::new(RANGE_TUPLE_CHECKER_BUS));
+    let chip = VmChipWrapper::new(
+        Rv32MultAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32MultiplicationCoreChip::new(range_tuple_chip.clone()),
+    );
+    (chip, range_tuple_chip)
+}
+
+/// `MUL rd, rs1, rs2`.
+fn mul(rd: usize, rs1: usize, rs2: usize) -> Instruction<F> {
+    let [rd, rs1, rs2] = [rd, rs1, rs2].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    Instruction::from_usize(
+        MulOpcode::MUL.global_opcode(),
+        [rd, rs1, rs2, RV32_REGISTER_AS as usize, 0],
+    )
+}
+
+/// Like [VmChipTestBuilder::verify_chip], with the range tuple checker of the chip.
+fn verify(
+    tester: VmChipTestBuilder,
+    mut chip: Rv32MultiplicationChip,
+    range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    modify_trace: impl FnOnce(&mut RowMajorMatrix<F>),
+) {
+    let mut trace = chip.generate_trace(&tester.memory);
+    modify_trace(&mut trace);
+    let mut interactions = check_constraints(&chip.air(), &trace);
+    interactions
+        .extend(check_constraints(&range_tuple_chip.air, &range_tuple_chip.generate_trace()));
+    tester.verify(interactions);
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_mul() {
+        let (result, carry) = run_mul(&limbs(0x0102_0304), &limbs(0x0506_0708));
+        assert_eq!(result, limbs(0x0102_0304u32.wrapping_mul(0x0506_0708)));
+        assert_eq!(carry, [0, 0, 0, 0]);
+        let (result, carry) = run_mul(&limbs(u32::MAX), &limbs(u32::MAX));
+        assert_eq!(result, limbs(1));
+        assert_eq!(carry, [254, 509, 764, 1019]);
+    }
+
+    #[test]
+    pub fn test_mul_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        let mut rng = TestRng::default();
+        for _ in 0..100 {
+            let [rd, rs1, rs2] = [(); 3].map(|_| 1 + rng.gen_below(31) as usize);
+            let [x, y] = [(); 2].map(|_| rng.next_u32());
+            tester.write_register(rs1, limbs(x));
+            tester.write_register(rs2, limbs(y));
+            let [x, y] = [rs1, rs2].map(|reg| compose(&tester.read_register(reg)));
+            tester.execute(&mut chip, &mul(rd, rs1, rs2), 0x100);
+            assert_eq!(compose(&tester.read_register(rd)), x.wrapping_mul(y));
+        }
+        verify(tester, chip, range_tuple_chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    pub fn test_mul_max_carries() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(u32::MAX));
+        tester.execute(&mut chip, &mul(2, 1, 1), 0x100);
+        assert_eq!(compose(&tester.read_register(2)), 1);
+        verify(tester, chip, range_tuple_chip, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_mul_invalid_row() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(3));
+        tester.execute(&mut chip, &mul(2, 1, 1), 0x100);
+        verify(tester, chip, range_tuple_chip, |trace| {
+            core_cols::<Rv32MultAdapterCols<F>, Rv32MultiplicationCoreCols<F>>(trace.row_mut(0))
+                .is_valid = F::from_canonical_u32(2);
+        });
+    }
+}
@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32MultAdapterChip, Rv32MultAdapterCols, compose},
+    core::RV32_REGISTER_NUM_LIMBS,
+    instructions::{Instruction, LocalOpcode, MulHOpcode},
+    integration_api::VmChipWrapper,
+    mulh::{Rv32MulHChip, Rv32MulHCoreChip, Rv32MulHCoreCols, run_mulh},
+    openvm_stark_backend::{
+        debug::check_constraints,
+        field::{F, FieldAlgebra},
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    range_tuple::{RangeTupleCheckerChip, SharedRangeTupleCheckerChip},
+    testing::{RANGE_TUPLE_CHECKER_BUS, TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> (Rv32MulHChip, SharedRangeTupleCheckerChip<2>) {
+    let range_tuple_chip = Arc::new(RangeTupleCheckerChip::new(RANGE_TUPLE_CHECKER_BUS));
+    let chip = VmChipWrapper::new(
+        Rv32MultAdapterChip::new(tester.memory_bridge(), tester.execution_bridge()),
+        Rv32MulHCoreChip::new(tester.bitwise_chip.clone(), range_tuple_chip.clone()),
+    );
+    (chip, range_tuple_chip)
+}
+
+/// `MULH`, `MULHSU` or `MULHU rd, rs1, rs2`, the upper word of the 64-bit product.
+fn mulh(opcode: MulHOpcode, rd: usize, rs1: usize, rs2: usize) -> Instruction<F> {
+    let [rd, rs1, rs2] = [rd, rs1, rs2].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    Instruction::from_usize(opcode.global_opcode(), [rd, rs1, rs2, RV32_REGISTER_AS as usize, 0])
+}
+
+/// The upper 32 bits of `x * y`, with `x` signed except for `MULHU` and `y` signed for `MULH`.
+fn reference(opcode: MulHOpcode, x: u32, y: u32) -> u32 {
+    let product = match opcode {
+        MulHOpcode::MULH => i64::from(x as i32) * i64::from(y as i32),
+        MulHOpcode::MULHSU => i64::from(x as i32) * i64::from(y),
+        MulHOpcode::MULHU => (u64::from(x) * u64::from(y)) as i64,
+    };
+    (product >> 32) as u32
+}
+
+/// Like [VmChipTestBuilder::verify_chip], with the range tuple checker of the chip.
+fn verify(
+    tester: VmChipTestBuilder,
+    mut chip: Rv32MulHChip,
+    range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    modify_trace: impl FnOnce(&mut RowMajorMatrix<F>),
+) {
+    let mut trace = chip.generate_trace(&tester.memory);
+    modify_trace(&mut trace);
+    let mut interactions = check_constraints(&chip.air(), &trace);
+    interactions
+        .extend(check_constraints(&range_tuple_chip.air, &range_tuple_chip.generate_trace()));
+    tester.verify(interactions);
+}
+
+/// Like [verify], with the bitwise lookups rebuilt from the forged trace as in
+/// [VmChipTestBuilder::verify_forged_chip].
+fn verify_forged(
+    tester: VmChipTestBuilder,
+    mut chip: Rv32MulHChip,
+    range_tuple_chip: SharedRangeTupleCheckerChip<2>,
+    modify_trace: impl FnOnce(&mut RowMajorMatrix<F>),
+) {
+    let mut trace = chip.generate_trace(&tester.memory);
+    modify_trace(&mut trace);
+    let mut interactions = check_constraints(&chip.air(), &trace);
+    tester.rebuild_bitwise_lookups(&interactions);
+    interactions
+        .extend(check_constraints(&range_tuple_chip.air, &range_tuple_chip.generate_trace()));
+    tester.verify(interactions);
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_mulh() {
+        let cases = [
+            (0, 0),
+            (1, u32::MAX),
+            (u32::MAX, u32::MAX),
+            (0x8000_0000, 0x8000_0000),
+            (0x8000_0000, 0x7fff_ffff),
+            (0x1234_5678, 0xfedc_ba98),
+        ];
+        for opcode in MulHOpcode::ALL {
+            for (x, y) in cases {
+                let (mulh, mul, _, x_ext, y_ext) = run_mulh(opcode, &limbs(x), &limbs(y));
+                assert_eq!(mulh, limbs(reference(opcode, x, y)), "{opcode:?} {x:#x} {y:#x}");
+                assert_eq!(mul, limbs(x.wrapping_mul(y)));
+                let signed = [opcode != MulHOpcode::MULHU, opcode == MulHOpcode::MULH];
+                assert_eq!(x_ext, u32::from(signed[0] && (x as i32) < 0) * 255);
+                assert_eq!(y_ext, u32::from(signed[1] && (y as i32) < 0) * 255);
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_mulh_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        let mut rng = TestRng::default();
+        for i in 0..99 {
+            let opcode = MulHOpcode::ALL[i % 3];
+            let [rd, rs1, rs2] = [(); 3].map(|_| 1 + rng.gen_below(31) as usize);
+            let [x, y] = [(); 2].map(|_| rng.next_u32());
+            tester.write_register(rs1, limbs(x));
+            tester.write_register(rs2, limbs(y));
+            let [x, y] = [rs1, rs2].map(|reg| compose(&tester.read_register(reg)));
+            tester.execute(&mut chip, &mulh(opcode, rd, rs1, rs2), 0x100);
+            assert_eq!(compose(&tester.read_register(rd)), reference(opcode, x, y), "{opcode:?}");
+        }
+        verify(tester, chip, range_tuple_chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    pub fn test_mulh_extremes() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        for (x, y) in [(u32::MAX, u32::MAX), (0x8000_0000, 0x8000_0000), (0x8000_0000, 1)] {
+            tester.write_register(1, limbs(x));
+            tester.write_register(2, limbs(y));
+            for opcode in MulHOpcode::ALL {
+                tester.execute(&mut chip, &mulh(opcode, 3, 1, 2), 0x100);
+                assert_eq!(compose(&tester.read_register(3)), reference(opcode, x, y));
+            }
+        }
+        verify(tester, chip, range_tuple_chip, |_| {});
+    }
+
+    #[test]
+    #[should_panic(expected = "bitwise lookup [2013265665, 0, 0, 0] is not in the table")]
+    pub fn test_mulh_wrong_sign() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(2));
+        tester.write_register(2, limbs(3));
+        tester.execute(&mut chip, &mulh(MulHOpcode::MULH, 3, 1, 2), 0x100);
+        verify_forged(tester, chip, range_tuple_chip, |trace| {
+            // Sign extend the positive rs1 as if it were negative.
+            core_cols::<Rv32MultAdapterCols<F>, Rv32MulHCoreCols<F>>(trace.row_mut(0)).b_ext =
+                F::from_canonical_u32(255);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_mulhu_signed() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(u32::MAX));
+        tester.execute(&mut chip, &mulh(MulHOpcode::MULHU, 2, 1, 1), 0x100);
+        verify(tester, chip, range_tuple_chip, |trace| {
+            // Sign extend the operands of MULHU as MULH does.
+            let cols = core_cols::<Rv32MultAdapterCols<F>, Rv32MulHCoreCols<F>>(trace.row_mut(0));
+            cols.b_ext = F::from_canonical_u32(255);
+            cols.c_ext = F::from_canonical_u32(255);
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 5 is unbalanced")]
+    pub fn test_mulh_wrong_lower_half() {
+        let mut tester = VmChipTestBuilder::new();
+        let (mut chip, range_tuple_chip) = create_chip(&tester);
+        tester.write_register(1, limbs(0x1_0000));
+        tester.execute(&mut chip, &mulh(MulHOpcode::MULHU, 2, 1, 1), 0x100);
+        verify(tester, chip, range_tuple_chip, |trace| {
+            // The lower half is only a witness, constrained by its range checks: 0x1_0000^2 has
+            // a zero lower half, and a nonzero limb makes a carry out of range.
+            core_cols::<Rv32MultAdapterCols<F>, Rv32MulHCoreCols<F>>(trace.row_mut(0)).a_mul[0] =
+                F::from_canonical_u32(1);
+        });
+    }
+}
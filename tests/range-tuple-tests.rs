@@ -0,0 +1,69 @@
+use miri_test::{
+    bus::RangeTupleCheckerBus,
+    openvm_stark_backend::{
+        air::{Air, BaseAir},
+        debug::{check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    range_tuple::RangeTupleCheckerChip,
+};
+
+const SIZES: [u32; 2] = [16, 6];
+const BUS: RangeTupleCheckerBus<2> = RangeTupleCheckerBus::new(0, SIZES);
+
+/// Range checks the `(limb, carry)` pair in its two columns on every row.
+struct DummyAir;
+
+impl BaseAir<F> for DummyAir {
+    fn width(&self) -> usize {
+        2
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for DummyAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        BUS.send([local[0], local[1]]).eval(builder, AB::Expr::ONE);
+    }
+}
+
+fn dummy_trace(tuples: &[[u32; 2]]) -> RowMajorMatrix<F> {
+    RowMajorMatrix::new(tuples.iter().flatten().copied().map(F::from_canonical_u32).collect(), 2)
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_range_tuple_checker() {
+        let chip = RangeTupleCheckerChip::new(BUS);
+        let tuples = [[0, 0], [15, 0], [0, 5], [15, 5], [7, 3], [7, 3]];
+        tuples.iter().for_each(|tuple| chip.add_count(tuple));
+
+        let mut interactions = check_constraints(&DummyAir, &dummy_trace(&tuples));
+        let trace = chip.generate_trace();
+        assert_eq!(trace.height(), 16 * 6);
+        interactions.extend(check_constraints(&chip.air, &trace));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 0 is unbalanced")]
+    pub fn test_range_tuple_checker_out_of_range() {
+        let chip = RangeTupleCheckerChip::new(BUS);
+        chip.add_count(&[0, 1]);
+        // `16` is in range for the second element but not for the first.
+        let mut interactions = check_constraints(&DummyAir, &dummy_trace(&[[16, 0]]));
+        interactions.extend(check_constraints(&chip.air, &chip.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "element 1 of tuple [0, 6] out of range for size 6")]
+    pub fn test_range_tuple_checker_add_count_out_of_range() {
+        RangeTupleCheckerChip::new(BUS).add_count(&[0, 6]);
+    }
+}
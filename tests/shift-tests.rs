@@ -0,0 +1,143 @@
+use miri_test::{
+    adapters::{RV32_REGISTER_AS, Rv32BaseAluAdapterChip, Rv32BaseAluAdapterCols, compose},
+    core::RV32_REGISTER_NUM_LIMBS,
+    execution::DEFAULT_PC_STEP,
+    instructions::{Instruction, LocalOpcode, ShiftOpcode},
+    integration_api::VmChipWrapper,
+    openvm_stark_backend::{
+        field::{F, FieldAlgebra},
+        matrix::Matrix,
+    },
+    shift::{Rv32ShiftChip, Rv32ShiftCoreChip, Rv32ShiftCoreCols, run_shift},
+    testing::{TestRng, VmChipTestBuilder, core_cols, limbs},
+};
+
+fn create_chip(tester: &VmChipTestBuilder) -> Rv32ShiftChip {
+    VmChipWrapper::new(
+        Rv32BaseAluAdapterChip::new(
+            tester.memory_bridge(),
+            tester.execution_bridge(),
+            tester.bitwise_chip.clone(),
+        ),
+        Rv32ShiftCoreChip::new(tester.bitwise_chip.clone(), tester.range_checker.clone()),
+    )
+}
+
+/// `SLL`, `SRL` or `SRA rd, rs1, rs2`, shifting by the lowest five bits of `rs2`.
+fn shift(opcode: ShiftOpcode, rd: usize, rs1: usize, rs2: usize) -> Instruction<F> {
+    let [rd, rs1, rs2] = [rd, rs1, rs2].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    let register_as = RV32_REGISTER_AS as usize;
+    Instruction::from_usize(opcode.global_opcode(), [rd, rs1, rs2, register_as, register_as])
+}
+
+/// `OPI rd, rs1, shamt`.
+fn shift_imm(opcode: ShiftOpcode, rd: usize, rs1: usize, shamt: usize) -> Instruction<F> {
+    let [rd, rs1] = [rd, rs1].map(|reg| reg * RV32_REGISTER_NUM_LIMBS);
+    Instruction::from_usize(opcode.global_opcode(), [rd, rs1, shamt, RV32_REGISTER_AS as usize, 0])
+}
+
+/// `x` shifted by the lowest five bits of `y`, with `SRA` filling in the sign bit.
+fn reference(opcode: ShiftOpcode, x: u32, y: u32) -> u32 {
+    let shamt = y & 31;
+    match opcode {
+        ShiftOpcode::SLL => x << shamt,
+        ShiftOpcode::SRL => x >> shamt,
+        ShiftOpcode::SRA => ((x as i32) >> shamt) as u32,
+    }
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_run_shift() {
+        let mut rng = TestRng::default();
+        let edge_cases = [0, 1, 7, 8, 9, 31, 32, 0x7fff_ffff, 0x8000_0000, u32::MAX];
+        for opcode in ShiftOpcode::ALL {
+            for _ in 0..1000 {
+                let (x, y) = (rng.next_u32(), rng.next_u32());
+                let (result, limb_shift, bit_shift) = run_shift(opcode, &limbs(x), &limbs(y));
+                assert_eq!(result, limbs(reference(opcode, x, y)), "{opcode:?} {x:#x} {y:#x}");
+                assert_eq!(limb_shift * 8 + bit_shift, (y & 31) as usize);
+            }
+            for x in edge_cases {
+                for y in edge_cases {
+                    let (result, ..) = run_shift(opcode, &limbs(x), &limbs(y));
+                    assert_eq!(result, limbs(reference(opcode, x, y)), "{opcode:?} {x:#x} {y:#x}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_shift_rand() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        let mut rng = TestRng::default();
+        let mut pc = 0;
+        for i in 0..100 {
+            let opcode = ShiftOpcode::ALL[i % ShiftOpcode::COUNT];
+            let [rd, rs1, rs2] = [(); 3].map(|_| 1 + rng.gen_below(31) as usize);
+            tester.write_register(rs1, limbs(rng.next_u32()));
+            let (instruction, y) = if i % 4 == 0 {
+                let shamt = rng.gen_below(32);
+                (shift_imm(opcode, rd, rs1, shamt as usize), shamt)
+            } else {
+                // The shift amount only depends on the low 5 bits of rs2.
+                tester.write_register(rs2, limbs(rng.next_u32()));
+                (shift(opcode, rd, rs1, rs2), compose(&tester.read_register(rs2)))
+            };
+            let x = compose(&tester.read_register(rs1));
+            let to_state = tester.execute(&mut chip, &instruction, pc);
+            assert_eq!(to_state.pc, pc + DEFAULT_PC_STEP);
+            assert_eq!(compose(&tester.read_register(rd)), reference(opcode, x, y), "{opcode:?}");
+            pc = to_state.pc;
+        }
+        tester.verify_chip(&mut chip, |trace| assert_eq!(trace.height(), 128));
+    }
+
+    #[test]
+    #[should_panic(expected = "constraints had nonzero value on row 0")]
+    pub fn test_shift_wrong_limb_shift() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x1234_5678));
+        tester.execute(&mut chip, &shift_imm(ShiftOpcode::SLL, 2, 1, 12), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            // Claim a shift by 4 instead of 12 without changing the result.
+            core_cols::<Rv32BaseAluAdapterCols<F>, Rv32ShiftCoreCols<F>>(trace.row_mut(0))
+                .limb_shift_marker = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 1 is unbalanced")]
+    pub fn test_shift_wrong_shift_amount() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        // Every shift of zero is zero, so only the decomposition of c can be wrong.
+        tester.write_register(1, limbs(0));
+        tester.execute(&mut chip, &shift_imm(ShiftOpcode::SRL, 2, 1, 12), 0);
+        tester.verify_chip(&mut chip, |trace| {
+            // Claim a shift by 4, which does not match the shift amount 12 of c.
+            core_cols::<Rv32BaseAluAdapterCols<F>, Rv32ShiftCoreCols<F>>(trace.row_mut(0))
+                .limb_shift_marker = [F::ONE, F::ZERO, F::ZERO, F::ZERO];
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "bitwise lookup [128, 128, 256, 1] is not in the table")]
+    pub fn test_shift_wrong_sra_sign() {
+        let mut tester = VmChipTestBuilder::new();
+        let mut chip = create_chip(&tester);
+        tester.write_register(1, limbs(0x8000_0000));
+        tester.execute(&mut chip, &shift_imm(ShiftOpcode::SRA, 2, 1, 8), 0);
+        tester.verify_forged_chip(&mut chip, |trace| {
+            let cols =
+                core_cols::<Rv32BaseAluAdapterCols<F>, Rv32ShiftCoreCols<F>>(trace.row_mut(0));
+            // Fill with zeros as if b were non-negative, i.e. a logical shift.
+            cols.b_sign = F::ZERO;
+            cols.a = limbs(0x0080_0000).map(F::from_canonical_u32);
+        });
+    }
+}
@@ -0,0 +1,108 @@
+use miri_test::{
+    bus::VariableRangeCheckerBus,
+    openvm_stark_backend::{
+        air::{Air, BaseAir},
+        debug::{check_constraints, verify_interactions},
+        field::{F, FieldAlgebra},
+        interaction::InteractionBuilder,
+        matrix::{Matrix, RowMajorMatrix},
+    },
+    var_range::{DecomposeError, VariableRangeCheckerChip},
+};
+
+const RANGE_MAX_BITS: usize = 5;
+const BUS: VariableRangeCheckerBus = VariableRangeCheckerBus::new(0, RANGE_MAX_BITS);
+
+/// Range checks its single column against `2^max_bits` on every row.
+struct DummyAir {
+    max_bits: usize,
+}
+
+impl BaseAir<F> for DummyAir {
+    fn width(&self) -> usize {
+        1
+    }
+}
+
+impl<AB: InteractionBuilder<F = F>> Air<AB> for DummyAir {
+    fn eval(&self, builder: &mut AB) {
+        let main = builder.main();
+        let local = main.row_slice(0);
+        BUS.range_check(local[0], self.max_bits).eval(builder, AB::Expr::ONE);
+    }
+}
+
+fn dummy_trace(values: &[u32]) -> RowMajorMatrix<F> {
+    RowMajorMatrix::new(values.iter().copied().map(F::from_canonical_u32).collect(), 1)
+}
+
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_variable_range_checker() {
+        let chip = VariableRangeCheckerChip::new(BUS);
+        let values = [0, 1, 6, 7];
+        values.iter().for_each(|&value| chip.add_count(value, 3));
+
+        let mut interactions = check_constraints(&DummyAir { max_bits: 3 }, &dummy_trace(&values));
+        let trace = chip.generate_trace();
+        assert_eq!(trace.height(), 1 << (RANGE_MAX_BITS + 1));
+        interactions.extend(check_constraints(&chip.air, &trace));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    #[should_panic(expected = "bus 0 is unbalanced")]
+    pub fn test_variable_range_checker_out_of_range() {
+        let chip = VariableRangeCheckerChip::new(BUS);
+        chip.add_count(7, 3);
+        let mut interactions = check_constraints(&DummyAir { max_bits: 3 }, &dummy_trace(&[8]));
+        interactions.extend(check_constraints(&chip.air, &chip.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    pub fn test_decompose() {
+        let chip = VariableRangeCheckerChip::new(BUS);
+        let mut limbs = [F::ONE; 4];
+        // 15 bits only need 3 limbs of 5 bits, so the last limb is checked to be zero.
+        chip.decompose(0b10110_00011_11111, 15, &mut limbs).unwrap();
+        assert_eq!(limbs, [31, 3, 22, 0].map(F::from_canonical_u32));
+
+        let mut interactions = vec![];
+        for (value, bits) in [(31, 5), (3, 5), (22, 5), (0, 0)] {
+            interactions
+                .extend(check_constraints(&DummyAir { max_bits: bits }, &dummy_trace(&[value])));
+        }
+        interactions.extend(check_constraints(&chip.air, &chip.generate_trace()));
+        verify_interactions(interactions);
+    }
+
+    #[test]
+    pub fn test_decompose_errors() {
+        let chip = VariableRangeCheckerChip::new(BUS);
+        let mut limbs = [F::ZERO; 2];
+        assert_eq!(
+            chip.decompose(0, 15, &mut limbs),
+            Err(DecomposeError::NotEnoughLimbs { len: 2, needed: 3 })
+        );
+        assert_eq!(
+            chip.decompose(1 << 10, 10, &mut limbs),
+            Err(DecomposeError::ValueOutOfRange { value: 1 << 10, bits: 10 })
+        );
+        // Nothing was recorded by the failed decompositions.
+        assert!(chip.generate_trace().values.iter().all(|&count| count == F::ZERO));
+    }
+    #[test]
+    #[should_panic(expected = "range_max_bits must be in 1..32")]
+    pub fn test_bus_zero_range_max_bits() {
+        let _ = VariableRangeCheckerBus::new(0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "range_max_bits must be in 1..32")]
+    pub fn test_bus_too_wide_range_max_bits() {
+        let _ = VariableRangeCheckerBus::new(0, 32);
+    }
+}